@@ -0,0 +1,162 @@
+"""
+Summary statistics for benchmark timing samples.
+
+Samples are filtered with a MAD (median absolute deviation) outlier test
+before the summary is computed, so a single descheduled run doesn't skew
+the mean or standard deviation.
+
+For --adaptive sampling, a bootstrap confidence interval of that median
+decides when enough samples have been taken.
+"""
+from __future__ import annotations
+
+import random
+import statistics
+from dataclasses import dataclass
+
+# Modified z-score cutoff (Iglewicz & Hoaglin); 0.6745 scales MAD to sigma
+# for normally distributed data.
+MAD_Z_THRESHOLD = 3.5
+MAD_SCALE = 0.6745
+
+# A result is flagged unstable when stddev exceeds this fraction of the median.
+UNSTABLE_CV = 0.10
+
+# Percentile bootstrap of the median. A fixed seed makes the interval a
+# function of the samples alone, so a rerun on the same samples stops at
+# the same point.
+BOOTSTRAP_RESAMPLES = 1000
+BOOTSTRAP_SEED = 0x7E57
+BOOTSTRAP_CONFIDENCE = 0.95
+
+
+@dataclass(frozen=True)
+class Summary:
+    """Statistics over the samples that survived outlier rejection."""
+    samples: int
+    outliers: int
+    mean: float
+    median: float
+    stddev: float
+    min: float
+    max: float
+
+    @property
+    def unstable(self) -> bool:
+        return self.median > 0 and self.stddev > UNSTABLE_CV * self.median
+
+
+EMPTY = Summary(samples=0, outliers=0, mean=0.0, median=0.0, stddev=0.0, min=0.0, max=0.0)
+
+
+def mad_filter(samples: list[float]) -> tuple[list[float], int]:
+    """Split off outliers, returning (kept samples, outlier count).
+
+    When the MAD is zero (at least half the samples are identical) nothing
+    is rejected, since every deviation would otherwise be infinite.
+    """
+    if len(samples) < 3:
+        return list(samples), 0
+
+    med = statistics.median(samples)
+    mad = statistics.median(abs(x - med) for x in samples)
+    if mad == 0:
+        return list(samples), 0
+
+    kept = [x for x in samples if MAD_SCALE * abs(x - med) / mad <= MAD_Z_THRESHOLD]
+    return kept, len(samples) - len(kept)
+
+
+def summarize(samples: list[float]) -> Summary:
+    """Summarize timing samples after MAD-based outlier rejection."""
+    if not samples:
+        return EMPTY
+
+    kept, outliers = mad_filter(samples)
+    return Summary(
+        samples=len(kept),
+        outliers=outliers,
+        mean=statistics.mean(kept),
+        median=statistics.median(kept),
+        stddev=statistics.stdev(kept) if len(kept) > 1 else 0.0,
+        min=min(kept),
+        max=max(kept),
+    )
+
+
+def bootstrap_median_ci(
+    samples: list[float],
+    confidence: float = BOOTSTRAP_CONFIDENCE,
+    resamples: int = BOOTSTRAP_RESAMPLES,
+    seed: int = BOOTSTRAP_SEED,
+) -> tuple[float, float]:
+    """Percentile-bootstrap confidence interval for the median of samples.
+
+    Unlike summarize, this keeps the outliers: in a bimodal distribution
+    the MAD test would reject the smaller mode, and the interval must
+    widen to cover it rather than ignore it. Each resample draws
+    len(samples) samples with replacement from a PRNG seeded with `seed`,
+    so the same samples always give the same interval.
+    """
+    if not samples:
+        return 0.0, 0.0
+    rng = random.Random(seed)
+    medians = sorted(statistics.median(rng.choices(samples, k=len(samples)))
+                     for _ in range(resamples))
+    tail = (1 - confidence) / 2
+    return medians[int(tail * (resamples - 1))], medians[-1 - int(tail * (resamples - 1))]
+
+
+def median_ci_fraction(samples: list[float]) -> float:
+    """How far the bootstrap interval reaches from the median, as a fraction
+    of it: max(median - low, high - median) / median. Zero for identical
+    samples; infinite when the median is zero but the interval isn't."""
+    median = statistics.median(samples) if samples else 0.0
+    low, high = bootstrap_median_ci(samples)
+    reach = max(median - low, high - median)
+    if median == 0:
+        return 0.0 if reach == 0 else float("inf")
+    return reach / median
+
+
+# Why adaptive sampling stopped; only the first counts as converged.
+CONVERGED = "converged"
+MAX_SAMPLES = "max samples"
+TIME_BUDGET = "time budget"
+
+
+@dataclass(frozen=True)
+class AdaptivePolicy:
+    """When --adaptive sampling stops: once there are at least min_samples
+    and the median's bootstrap interval is within `target` of it (a
+    fraction, 0.02 for 2%), or at max_samples, or once sampling has taken
+    max_seconds, whichever comes first.
+    """
+    target: float = 0.02
+    min_samples: int = 5
+    max_samples: int = 200
+    max_seconds: float = 30.0
+
+    def stop(self, samples: list[float], elapsed_s: float) -> str | None:
+        """Why to stop after these samples, or None to take another.
+
+        Convergence is checked first, so a run that converges on its last
+        allowed sample still counts as converged. The bootstrap costs
+        milliseconds, so past 20 samples it is only redone every tenth or
+        so of the count (and at max_samples) rather than after each one.
+        """
+        n = len(samples)
+        check = n >= self.min_samples and (
+            (n - self.min_samples) % max(1, n // 10) == 0 or n >= self.max_samples)
+        if check and median_ci_fraction(samples) <= self.target:
+            return CONVERGED
+        if len(samples) >= self.max_samples:
+            return MAX_SAMPLES
+        if elapsed_s >= self.max_seconds:
+            return TIME_BUDGET
+        return None
+
+    def describe(self) -> str:
+        """The policy as text, e.g. for a cache key: "adaptive 0.02 5-200 30s"."""
+        return (f"adaptive {self.target:g} {self.min_samples}-{self.max_samples} "
+                f"{self.max_seconds:g}s")
@@ -17,22 +17,21 @@ int main(void) {
         input[len++] = (uint8_t)c;
     }
 
-    uint32_t freq[256] = {0};
+    uint64_t freq[256] = {0};
 
     for (size_t i = 0; i < len; i++) {
         freq[input[i]]++;
     }
 
-    uint8_t output[256 * 5];
+    uint8_t output[256 * 9];
     size_t out_len = 0;
 
     for (int j = 0; j < 256; j++) {
         if (freq[j] > 0) {
             output[out_len++] = (uint8_t)j;
-            output[out_len++] = (uint8_t)(freq[j] & 0xFF);
-            output[out_len++] = (uint8_t)((freq[j] >> 8) & 0xFF);
-            output[out_len++] = (uint8_t)((freq[j] >> 16) & 0xFF);
-            output[out_len++] = (uint8_t)((freq[j] >> 24) & 0xFF);
+            for (int k = 0; k < 8; k++) {
+                output[out_len++] = (uint8_t)((freq[j] >> (8 * k)) & 0xFF);
+            }
         }
     }
 
@@ -2,25 +2,33 @@
 #include <stdio.h>
 
 int main(void) {
-    uint32_t n;
-    if (fread(&n, sizeof(uint32_t), 1, stdin) != 1) {
+    /* n is a u64 LE; a 4-byte u32 LE input is still accepted. */
+    uint8_t buf[8] = {0};
+    size_t got = fread(buf, 1, sizeof(buf), stdin);
+    if (got < 4) {
         return 1;
     }
+    size_t width = got < 8 ? 4 : 8;
+    uint64_t n = 0;
+    for (size_t i = 0; i < width; i++) {
+        n |= (uint64_t)buf[i] << (8 * i);
+    }
 
-    uint32_t result;
+    /* F(n) mod 2^64: unsigned arithmetic wraps. */
+    uint64_t result;
     if (n < 2) {
         result = n;
     } else {
-        uint32_t a = 0;
-        uint32_t b = 1;
-        for (uint32_t i = 2; i <= n; i++) {
-            uint32_t tmp = a + b;
+        uint64_t a = 0;
+        uint64_t b = 1;
+        for (uint64_t i = 2; i <= n; i++) {
+            uint64_t tmp = a + b;
             a = b;
             b = tmp;
         }
         result = b;
     }
 
-    fwrite(&result, sizeof(uint32_t), 1, stdout);
+    fwrite(&result, sizeof(uint64_t), 1, stdout);
     return 0;
 }
@@ -4,6 +4,19 @@
 #include <string.h>
 #include <regex.h>
 
+/* Malformed input: one-line diagnostic, no stdout, exit 2 (matches rust_cargo/proto). */
+static int malformed(const char *msg) {
+    fprintf(stderr, "malformed input: %s\n", msg);
+    return 2;
+}
+
+/* Length of the UTF-8 sequence starting at text[i], clamped to the text. */
+static size_t utf8_char_len(const char *text, size_t i, size_t len) {
+    unsigned char b = (unsigned char)text[i];
+    size_t n = b >= 0xF0 ? 4 : b >= 0xE0 ? 3 : b >= 0xC0 ? 2 : 1;
+    return n < len - i ? n : len - i;
+}
+
 int main(void) {
     uint8_t *input = NULL;
     size_t capacity = 0;
@@ -19,20 +32,16 @@ int main(void) {
     }
 
     if (len < 4) {
-        uint32_t result = 0;
-        fwrite(&result, sizeof(uint32_t), 1, stdout);
         free(input);
-        return 0;
+        return malformed("truncated header");
     }
 
     uint32_t pat_len;
     memcpy(&pat_len, input, 4);
 
-    if (4 + pat_len > len) {
-        uint32_t result = 0;
-        fwrite(&result, sizeof(uint32_t), 1, stdout);
+    if ((size_t)pat_len > len - 4) {
         free(input);
-        return 0;
+        return malformed("pattern length past end of input");
     }
 
     char *pattern = malloc(pat_len + 1);
@@ -46,25 +55,48 @@ int main(void) {
 
     regex_t regex;
     int ret = regcomp(&regex, pattern, REG_EXTENDED);
+    if (ret != 0) {
+        free(pattern);
+        free(text);
+        free(input);
+        return malformed("invalid pattern");
+    }
 
-    uint32_t count = 0;
-    if (ret == 0) {
-        regmatch_t match;
-        const char *cursor = text;
-        while (regexec(&regex, cursor, 1, &match, 0) == 0) {
-            count++;
-            if (match.rm_eo == 0) {
-                cursor++;
-                if (*cursor == '\0') break;
-            } else {
-                cursor += match.rm_eo;
-            }
-            if (*cursor == '\0') break;
+    /*
+     * Non-overlapping matches, counted the way the Rust regex crate's
+     * find_iter does: an empty match may occur at any UTF-8 character
+     * boundary, including the end of the text, except directly at the end
+     * of the previous match.
+     */
+    uint64_t count = 0;
+    regmatch_t match;
+    size_t pos = 0;
+    size_t last_end = (size_t)-1;
+    while (pos <= text_len &&
+           regexec(&regex, text + pos, 1, &match, pos > 0 ? REG_NOTBOL : 0) == 0) {
+        size_t start = pos + (size_t)match.rm_so;
+        size_t end = pos + (size_t)match.rm_eo;
+        if (start == end && start == last_end) {
+            if (start >= text_len) break;
+            pos = start + utf8_char_len(text, start, text_len);
+            continue;
+        }
+        count++;
+        last_end = end;
+        if (start == end) {
+            if (end >= text_len) break;
+            pos = end + utf8_char_len(text, end, text_len);
+        } else {
+            pos = end;
         }
-        regfree(&regex);
     }
+    regfree(&regex);
 
-    fwrite(&count, sizeof(uint32_t), 1, stdout);
+    uint8_t out[8];
+    for (int k = 0; k < 8; k++) {
+        out[k] = (uint8_t)((count >> (8 * k)) & 0xFF);
+    }
+    fwrite(out, 1, sizeof(out), stdout);
 
     free(pattern);
     free(text);
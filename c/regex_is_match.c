@@ -4,6 +4,12 @@
 #include <string.h>
 #include <regex.h>
 
+/* Malformed input: one-line diagnostic, no stdout, exit 2 (matches rust_cargo/proto). */
+static int malformed(const char *msg) {
+    fprintf(stderr, "malformed input: %s\n", msg);
+    return 2;
+}
+
 int main(void) {
     uint8_t *input = NULL;
     size_t capacity = 0;
@@ -19,20 +25,16 @@ int main(void) {
     }
 
     if (len < 4) {
-        uint32_t result = 0;
-        fwrite(&result, sizeof(uint32_t), 1, stdout);
         free(input);
-        return 0;
+        return malformed("truncated header");
     }
 
     uint32_t pat_len;
     memcpy(&pat_len, input, 4);
 
-    if (4 + pat_len > len) {
-        uint32_t result = 0;
-        fwrite(&result, sizeof(uint32_t), 1, stdout);
+    if ((size_t)pat_len > len - 4) {
         free(input);
-        return 0;
+        return malformed("pattern length past end of input");
     }
 
     char *pattern = malloc(pat_len + 1);
@@ -46,14 +48,17 @@ int main(void) {
 
     regex_t regex;
     int ret = regcomp(&regex, pattern, REG_EXTENDED | REG_NOSUB);
-
-    uint32_t result = 0;
-    if (ret == 0) {
-        ret = regexec(&regex, text, 0, NULL, 0);
-        result = (ret == 0) ? 1 : 0;
-        regfree(&regex);
+    if (ret != 0) {
+        free(pattern);
+        free(text);
+        free(input);
+        return malformed("invalid pattern");
     }
 
+    ret = regexec(&regex, text, 0, NULL, 0);
+    uint32_t result = (ret == 0) ? 1 : 0;
+    regfree(&regex);
+
     fwrite(&result, sizeof(uint32_t), 1, stdout);
 
     free(pattern);
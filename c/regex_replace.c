@@ -4,6 +4,12 @@
 #include <string.h>
 #include <regex.h>
 
+/* Malformed input: one-line diagnostic, no stdout, exit 2 (matches rust_cargo/proto). */
+static int malformed(const char *msg) {
+    fprintf(stderr, "malformed input: %s\n", msg);
+    return 2;
+}
+
 int main(void) {
     uint8_t *input = NULL;
     size_t capacity = 0;
@@ -19,22 +25,17 @@ int main(void) {
     }
 
     if (len < 8) {
-        fwrite(input, 1, len, stdout);
         free(input);
-        return 0;
+        return malformed("truncated header");
     }
 
     uint32_t pat_len, repl_len;
     memcpy(&pat_len, input, 4);
     memcpy(&repl_len, input + 4, 4);
 
-    if (8 + pat_len + repl_len > len) {
-        size_t text_start = 8 + pat_len + repl_len;
-        if (text_start <= len) {
-            fwrite(input + text_start, 1, len - text_start, stdout);
-        }
+    if ((size_t)pat_len > len - 8 || (size_t)repl_len > len - 8 - pat_len) {
         free(input);
-        return 0;
+        return malformed("field length past end of input");
     }
 
     char *pattern = malloc(pat_len + 1);
@@ -54,12 +55,11 @@ int main(void) {
     int ret = regcomp(&regex, pattern, REG_EXTENDED);
 
     if (ret != 0) {
-        fwrite(text, 1, text_len, stdout);
         free(pattern);
         free(replacement);
         free(text);
         free(input);
-        return 0;
+        return malformed("invalid pattern");
     }
 
     size_t out_capacity = text_len * 2 + 1024;
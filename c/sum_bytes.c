@@ -16,12 +16,17 @@ int main(void) {
         input[len++] = (uint8_t)c;
     }
 
-    uint32_t acc = 0;
+    /* u64 sum, wrapping modulo 2^64; emitted little-endian. */
+    uint64_t acc = 0;
     for (size_t i = 0; i < len; i++) {
         acc += input[i];
     }
 
-    fwrite(&acc, sizeof(uint32_t), 1, stdout);
+    uint8_t out[8];
+    for (int k = 0; k < 8; k++) {
+        out[k] = (uint8_t)((acc >> (8 * k)) & 0xFF);
+    }
+    fwrite(out, 1, sizeof(out), stdout);
 
     free(input);
     return 0;
@@ -12,19 +12,19 @@ func main() {
 		os.Exit(1)
 	}
 
-	var freq [256]uint32
+	var freq [256]uint64
 	for _, b := range input {
 		freq[b]++
 	}
 
-	out := make([]byte, 0, 256*5)
-	var tmp [4]byte
+	out := make([]byte, 0, 256*9)
+	var tmp [8]byte
 	for i, n := range freq {
 		if n == 0 {
 			continue
 		}
 		out = append(out, byte(i))
-		binary.LittleEndian.PutUint32(tmp[:], n)
+		binary.LittleEndian.PutUint64(tmp[:], n)
 		out = append(out, tmp[:]...)
 	}
 
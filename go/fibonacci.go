@@ -11,18 +11,25 @@ func main() {
 	if err != nil {
 		os.Exit(1)
 	}
-	if len(input) < 4 {
+	// n is a u64 LE; a 4-byte u32 LE input is still accepted.
+	var n uint64
+	switch {
+	case len(input) >= 8:
+		n = binary.LittleEndian.Uint64(input[:8])
+	case len(input) >= 4:
+		n = uint64(binary.LittleEndian.Uint32(input[:4]))
+	default:
 		os.Exit(1)
 	}
 
-	n := binary.LittleEndian.Uint32(input[:4])
-	var result uint32
+	// F(n) mod 2^64: unsigned arithmetic wraps.
+	var result uint64
 	if n < 2 {
 		result = n
 	} else {
-		var a uint32 = 0
-		var b uint32 = 1
-		for i := uint32(2); i <= n; i++ {
+		var a uint64 = 0
+		var b uint64 = 1
+		for i := uint64(2); i <= n; i++ {
 			tmp := a + b
 			a = b
 			b = tmp
@@ -30,8 +37,8 @@ func main() {
 		result = b
 	}
 
-	var out [4]byte
-	binary.LittleEndian.PutUint32(out[:], result)
+	var out [8]byte
+	binary.LittleEndian.PutUint64(out[:], result)
 	if _, err := os.Stdout.Write(out[:]); err != nil {
 		os.Exit(1)
 	}
@@ -12,13 +12,13 @@ func main() {
 		os.Exit(1)
 	}
 
-	var acc uint32
+	var acc uint64
 	for _, b := range input {
-		acc += uint32(b)
+		acc += uint64(b)
 	}
 
-	var out [4]byte
-	binary.LittleEndian.PutUint32(out[:], acc)
+	var out [8]byte
+	binary.LittleEndian.PutUint64(out[:], acc)
 	if _, err := os.Stdout.Write(out[:]); err != nil {
 		os.Exit(1)
 	}
@@ -0,0 +1,213 @@
+"""
+Run history in SQLite, for following results across commits of this repo.
+
+A run appends one row to `runs` (when, which commit, which host, which
+rustc), one row per implementation and size to `benchmarks`, and every
+timed sample to `samples`. Nothing is ever updated or deleted, so the
+database can be shared by runs from several checkouts and hosts.
+
+The schema is versioned: `schema_version` holds the number of MIGRATIONS
+applied, and connect() applies the rest. To change the schema, append a
+migration; never edit one that has shipped.
+"""
+from __future__ import annotations
+
+import sqlite3
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Iterable
+
+# MIGRATIONS[i] upgrades a version-i database to version i + 1, in one
+# transaction with the version bump.
+MIGRATIONS: list[tuple[str, ...]] = [
+    (
+        """CREATE TABLE runs (
+            id INTEGER PRIMARY KEY,
+            started_at TEXT NOT NULL,
+            git_commit TEXT,
+            hostname TEXT NOT NULL,
+            rustc_version TEXT,
+            config TEXT NOT NULL
+        )""",
+        """CREATE TABLE benchmarks (
+            id INTEGER PRIMARY KEY,
+            run_id INTEGER NOT NULL REFERENCES runs (id),
+            benchmark TEXT NOT NULL,
+            language TEXT NOT NULL,
+            size_kb INTEGER,
+            success INTEGER NOT NULL,
+            timed_out INTEGER NOT NULL,
+            median_time_ms REAL NOT NULL,
+            compared_time_ms REAL NOT NULL,
+            min_time_ms REAL NOT NULL,
+            max_time_ms REAL NOT NULL,
+            input_sha256 TEXT NOT NULL,
+            cached INTEGER NOT NULL
+        )""",
+        "CREATE INDEX benchmarks_by_name ON benchmarks (benchmark, run_id)",
+        """CREATE TABLE samples (
+            benchmark_id INTEGER NOT NULL REFERENCES benchmarks (id),
+            sample INTEGER NOT NULL,
+            time_ms REAL NOT NULL,
+            PRIMARY KEY (benchmark_id, sample)
+        )""",
+    ),
+]
+SCHEMA_VERSION = len(MIGRATIONS)
+
+
+class HistoryError(Exception):
+    pass
+
+
+def migrate(conn: sqlite3.Connection) -> int:
+    """Bring conn's schema up to SCHEMA_VERSION, returning the version it
+    had. conn must be in autocommit mode (isolation_level=None) so each
+    migration can run in a transaction of its own."""
+    conn.execute("CREATE TABLE IF NOT EXISTS schema_version (version INTEGER NOT NULL)")
+    row = conn.execute("SELECT version FROM schema_version").fetchone()
+    if row is None:
+        conn.execute("INSERT INTO schema_version (version) VALUES (0)")
+    found = row[0] if row is not None else 0
+    if found > SCHEMA_VERSION:
+        raise HistoryError(
+            f"history database is at schema version {found}, newer than this runner's "
+            f"{SCHEMA_VERSION}; update x07-perf-compare"
+        )
+    for version in range(found, SCHEMA_VERSION):
+        conn.execute("BEGIN IMMEDIATE")
+        try:
+            for statement in MIGRATIONS[version]:
+                conn.execute(statement)
+            conn.execute("UPDATE schema_version SET version = ?", (version + 1,))
+        except BaseException:
+            conn.execute("ROLLBACK")
+            raise
+        conn.execute("COMMIT")
+    return found
+
+
+def connect(path: Path | str) -> sqlite3.Connection:
+    """Open (creating if needed) and migrate the database at path, which
+    may be ":memory:"."""
+    try:
+        conn = sqlite3.connect(str(path), isolation_level=None)
+        conn.execute("PRAGMA foreign_keys = ON")
+        migrate(conn)
+    except sqlite3.DatabaseError as e:
+        raise HistoryError(f"{path}: {e}") from None
+    return conn
+
+
+@dataclass(frozen=True)
+class RunInfo:
+    started_at: str  # ISO 8601, UTC
+    git_commit: str | None  # None outside a git checkout
+    hostname: str
+    rustc_version: str | None  # None when rustc isn't on PATH
+    config: str  # the results file's "config", as JSON
+
+
+def insert_run(conn: sqlite3.Connection, run: RunInfo, records: Iterable[Any]) -> int:
+    """Append one run and its results, returning the run's id.
+
+    records are run_benchmarks.ResultRecords, or anything with their
+    fields; it is all one transaction, so a failed insert leaves no
+    partial run behind.
+    """
+    conn.execute("BEGIN IMMEDIATE")
+    try:
+        run_id = conn.execute(
+            "INSERT INTO runs (started_at, git_commit, hostname, rustc_version, config) "
+            "VALUES (?, ?, ?, ?, ?)",
+            (run.started_at, run.git_commit, run.hostname, run.rustc_version, run.config),
+        ).lastrowid
+        for r in records:
+            benchmark_id = conn.execute(
+                "INSERT INTO benchmarks (run_id, benchmark, language, size_kb, success, "
+                "timed_out, median_time_ms, compared_time_ms, min_time_ms, max_time_ms, "
+                "input_sha256, cached) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                (run_id, r.benchmark, r.language, r.size_kb, r.success, r.timed_out,
+                 r.median_time_ms, r.compared_time_ms, r.min_time_ms, r.max_time_ms,
+                 r.input_sha256, r.cached),
+            ).lastrowid
+            conn.executemany(
+                "INSERT INTO samples (benchmark_id, sample, time_ms) VALUES (?, ?, ?)",
+                [(benchmark_id, i, ms) for i, ms in enumerate(r.samples_ms)],
+            )
+    except BaseException:
+        conn.execute("ROLLBACK")
+        raise
+    conn.execute("COMMIT")
+    return run_id
+
+
+@dataclass(frozen=True)
+class HistoryPoint:
+    """One successful (implementation, size) result of a recorded run."""
+    run_id: int
+    started_at: str
+    git_commit: str | None
+    hostname: str
+    language: str
+    size_kb: int | None
+    median_time_ms: float
+    samples: int
+
+
+def history(
+    conn: sqlite3.Connection, benchmark: str, hostname: str | None = None
+) -> list[HistoryPoint]:
+    """benchmark's successful results, oldest run first (ties by run id),
+    optionally only those recorded on hostname."""
+    rows = conn.execute(
+        "SELECT r.id, r.started_at, r.git_commit, r.hostname, b.language, b.size_kb, "
+        "b.median_time_ms, (SELECT count(*) FROM samples s WHERE s.benchmark_id = b.id) "
+        "FROM benchmarks b JOIN runs r ON r.id = b.run_id "
+        "WHERE b.benchmark = ? AND b.success AND (? IS NULL OR r.hostname = ?) "
+        "ORDER BY r.started_at, r.id, b.language, b.size_kb",
+        (benchmark, hostname, hostname),
+    )
+    return [HistoryPoint(*row) for row in rows]
+
+
+@dataclass(frozen=True)
+class Regression:
+    language: str
+    size_kb: int | None
+    before: HistoryPoint
+    after: HistoryPoint
+
+    @property
+    def pct(self) -> float:
+        return (self.after.median_time_ms / self.before.median_time_ms - 1) * 100
+
+
+def largest_regression(points: list[HistoryPoint]) -> Regression | None:
+    """The biggest slowdown of a median from one commit to the next, or None
+    if no median ever went up.
+
+    Commits are compared within one (host, implementation, size): timings
+    from different machines aren't comparable. Consecutive runs of the same
+    commit count once, by the latest of them, and runs recorded outside a
+    git checkout are left out, since there is no commit to blame.
+    """
+    latest: dict[tuple[str, str, int | None], list[HistoryPoint]] = {}
+    for p in points:
+        if p.git_commit is None:
+            continue
+        line = latest.setdefault((p.hostname, p.language, p.size_kb), [])
+        if line and line[-1].git_commit == p.git_commit:
+            line[-1] = p
+        else:
+            line.append(p)
+
+    worst: Regression | None = None
+    for (_, language, size_kb), line in latest.items():
+        for before, after in zip(line, line[1:]):
+            if before.median_time_ms <= 0 or after.median_time_ms <= before.median_time_ms:
+                continue
+            candidate = Regression(language, size_kb, before, after)
+            if worst is None or candidate.pct > worst.pct:
+                worst = candidate
+    return worst
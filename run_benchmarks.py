@@ -8,19 +8,41 @@ across multiple languages.
 from __future__ import annotations
 
 import argparse
+import array
+import base64
+import calendar
+import cmath
+import csv
+import datetime
+import decimal
+import fnmatch
+import hashlib
+import heapq
+import html
+import ipaddress
+import itertools
 import json
+import math
 import os
 import random
+import re
 import shutil
-import statistics
+import signal
+import socket
 import struct
 import subprocess
 import sys
 import tempfile
+import threading
 import time
-from dataclasses import dataclass, field
-from pathlib import Path
-from typing import Any
+import tomllib
+from dataclasses import asdict, dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Any, Callable
+
+import history_db
+import svg_charts
+from bench_stats import CONVERGED, AdaptivePolicy, Summary, bootstrap_median_ci, summarize
 
 
 def _perf_repo_root() -> Path:
@@ -94,45 +116,236 @@ def _resolve_x07_host_runner(
     )
 
 
-def _time_bin() -> list[str] | None:
-    p = Path("/usr/bin/time")
-    if not (p.is_file() and os.access(p, os.X_OK)):
+def _rss_exec() -> Path | None:
+    """Build (once) and return tools/rss_exec, or None if RSS can't be measured.
+
+    The helper forks the benchmark, reaps it with wait4, and reports its
+    peak RSS; see tools/rss_exec.c for why the runner can't do this itself.
+    """
+    global _rss_exec_path
+    if _rss_exec_path is None:
+        _rss_exec_path = _build_rss_exec()
+    return _rss_exec_path or None
+
+
+_rss_exec_path: Path | bool | None = None
+
+
+def _build_rss_exec() -> Path | bool:
+    if not hasattr(os, "fork"):
+        return False
+    src = _perf_repo_root() / "tools" / "rss_exec.c"
+    digest = hashlib.sha256(src.read_bytes()).hexdigest()[:16]
+    exe = Path(tempfile.gettempdir()) / f"x07-perf-compare-rss_exec-{digest}"
+    if _is_executable(exe):
+        return exe
+    tmp = exe.with_name(f"{exe.name}.{os.getpid()}")
+    res = subprocess.run(["cc", "-O2", "-o", str(tmp), str(src)], capture_output=True)
+    if res.returncode != 0:
+        print("warning: could not build tools/rss_exec.c; RSS will not be measured",
+              file=sys.stderr)
+        return False
+    os.replace(tmp, exe)
+    return exe
+
+
+# Wall-clock limit for each benchmark child process; None disables it.
+DEFAULT_TIMEOUT_S = 60.0
+
+# What a run uses when neither a flag nor the benchmark's benchmarks.toml
+# entry (size, seed, samples, timeout) says otherwise.
+DEFAULT_SIZE_KB = 100
+DEFAULT_SEED = 42
+DEFAULT_SAMPLES = 5
+_child_timeout_s: float | None = DEFAULT_TIMEOUT_S
+
+
+def set_child_timeout(seconds: float | None) -> None:
+    global _child_timeout_s
+    _child_timeout_s = seconds
+
+
+# CPU every benchmark child is pinned to, and the nice increment it runs at;
+# None leaves it as the runner's. Both are inherited by anything it spawns.
+_child_cpu: int | None = None
+_child_nice: int | None = None
+
+
+def set_child_placement(cpu: int | None, nice: int | None) -> list[str]:
+    """Pin benchmark children to cpu and run them at nice, returning a
+    warning for each setting this platform can't apply (it is then left
+    unset, so the run goes ahead as it would without the flag).
+
+    Raises ValueError if cpu isn't one the runner may use, or if nice
+    can't be applied (a negative one needs root or CAP_SYS_NICE).
+    """
+    global _child_cpu, _child_nice
+    warnings = []
+    if cpu is not None and not hasattr(os, "sched_setaffinity"):
+        warnings.append(f"--pin {cpu}: CPU affinity isn't supported on {sys.platform}; "
+                        "running unpinned")
+        cpu = None
+    if cpu is not None and cpu not in os.sched_getaffinity(0):
+        allowed = _format_cpu_list(os.sched_getaffinity(0))
+        raise ValueError(f"--pin {cpu}: CPU {cpu} is not one this process may run on "
+                         f"({allowed})")
+    if nice is not None and not hasattr(os, "nice"):
+        warnings.append(f"--nice {nice}: not supported on {sys.platform}; ignoring it")
+        nice = None
+    _child_cpu, _child_nice = cpu, nice
+    if nice is not None:
+        # Try it on a throwaway child, so a missing privilege is one clear
+        # error now rather than every benchmark failing to start.
+        try:
+            subprocess.run([sys.executable, "-c", ""], preexec_fn=_place_child, check=True)
+        except (OSError, subprocess.SubprocessError):
+            _child_cpu = _child_nice = None
+            raise ValueError(f"--nice {nice}: could not set the priority (a negative one "
+                             "needs root or CAP_SYS_NICE)") from None
+    return warnings
+
+
+def child_placement() -> str:
+    """The placement set_child_placement applied, e.g. "cpu=2,nice=-5", or
+    "" for none. Part of the results cache key, since it changes timings."""
+    parts = []
+    if _child_cpu is not None:
+        parts.append(f"cpu={_child_cpu}")
+    if _child_nice is not None:
+        parts.append(f"nice={_child_nice}")
+    return ",".join(parts)
+
+
+def _place_child() -> None:
+    # Runs in the forked child before exec, so the benchmark starts placed.
+    if _child_cpu is not None:
+        os.sched_setaffinity(0, {_child_cpu})
+    if _child_nice is not None:
+        os.nice(_child_nice)
+
+
+def _parse_cpu_list(text: str) -> set[int]:
+    """CPUs from a kernel list such as "0-3,8" (/proc and sysfs format)."""
+    cpus: set[int] = set()
+    for part in text.strip().split(","):
+        if part:
+            lo, _, hi = part.partition("-")
+            cpus.update(range(int(lo), int(hi or lo) + 1))
+    return cpus
+
+
+def _format_cpu_list(cpus: set[int]) -> str:
+    """Inverse of _parse_cpu_list: {0, 1, 2, 3, 8} -> "0-3,8"."""
+    ranges: list[list[int]] = []
+    for cpu in sorted(cpus):
+        if ranges and ranges[-1][1] == cpu - 1:
+            ranges[-1][1] = cpu
+        else:
+            ranges.append([cpu, cpu])
+    return ",".join(f"{lo}-{hi}" if hi > lo else f"{lo}" for lo, hi in ranges)
+
+
+def _read_sysfs(path: str) -> str | None:
+    try:
+        return Path(path).read_text().strip()
+    except (OSError, UnicodeDecodeError):
         return None
-    if sys.platform == "darwin":
-        return [str(p), "-l"]
-    return [str(p), "-v"]
 
 
-def _parse_time_max_rss_kb(stderr: bytes) -> int:
-    txt = stderr.decode(errors="replace")
+def measurement_environment() -> dict[str, Any]:
+    """What the machine was doing that affects timings, for the results
+    JSON. Only recorded, never enforced; each value is None where it can't
+    be read (always, off Linux).
+
+    governors are the online CPUs' cpufreq governors, and governor_ok
+    whether they are all "performance". turbo is whether Intel turbo or
+    cpufreq boost is enabled, and smt whether SMT is on. smt_siblings are
+    the pinned CPU's online hyperthread siblings, which share its core and
+    so compete with the benchmark for it.
+    """
+    online = _read_sysfs("/sys/devices/system/cpu/online")
+    cpus = _parse_cpu_list(online) if online else set()
+    governors = {
+        g for g in (_read_sysfs(f"/sys/devices/system/cpu/cpu{c}/cpufreq/scaling_governor")
+                    for c in cpus)
+        if g is not None
+    }
+    no_turbo = _read_sysfs("/sys/devices/system/cpu/intel_pstate/no_turbo")
+    boost = _read_sysfs("/sys/devices/system/cpu/cpufreq/boost")
+    turbo = no_turbo == "0" if no_turbo is not None else (
+        boost == "1" if boost is not None else None)
+    smt = _read_sysfs("/sys/devices/system/cpu/smt/active")
+    siblings = None
+    if _child_cpu is not None:
+        listed = _read_sysfs(f"/sys/devices/system/cpu/cpu{_child_cpu}/topology/"
+                             "thread_siblings_list")
+        if listed is not None:
+            siblings = sorted((_parse_cpu_list(listed) & cpus) - {_child_cpu})
+    return {
+        "pin_cpu": _child_cpu,
+        "nice": _child_nice,
+        "governors": sorted(governors) or None,
+        "governor_ok": governors == {"performance"} if governors else None,
+        "turbo": turbo,
+        "smt": smt == "1" if smt is not None else None,
+        "smt_siblings": siblings,
+    }
+
+
+class BenchmarkTimeout(RuntimeError):
+    """A benchmark child process ran past the timeout and was killed."""
+
+    def __init__(self, cmd: list[str], seconds: float):
+        super().__init__(f"timed out after {seconds:g}s: {Path(cmd[0]).name}")
+        self.seconds = seconds
+
+
+def _kill_process_group(proc: subprocess.Popen) -> None:
+    try:
+        if hasattr(os, "killpg"):
+            os.killpg(proc.pid, signal.SIGKILL)
+        else:
+            proc.kill()
+    except ProcessLookupError:
+        pass
 
-    # macOS (/usr/bin/time -l): max RSS in bytes.
-    if sys.platform == "darwin":
-        for line in txt.splitlines():
-            if "maximum resident set size" not in line:
-                continue
-            parts = line.strip().split()
-            if not parts:
-                continue
-            try:
-                rss_bytes = int(parts[0])
-                return rss_bytes // 1024
-            except ValueError:
-                continue
-        return 0
 
-    # GNU time (/usr/bin/time -v): max RSS in kbytes.
-    for line in txt.splitlines():
-        if "Maximum resident set size (kbytes):" not in line:
-            continue
-        _, v = line.split(":", 1)
-        v = v.strip()
+def _run_child(
+    cmd: list[str],
+    input: bytes | str | None = None,
+    capture_output: bool = False,
+    stdout: Any = None,
+    stderr: Any = None,
+    text: bool = False,
+    cwd: str | None = None,
+    env: dict[str, str] | None = None,
+) -> subprocess.CompletedProcess:
+    """subprocess.run for benchmark programs, enforcing the child timeout.
+
+    The child gets its own process group so a timeout also kills anything
+    it spawned. Stdin is /dev/null when there is no input, so a program
+    that reads stdin can't block on the runner's terminal.
+    """
+    if capture_output:
+        stdout = stderr = subprocess.PIPE
+    with subprocess.Popen(
+        cmd,
+        stdin=subprocess.PIPE if input is not None else subprocess.DEVNULL,
+        stdout=stdout,
+        stderr=stderr,
+        text=text,
+        cwd=cwd,
+        env=env,
+        start_new_session=True,
+        preexec_fn=_place_child if child_placement() else None,
+    ) as proc:
         try:
-            return int(v)
-        except ValueError:
-            return 0
-
-    return 0
+            out, err = proc.communicate(input, timeout=_child_timeout_s)
+        except subprocess.TimeoutExpired:
+            _kill_process_group(proc)
+            proc.communicate()
+            raise BenchmarkTimeout(cmd, _child_timeout_s) from None
+    return subprocess.CompletedProcess(cmd, proc.returncode, out, err)
 
 
 def _run_with_optional_rss(
@@ -141,25 +354,28 @@ def _run_with_optional_rss(
     cwd: Path | None = None,
     env: dict[str, str] | None = None,
     measure_rss: bool = False,
-) -> tuple[subprocess.CompletedProcess[bytes], int]:
-    rss_kb = 0
-    wrapped = cmd
-    time_bin = _time_bin() if measure_rss else None
-    if time_bin:
-        wrapped = time_bin + cmd
-
-    res = subprocess.run(
-        wrapped,
-        input=input_data,
-        capture_output=True,
-        cwd=str(cwd) if cwd else None,
-        env=env,
-    )
-
-    if time_bin:
-        rss_kb = _parse_time_max_rss_kb(res.stderr)
-
-    return res, rss_kb
+) -> tuple[subprocess.CompletedProcess[bytes], int | None]:
+    """Run cmd, returning its result and peak RSS in KiB (None if not measured)."""
+    helper = _rss_exec() if measure_rss else None
+    if helper is None:
+        res = _run_child(cmd, input=input_data, capture_output=True,
+                         cwd=str(cwd) if cwd else None, env=env)
+        return res, None
+
+    with tempfile.NamedTemporaryFile(prefix="rss_", suffix=".txt", delete=False) as f:
+        rss_path = Path(f.name)
+    try:
+        res = _run_child(
+            [str(helper), str(rss_path)] + cmd,
+            input=input_data,
+            capture_output=True,
+            cwd=str(cwd) if cwd else None,
+            env=env,
+        )
+        text = rss_path.read_text().strip()
+        return res, int(text) if text else None
+    finally:
+        rss_path.unlink(missing_ok=True)
 
 
 @dataclass
@@ -168,24 +384,64 @@ class BenchmarkResult:
     language: str
     benchmark: str
     times_ms: list[float] = field(default_factory=list)
-    peak_rss_kb: int = 0
+    peak_rss_kb: int | None = None
     build_size_bytes: int = 0
     output_bytes: bytes = b""
     compile_time_ms: float = 0.0
     success: bool = True
     error: str = ""
+    timed_out: bool = False
+    source_path: str = ""
+    binary_path: str = ""
+    input_sha256: str = ""
+    input_bytes: int = 0
+    size_kb: int | None = None
+    startup_baseline_ms: float | None = None
+    cached: bool = False
+    # Whether --adaptive sampling converged; None for a fixed sample count.
+    converged: bool | None = None
+
+    @property
+    def summary(self) -> Summary:
+        return summarize(self.times_ms)
 
     @property
     def mean_time_ms(self) -> float:
-        return statistics.mean(self.times_ms) if self.times_ms else 0.0
+        return self.summary.mean
 
     @property
     def stddev_time_ms(self) -> float:
-        return statistics.stdev(self.times_ms) if len(self.times_ms) > 1 else 0.0
+        return self.summary.stddev
 
     @property
     def min_time_ms(self) -> float:
-        return min(self.times_ms) if self.times_ms else 0.0
+        return self.summary.min
+
+    @property
+    def median_time_ms(self) -> float:
+        return self.summary.median
+
+    @property
+    def max_time_ms(self) -> float:
+        return self.summary.max
+
+    @property
+    def throughput_mib_s(self) -> float | None:
+        return throughput_mib_s(self.input_bytes, self.median_time_ms)
+
+    @property
+    def adjusted_median_time_ms(self) -> float | None:
+        """Median minus this implementation's noop median, clamped at zero."""
+        if self.startup_baseline_ms is None:
+            return None
+        return max(0.0, self.median_time_ms - self.startup_baseline_ms)
+
+
+def throughput_mib_s(input_bytes: int, median_ms: float) -> float | None:
+    """Stdin bytes per second of median time, in MiB/s; None with no input or time."""
+    if input_bytes <= 0 or median_ms <= 0:
+        return None
+    return input_bytes / (1024 * 1024) / (median_ms / 1000.0)
 
 
 @dataclass
@@ -195,15 +451,1024 @@ class InputData:
     data: bytes
     size_kb: float
 
-
-def generate_input_data(benchmark: str, size_kb: int, seed: int = 42) -> InputData:
-    """Generate input data for a specific benchmark."""
+    @property
+    def sha256(self) -> str:
+        return hashlib.sha256(self.data).hexdigest()
+
+
+# Version tags that start a v2 or v3 frame (see rust_cargo/proto).
+FRAME_V2_TAG = 0xF2
+FRAME_V3_TAG = 0xF3
+
+# v3 flag bits understood by the regex benchmarks (proto::regex_flags), and
+# the regex_replace-only bit that turns off `$1` expansion.
+REGEX_FLAG_BITS = 0x0F
+REGEX_LITERAL_REPLACEMENT = 0x10
+
+
+def write_frames(
+    fields: list[bytes], payload: bytes, v2: bool = False, flags: int | None = None
+) -> bytes:
+    """Build a length-prefixed frame (the layout parsed by rust_cargo/proto).
+
+    v1 layout: one u32_le length per field, then the fields, then the payload.
+    v2 layout: FRAME_V2_TAG, then one u64_le length per field, then the same.
+    v3 layout (when flags is given): FRAME_V3_TAG, the u64_le lengths, then
+    the flags byte, then the same.
+    """
+    if flags is not None:
+        header = (bytes([FRAME_V3_TAG]) + b"".join(struct.pack("<Q", len(f)) for f in fields)
+                  + bytes([flags]))
+    elif v2:
+        header = bytes([FRAME_V2_TAG]) + b"".join(struct.pack("<Q", len(f)) for f in fields)
+    else:
+        header = b"".join(struct.pack("<I", len(f)) for f in fields)
+    return header + b"".join(fields) + payload
+
+
+def _one_of(*choices: str) -> Callable[[str], str]:
+    def convert(value: str) -> str:
+        if value not in choices:
+            raise ValueError(value)
+        return value
+    convert.__name__ = "one of " + ", ".join(choices)
+    return convert
+
+
+def regex_flags(value: str) -> int:
+    flags = int(value, 0)
+    if not 0 <= flags <= REGEX_FLAG_BITS:
+        raise ValueError(value)
+    return flags
+
+
+regex_flags.__name__ = f"flag bits 0..{REGEX_FLAG_BITS:#x}"
+
+_REGEX_FLAGS_HELP = ("v3 flags byte: 1 case-insensitive, 2 multi-line, 4 dot-all, 8 swap-greed "
+                     "(default 0: the flagless v1 layout)")
+
+SORT_DISTRIBUTIONS = ("uniform", "sorted", "reverse", "few_unique")
+
+# to_upper's, popcount's, bsearch's, heap_bench's, parse_ints', ip_parse's,
+# nqueens', collatz's, gcd_batch's, ackermann's, rot13's, bincode_roundtrip's,
+# knapsack's and lcs's leading mode bytes are indexes into these tuples.
+TO_UPPER_MODES = ("branchy", "branchless")
+POPCOUNT_MODES = ("table", "count_ones", "harley_seal")
+BSEARCH_MODES = ("std", "branchless")
+HEAP_MODES = ("std", "array")
+PARSE_INTS_MODES = ("std", "bytes")
+IP_PARSE_MODES = ("std", "fast_v4")
+NQUEENS_MODES = ("recursive", "iterative")
+COLLATZ_MODES = ("direct", "memo")
+GCD_MODES = ("euclid", "binary")
+ACKERMANN_MODES = ("recursive", "memo", "iterative")
+ROT13_MODES = ("rot13", "table")
+BINCODE_MODES = ("bincode", "manual")
+KNAPSACK_MODES = ("branchy", "branchless")
+LCS_MODES = ("bits", "naive")
+
+# Tunable distribution parameters per benchmark: name -> (parse, default, help).
+# Defaults reproduce the inputs the runner has always generated.
+FLOAT_VALUES = ("unit", "decimal", "bits")
+_FLOAT_VALUES_HELP = ("unit: uniform in [0, 1), mostly 16-17 significant digits; decimal: up to "
+                      "4 places in (-10^4, 10^4); bits: random bit patterns, every exponent")
+
+GEN_PARAMS: dict[str, dict[str, tuple[Callable[[str], Any], Any, str]]] = {
+    "word_count": {
+        "break_rate": (float, 0.1, "probability of a line break after each word"),
+    },
+    "reverse_utf8": {
+        "content": (_one_of("ascii", "mixed"), "mixed",
+                    "ASCII words, or words in 1- to 4-byte UTF-8 with emoji and combining marks"),
+    },
+    "rle_encode": {
+        "max_run": (int, 50, "run lengths are uniform in 1..max_run (mean (1+max_run)/2)"),
+    },
+    "rle_decode": {
+        "max_run": (int, 50, "pair counts are uniform in 1..max_run"),
+    },
+    "bsearch": {
+        "mode": (_one_of(*BSEARCH_MODES), "std", "search strategy selected by the leading mode byte"),
+        "haystack_mib": (int, 0, "haystack size in MiB regardless of --size, e.g. 256 so cache misses "
+                                 "dominate (default 0: the haystack fills --size)"),
+    },
+    "life": {
+        "pattern": (_one_of("soup", "glider", "blinker"), "soup",
+                    "random soup, or one glider (top left) or blinker (centre) on an empty grid"),
+        "density": (float, 0.3, "fraction of live cells in a soup"),
+        "generations": (int, 100, "generations to run"),
+    },
+    "fft": {
+        "signal": (_one_of("noise", "sine", "impulse"), "noise",
+                   "uniform complex noise in [-1, 1], e^(2 pi i m n / N) for m = N / 8 "
+                   "(one nonzero bin), or 1 at n = 0 (a flat spectrum)"),
+    },
+    "kmeans": {
+        "dims": (int, 2, "dimensions per point"),
+        "clusters": (int, 8, "Gaussian blobs the points are drawn from, and K"),
+        "iterations": (int, 10, "Lloyd iterations to run"),
+    },
+    "parse_ints": {
+        "mode": (_one_of(*PARSE_INTS_MODES), "std", "parser selected by the leading mode byte"),
+        "digits": (_one_of("short", "uniform", "long"), "uniform",
+                   "digit count per value: short 1-4, uniform 1-18, or long 19 (up to i64::MAX)"),
+        "bad_rate": (float, 0.01, "fraction of lines that fail to parse: empty, overflowing, or "
+                                  "with a stray character"),
+    },
+    "ip_parse": {
+        "mode": (_one_of(*IP_PARSE_MODES), "std", "parser selected by the leading mode byte"),
+        "v6_rate": (float, 0.3, "fraction of IPv6 lines, a tenth of them with embedded IPv4"),
+        "edge_rate": (float, 0.05, "fraction of lines from a fixed list of edge cases, most of "
+                                   "them invalid: leading zeros, octets over 255, misplaced or "
+                                   "repeated '::', too many groups, zone IDs, brackets"),
+    },
+    "parse_floats": {
+        "values": (_one_of(*FLOAT_VALUES), "unit", _FLOAT_VALUES_HELP),
+        "form": (_one_of("repr", "plain", "exponent"), "repr",
+                 "text form: Python's repr (exponent below 1e-4 or from 1e16), positional digits, "
+                 "or always an exponent"),
+        "special_rate": (float, 0.01, "fraction of lines that are infinities, NaNs, overflows, "
+                                      "or invalid"),
+    },
+    "format_floats": {
+        "values": (_one_of(*FLOAT_VALUES), "unit", _FLOAT_VALUES_HELP),
+    },
+    "popcount": {
+        "mode": (_one_of(*POPCOUNT_MODES), "table", "counting strategy selected by the leading mode byte"),
+    },
+    "trie_bench": {
+        "hit_rate": (float, 0.5, "fraction of queries that are prefixes of dictionary words; "
+                                 "the rest share a root but then leave the trie"),
+    },
+    "varint_encode": {
+        "distribution": (_one_of("small", "uniform"), "small",
+                         "small: mostly 1-2 byte varints; uniform: random u64s, mostly 10 bytes"),
+    },
+    "varint_decode": {
+        "distribution": (_one_of("small", "uniform"), "small",
+                         "small: mostly 1-2 byte varints; uniform: random u64s, mostly 10 bytes"),
+    },
+    "bigram_freq": {
+        "content": (_one_of("uniform", "low_entropy"), "uniform",
+                    "uniform: random bytes, all 65,536 pairs about equally; low_entropy: 8 byte "
+                    "values with weights halving from 1/2, so a few dozen pairs dominate"),
+    },
+    "slice_bytes": {
+        "offset": (int, None, "bytes to skip (default: a quarter of --size)"),
+        "length": (int, None, "bytes to copy, cut short at the end of the payload "
+                              "(default: half of --size)"),
+    },
+    "xor_cipher": {
+        "key_len": (int, 16, "key length in bytes (at least 1)"),
+    },
+    "csv_stats": {
+        "columns": (int, 8, "fields per record"),
+        "quote_rate": (float, 0.1, "fraction of quoted fields, each holding a comma, an escaped quote, or a newline"),
+    },
+    "bincode_roundtrip": {
+        "mode": (_one_of(*BINCODE_MODES), "bincode", "serializer selected by the leading mode byte"),
+        "records": (int, None, "record count (default: as many as fit in --size)"),
+        "name_len": (int, 16, "names are 0 to name_len characters"),
+        "max_tags": (int, 4, "tags per record are 0 to max_tags"),
+        "tag_len": (int, 8, "tags are 0 to tag_len characters"),
+    },
+    "url_parse": {
+        "query_rate": (float, 0.3, "fraction of URLs with a query string of 1-5 parameters"),
+        "fragment_rate": (float, 0.1, "fraction of URLs with a fragment"),
+        "port_rate": (float, 0.1, "fraction of URLs with an explicit port"),
+        "malformed_rate": (float, 0.05, "fraction of lines that do not parse"),
+    },
+    "datetime_parse": {
+        "min_year": (int, 1900, "earliest year of the uniform timestamps (at least 1)"),
+        "max_year": (int, 2100, "latest year (at most 9999)"),
+        "forms": (_one_of("utc", "mixed"), "utc",
+                  "utc: only YYYY-MM-DDTHH:MM:SSZ, which both versions parse; mixed: half get a "
+                  "+HH:MM/-HH:MM offset and half fractional seconds, which only the crate accepts"),
+        "invalid_rate": (float, 0.01, "fraction of invalid lines: month 13, day 32, February 29 of "
+                                      "a common year, hour 24, or truncated"),
+    },
+    "to_upper": {
+        "mode": (_one_of(*TO_UPPER_MODES), "branchy", "implementation selected by the leading mode byte"),
+        "content": (_one_of("text", "binary"), "text", "ASCII letters and punctuation, or uniform random bytes"),
+    },
+    "rot13": {
+        "mode": (_one_of(*ROT13_MODES), "rot13", "substitution selected by the leading mode byte; "
+                                                 "table is a random permutation of the 256 bytes"),
+        "content": (_one_of("text", "binary"), "text", "ASCII letters and punctuation, or uniform random bytes"),
+    },
+    "hex_decode": {
+        "case": (_one_of("lower", "upper", "mixed"), "lower", "case of the hex letters"),
+        "line_len": (int, 0, "hex digits per line; 0 for no line breaks"),
+    },
+    "bwt": {
+        "content": (_one_of("text", "random", "repeat"), "text",
+                    "words, uniform random bytes, or a 64-byte block repeated with rare changes"),
+    },
+    "bfs": {
+        "graph": (_one_of("random", "grid"), "random",
+                  "random: uniform random edges, random source and target; grid: a square "
+                  "lattice searched corner to corner, distance 2*(side-1)"),
+        "degree": (float, 8.0, "average degree of a random graph"),
+    },
+    "bloom": {
+        "hit_rate": (float, 0.5, "fraction of queries that are inserted keys; the rest are "
+                                 "absent, so only false positives count among them"),
+    },
+    "btree_bench": {
+        "keys": (_one_of("uniform", "zipf", "sequential"), "uniform",
+                 "as for hashmap_bench, but scrambled keys fall in 0..256*records/4"),
+        "lookup_rate": (float, 0.3, "fraction of records that are lookups"),
+        "range_rate": (float, 0.2, "fraction of records that are 1000-wide range sums"),
+    },
+    "hashmap_bench": {
+        "keys": (_one_of("uniform", "zipf", "sequential"), "uniform",
+                 "uniform or Zipf(1) over records/4 scrambled keys, or sequential: inserts "
+                 "of 0, 1, 2, ... in order"),
+        "lookup_rate": (float, 0.5, "fraction of records that are lookups"),
+    },
+    "dijkstra": {
+        "graph": (_one_of("random", "grid"), "random",
+                  "random: uniform random edges and weights; grid: a square lattice with "
+                  "horizontal weight 1 and vertical weight 2, corner to corner distance 3*(side-1)"),
+        "degree": (float, 8.0, "average degree of a random graph"),
+        "max_weight": (int, 1000, "random graph weights are uniform in 0..max_weight"),
+    },
+    "heap_bench": {
+        "mode": (_one_of(*HEAP_MODES), "std", "heap implementation selected by the leading mode byte"),
+        "pop_rate": (float, 0.25, "fraction of values that are pop counts"),
+        "max_pop": (int, 3, "pop counts are uniform in 1..max_pop (with the defaults the heap "
+                            "grows by a quarter of a value per value)"),
+    },
+    "huffman_encode": {
+        "skew": (float, 1.0, "Zipf exponent of the byte frequencies; 0 is uniform"),
+    },
+    "huffman_decode": {
+        "skew": (float, 1.0, "Zipf exponent of the byte frequencies before encoding; 0 is uniform"),
+    },
+    "rle2_encode": {
+        "max_run": (int, 8, "run lengths are uniform in 1..max_run"),
+    },
+    "rle2_decode": {
+        "max_run": (int, 8, "run lengths (before encoding) are uniform in 1..max_run"),
+    },
+    "fibonacci": {
+        "n": (int, None, "which Fibonacci number to compute (default: 10 per KiB of --size)"),
+    },
+    "bigint_factorial": {
+        "n": (int, None, "compute n! (default: 10 per KiB of --size, so --sweep "
+                         "100KiB,1MiB,5000KiB runs n = 1000 to 50000)"),
+    },
+    "pi_digits": {
+        "n": (int, None, "digits of pi to compute (default: 10 per KiB of --size, so --sweep "
+                         "100KiB,500KiB,1000KiB runs 1000, 5000 and 10000 digits)"),
+    },
+    "knucleotide": {
+        "line_len": (int, 60, "bases per line; 0 for one unbroken sequence"),
+    },
+    "levenshtein": {
+        "length": (int, 10000, "length of the common ancestor string"),
+        "mutation_rate": (float, 0.1, "per-byte edit probability from the common ancestor, for each string"),
+    },
+    "lcs": {
+        "mode": (_one_of(*LCS_MODES), "bits",
+                 "algorithm selected by the leading mode byte; naive is quadratic"),
+        "length": (int, 100000, "length of the common ancestor string"),
+        "mutation_rate": (float, 0.1, "per-byte edit probability from the common ancestor, for each string"),
+    },
+    "fannkuch": {
+        "n": (int, 10, "permutation length (7-12 is the useful range)"),
+    },
+    "spectralnorm": {
+        "n": (int, 1000, "vector length"),
+    },
+    "mandelbrot": {
+        "n": (int, 1000, "image width and height in pixels"),
+    },
+    "raytrace": {
+        "n": (int, 512, "image width and height in pixels"),
+    },
+    "binary_trees": {
+        "depth": (int, 14, "maximum tree depth n (at least 6 is used)"),
+    },
+    "ackermann": {
+        "mode": (_one_of(*ACKERMANN_MODES), "recursive",
+                 "evaluation selected by the leading mode byte"),
+        "m": (int, 3, "first argument, at most 3"),
+        "n": (int, None, "second argument, with A(m, n) at most 2^19, so n at most 16 for m = 3 "
+                         "(default: 4 + log2 of --size in KiB, at most 11, so 100 KiB is n = 10)"),
+    },
+    "nqueens": {
+        "mode": (_one_of(*NQUEENS_MODES), "recursive", "search selected by the leading mode byte"),
+        "n": (int, None, "board size, at most 16 (default: 8 + log2 of --size in KiB, at most "
+                         "15, so --sweep 1KiB,2KiB,...,128KiB runs N = 8 to 15)"),
+    },
+    "collatz": {
+        "mode": (_one_of(*COLLATZ_MODES), "direct", "implementation selected by the leading mode byte"),
+        "start": (int, 1, "first n (at least 1)"),
+        "count": (int, None, "how many consecutive n (default: --size in bytes)"),
+    },
+    "gcd_batch": {
+        "mode": (_one_of(*GCD_MODES), "euclid", "GCD algorithm selected by the leading mode byte"),
+        "distribution": (_one_of("uniform", "smooth"), "uniform",
+                         "uniform: random u64s, 61% coprime; smooth: products of primes up to 31, "
+                         "which nearly always share factors"),
+    },
+    "sieve": {
+        "limit": (int, None, "count primes up to this N (default: --size in bytes)"),
+    },
+    "matmul": {
+        "n": (int, 256, "matrix dimension"),
+    },
+    "knapsack": {
+        "mode": (_one_of(*KNAPSACK_MODES), "branchy",
+                 "DP update selected by the leading mode byte"),
+        "capacity": (int, 10000,
+                     "knapsack capacity, at most 2^27; the DP takes items x capacity steps"),
+        "max_weight": (int, None,
+                       "item weights are uniform in 1..max_weight (default: capacity / 10)"),
+    },
+    "maze": {
+        "width": (int, None, "maze width in cells (default: the square root of --size in bytes, "
+                             "at most 4096, so 100 KiB is 320x320 and 16 MiB 4096x4096)"),
+        "height": (int, None, "maze height in cells (default: the same as the width)"),
+    },
+    "json_count": {
+        "depth": (int, 4, "nesting depth of each top-level record"),
+        "width": (int, 4, "members per object or array"),
+    },
+    "sort_u32": {
+        "distribution": (_one_of(*SORT_DISTRIBUTIONS), "uniform", "shape of the u32 array"),
+    },
+    "sort_u32_radix": {
+        "distribution": (_one_of(*SORT_DISTRIBUTIONS), "uniform", "shape of the u32 array"),
+    },
+    "window_max": {
+        "window": (int, 1000, "window size W (at least 1)"),
+        "distribution": (_one_of("uniform", "sorted", "reverse", "sawtooth"), "uniform",
+                         "uniform: random i64s; sorted and reverse: ascending or descending, so "
+                         "the deque keeps one or W candidates; sawtooth: ramps of 2W values"),
+    },
+    "sort_lines": {
+        "min_line": (int, 0, "shortest line length in bytes"),
+        "max_line": (int, 80, "longest line length in bytes (lengths are uniform in between)"),
+    },
+    "regex_captures": {
+        "match_rate": (float, 0.05, "probability that each token of filler text is a match"),
+    },
+    "regex_is_match": {
+        "match_density": (float, None, "fraction of text characters matching [a-z] (default: 26/53)"),
+        "flags": (regex_flags, 0, _REGEX_FLAGS_HELP),
+    },
+    "regex_count": {
+        "match_density": (float, None, "fraction of text characters matching [a-z] (default: 26/53)"),
+        "flags": (regex_flags, 0, _REGEX_FLAGS_HELP),
+    },
+    "regex_grep": {
+        "needle": (str, "timeout", "word planted in matching lines"),
+        "match_rate": (float, 0.1, "fraction of lines holding the needle"),
+        "pattern": (str, None, "pattern field (default: the needle); grep_fixed reads it as a "
+                               "literal, so it only agrees with regex_grep on a plain string"),
+    },
+    "regex_replace": {
+        "match_density": (float, None, "fraction of text characters matching [a-z] (default: 26/53)"),
+        "flags": (regex_flags, 0, _REGEX_FLAGS_HELP),
+        "replacement": (str, "X", "replacement string"),
+        "replace_mode": (_one_of("expand", "literal"), "expand",
+                         "expand $1/${name}/$$ in the replacement, or insert it literally"),
+    },
+}
+# Variants read exactly the same inputs as their siblings.
+GEN_PARAMS["word_count_fast"] = GEN_PARAMS["word_count"]
+GEN_PARAMS["tokenize"] = GEN_PARAMS["word_count"]
+GEN_PARAMS["reverse_bytes"] = GEN_PARAMS["reverse_utf8"]
+GEN_PARAMS["fibonacci_fast"] = GEN_PARAMS["fibonacci"]
+GEN_PARAMS["matmul_blocked"] = GEN_PARAMS["matmul"]
+GEN_PARAMS["binary_trees_arena"] = GEN_PARAMS["binary_trees"]
+GEN_PARAMS["mandelbrot_threads"] = GEN_PARAMS["mandelbrot"]
+GEN_PARAMS["spectralnorm_threads"] = GEN_PARAMS["spectralnorm"]
+GEN_PARAMS["csv_stats_crate"] = GEN_PARAMS["csv_stats"]
+GEN_PARAMS["datetime_parse_crate"] = GEN_PARAMS["datetime_parse"]
+GEN_PARAMS["bigint_factorial_crate"] = GEN_PARAMS["bigint_factorial"]
+GEN_PARAMS["bwt_inverse"] = GEN_PARAMS["bwt"]
+GEN_PARAMS["suffix_array"] = GEN_PARAMS["bwt"]
+GEN_PARAMS["life_bitpacked"] = GEN_PARAMS["life"]
+GEN_PARAMS["hashmap_fx"] = GEN_PARAMS["hashmap_bench"]
+GEN_PARAMS["regex_is_match_bytes"] = GEN_PARAMS["regex_is_match"]
+GEN_PARAMS["regex_replace_bytes"] = GEN_PARAMS["regex_replace"]
+GEN_PARAMS["grep_fixed"] = GEN_PARAMS["regex_grep"]
+
+
+def parse_gen_params(benchmark: str, pairs: list[str]) -> dict[str, Any]:
+    """Resolve KEY=VALUE overrides against GEN_PARAMS, filling in defaults."""
+    spec = GEN_PARAMS.get(benchmark, {})
+    params = {name: default for name, (_, default, _) in spec.items()}
+    for pair in pairs:
+        key, sep, value = pair.partition("=")
+        if not sep:
+            raise ValueError(f"expected KEY=VALUE, got {pair!r}")
+        if key not in spec:
+            known = ", ".join(sorted(spec)) or "none"
+            raise ValueError(f"unknown parameter {key!r} for {benchmark} (known: {known})")
+        parse = spec[key][0]
+        try:
+            params[key] = parse(value)
+        except ValueError:
+            raise ValueError(f"{key}: expected {parse.__name__}, got {value!r}") from None
+    return params
+
+
+_SIZE_UNITS = {"": 1024, "k": 1024, "kb": 1024, "kib": 1024,
+               "m": 1024 ** 2, "mb": 1024 ** 2, "mib": 1024 ** 2,
+               "g": 1024 ** 3, "gb": 1024 ** 3, "gib": 1024 ** 3}
+
+
+def parse_size_kb(text: str) -> int:
+    """Parse a size like 100, 512KiB, or 64MiB into KiB (a bare number is KiB)."""
+    t = text.strip().lower()
+    num = t.rstrip("abcdefghijklmnopqrstuvwxyz")
+    unit = t[len(num):]
+    if not num.isdigit() or unit not in _SIZE_UNITS:
+        raise argparse.ArgumentTypeError(f"invalid size: {text!r} (e.g. 100, 512KiB, 64MiB)")
+    return int(num) * _SIZE_UNITS[unit] // 1024
+
+
+def parse_sweep(text: str) -> list[int]:
+    """Parse a comma-separated size list like "1KiB,64KiB,1MiB" into KiB."""
+    sizes = [parse_size_kb(part) for part in text.split(",") if part.strip()]
+    if not sizes:
+        raise argparse.ArgumentTypeError(f"invalid size list: {text!r}")
+    return sizes
+
+
+def format_size_kb(size_kb: int) -> str:
+    """Inverse of parse_size_kb for display: 1024 -> "1MiB", 100 -> "100KiB"."""
+    for unit, scale in (("GiB", 1024 ** 2), ("MiB", 1024)):
+        if size_kb >= scale and size_kb % scale == 0:
+            return f"{size_kb // scale}{unit}"
+    return f"{size_kb}KiB"
+
+
+def _regex_text(n: int, match_density: float | None) -> bytes:
+    alphabet = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ "
+    if match_density is None:
+        return "".join(random.choices(alphabet, k=n)).encode()
+    lower, other = alphabet[:26], alphabet[26:]
+    return "".join(
+        random.choice(lower) if random.random() < match_density else random.choice(other)
+        for _ in range(n)
+    ).encode()
+
+
+def _log_lines(n: int, needle: str, match_rate: float) -> bytes:
+    """About `n` bytes of log-like lines: a timestamp, a level and 3-12 words,
+    the needle put in place of a random word with probability `match_rate`.
+    One line in fifty is empty; the text ends with a newline."""
+    levels = ["INFO", "INFO", "INFO", "WARN", "ERROR", "DEBUG"]
+    words = ["request", "served", "user", "cache", "miss", "hit", "retry", "upstream",
+             "connection", "closed", "queue", "depth", "ms", "ok", "GET", "POST", "/api/v1",
+             "200", "404", "503"]
+    lines: list[str] = []
+    total = 0
+    while total < n:
+        if random.random() < 0.02:
+            line = ""
+        else:
+            body = random.choices(words, k=random.randint(3, 12))
+            if random.random() < match_rate:
+                body[random.randrange(len(body))] = needle
+            t = random.randrange(86400)
+            line = (f"{t // 3600:02}:{t // 60 % 60:02}:{t % 60:02} {random.choice(levels)} "
+                    + " ".join(body))
+        lines.append(line)
+        total += len(line) + 1
+    return "".join(f"{line}\n" for line in lines).encode()
+
+
+def _gcd_pairs(n: int, distribution: str) -> list[tuple[int, int]]:
+    """n (a, b) u64 pairs. One pair in a hundred is (0, x), (x, 0), (0, 0) or
+    (x, x) instead."""
+    def operand() -> int:
+        if distribution == "uniform":
+            return random.getrandbits(64)
+        v = 1
+        while True:
+            p = random.choice([2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31])
+            if v * p >= 2**64:
+                return v
+            v *= p
+
+    pairs = []
+    for _ in range(n):
+        a, b = operand(), operand()
+        if random.random() < 0.01:
+            a, b = random.choice([(0, b), (a, 0), (0, 0), (a, a)])
+        pairs.append((a, b))
+    return pairs
+
+
+def _wire_records(size: int, records: int | None, name_len: int, max_tags: int,
+                  tag_len: int) -> bytes:
+    """Records in bincode_roundtrip's wire format: `records` of them, or by
+    default as many whole ones as fit in `size` bytes. One string in ten is
+    two-byte UTF-8 characters ("é", "ß", ...) instead of ASCII."""
+    def string(max_chars: int) -> bytes:
+        alphabet = "abcdefghijklmnopqrstuvwxyz_-" if random.random() < 0.9 else "éüßñøå"
+        body = "".join(random.choices(alphabet, k=random.randint(0, max_chars))).encode()
+        return struct.pack("<I", len(body)) + body
+
+    out = bytearray()
+    count = 0
+    while count != records:
+        tags = random.randint(0, max_tags)
+        record = (struct.pack("<Qq", random.getrandbits(64), random.randint(-2**40, 2**40))
+                  + string(name_len) + struct.pack("<I", tags)
+                  + b"".join(string(tag_len) for _ in range(tags))
+                  + struct.pack("<d", random.uniform(-1e6, 1e6)))
+        if records is None and len(out) + len(record) > size:
+            break
+        out += record
+        count += 1
+    return bytes(out)
+
+
+def _mutate(ancestor: bytes, rate: float, alphabet: bytes) -> bytes:
+    """Copy `ancestor`, replacing, dropping, or inserting before each byte
+    with total probability `rate`."""
+    out = bytearray()
+    for b in ancestor:
+        if random.random() >= rate:
+            out.append(b)
+            continue
+        edit = random.randrange(3)
+        if edit == 0:
+            out.append(random.choice(alphabet))
+        elif edit == 2:
+            out.append(random.choice(alphabet))
+            out.append(b)
+    return bytes(out)
+
+
+def _packbits(data: bytes) -> bytes:
+    """rle2_encode's greedy PackBits encoding (the rule is in rust/rle2_encode.rs)."""
+    def run_at(i: int, cap: int) -> int:
+        r = 1
+        while r < cap and i + r < len(data) and data[i + r] == data[i]:
+            r += 1
+        return r
+
+    out = bytearray()
+    i = 0
+    while i < len(data):
+        r = run_at(i, 128)
+        if r >= 3:
+            out += bytes([257 - r, data[i]])
+            i += r
+            continue
+        start = i
+        i += 1
+        while i < len(data) and i - start < 128 and run_at(i, 3) < 3:
+            i += 1
+        out.append(i - start - 1)
+        out += data[start:i]
+    return bytes(out)
+
+
+def _huffman_encode(data: bytes) -> bytes:
+    """huffman_encode's output (the tie-break and code rules are in rust/huffman_encode.rs)."""
+    counts = [0] * 256
+    for b in data:
+        counts[b] += 1
+    lengths = [0] * 256
+    present = [s for s in range(256) if counts[s]]
+    if len(present) == 1:
+        lengths[present[0]] = 1
+    elif present:
+        # Heap entries are (weight, smallest symbol, symbols in the subtree).
+        heap = [(counts[s], s, [s]) for s in present]
+        heapq.heapify(heap)
+        while len(heap) > 1:
+            w1, m1, s1 = heapq.heappop(heap)
+            w2, m2, s2 = heapq.heappop(heap)
+            for s in s1 + s2:
+                lengths[s] += 1
+            heapq.heappush(heap, (w1 + w2, min(m1, m2), s1 + s2))
+
+    codes = {}
+    code, prev = 0, None
+    for s in sorted(present, key=lambda s: (lengths[s], s)):
+        if prev is not None:
+            code = (code + 1) << (lengths[s] - prev)
+        codes[s] = code
+        prev = lengths[s]
+
+    bits = "".join(format(codes[b], f"0{lengths[b]}b") for b in data)
+    bits += "0" * (-len(bits) % 8)
+    payload = int(bits, 2).to_bytes(len(bits) // 8, "big") if bits else b""
+    return bytes(lengths) + payload + struct.pack("<Q", len(data))
+
+
+def _bwt(data: bytes) -> bytes:
+    """bwt's output: primary index, then the transform without the sentinel."""
+    n = len(data) + 1
+    rank = [b + 1 for b in data] + [0]
+    sa = list(range(n))
+    k = 1
+    while True:
+        key = [(rank[i], rank[i + k] if i + k < n else 0) for i in range(n)]
+        sa.sort(key=key.__getitem__)
+        new = [0] * n
+        for j in range(1, n):
+            new[sa[j]] = new[sa[j - 1]] + (key[sa[j]] != key[sa[j - 1]])
+        rank = new
+        if rank[sa[-1]] == n - 1:
+            break
+        k *= 2
+    primary = sa.index(0)
+    return struct.pack("<Q", primary) + bytes(data[i - 1] for i in sa if i != 0)
+
+
+def _bwt_input(size: int, content: str) -> bytes:
+    if content == "random":
+        return bytes(random.randint(0, 255) for _ in range(size))
+    if content == "repeat":
+        # A 64-byte block over and over, with about one byte in 1000 changed.
+        block = bytes(random.randint(0, 255) for _ in range(64))
+        out = bytearray(block * (size // 64 + 1))[:size]
+        for _ in range(size // 1000):
+            out[random.randrange(size)] = random.randint(0, 255)
+        return bytes(out)
+    words = ["the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog", "banana"]
+    out = bytearray()
+    while len(out) < size:
+        out.extend(random.choice(words).encode())
+        out.append(10 if random.random() < 0.1 else 32)
+    return bytes(out[:size])
+
+
+def _bsearch_input(size: int, mode: str, haystack_mib: int) -> bytes:
+    """One query per 16 haystack values, at most 2^20 (each is a chain of
+    cache misses once the haystack is large): a tenth below the smallest
+    value, a tenth above the largest, 40% copies of haystack values (often
+    duplicated) and the rest anywhere in between."""
+    if haystack_mib > 0:
+        n = haystack_mib << 18
+    else:
+        n = max(0, (size - 5) // 4 * 16 // 17)
+    k = max(1, min(n // 16, 1 << 20))
+    # Gaps of 0, 1 or 2 from 1000: a third of neighbours are duplicates.
+    # Built in chunks, since a 256 MiB haystack as one list of ints won't fit.
+    haystack = array.array("I")
+    gap = bytes(i % 3 for i in range(256))
+    value = 1000
+    for start in range(0, n, 1 << 20):
+        chunk = itertools.accumulate(random.randbytes(min(1 << 20, n - start)).translate(gap),
+                                     initial=value)
+        haystack.extend(chunk)
+        value = haystack.pop()
+    if sys.byteorder != "little":
+        haystack.byteswap()
+
+    lo, hi = (haystack[0], haystack[-1]) if n else (1000, 1000)
+    queries = []
+    for _ in range(k):
+        r = random.random()
+        if r < 0.1:
+            queries.append(random.randint(0, lo - 1))
+        elif r < 0.2:
+            queries.append(random.randint(hi + 1, 0xFFFF_FFFF))
+        elif r < 0.6 and n:
+            queries.append(haystack[random.randrange(n)])
+        else:
+            queries.append(random.randint(lo, hi))
+    return (bytes([BSEARCH_MODES.index(mode)]) + struct.pack(f"<I{k}I", k, *queries)
+            + haystack.tobytes())
+
+
+def _parse_ints_text(size: int, digits: str, bad_rate: float) -> bytes:
+    """About `size` bytes of decimal lines. Values are '-' half the time and
+    '+' one time in twenty, with an occasional leading zero; a bad line is
+    one of the four failure kinds in equal shares."""
+    lines: list[str] = []
+    total = 0
+    while total < size:
+        if random.random() < bad_rate:
+            kind = random.randrange(4)
+            if kind == 0:
+                line = ""
+            elif kind == 1:
+                line = str(random.randint(2**63, 10**19))
+            elif kind == 2:
+                line = "-" + str(random.randint(2**63 + 1, 10**19))
+            else:
+                v = str(random.randint(0, 10**9))
+                i = random.randint(0, len(v))
+                line = v[:i] + random.choice("x. _") + v[i:]
+        else:
+            if digits == "long":
+                v = random.randint(10**18, 2**63 - 1)
+            else:
+                n = random.randint(1, 4 if digits == "short" else 18)
+                v = random.randint(10 ** (n - 1) if n > 1 else 0, 10**n - 1)
+            r = random.random()
+            sign = "-" if r < 0.5 else "+" if r < 0.55 else ""
+            line = sign + ("0" if random.random() < 0.01 else "") + str(v)
+        lines.append(line)
+        total += len(line) + 1
+    return "".join(f"{line}\n" for line in lines).encode()
+
+
+def _float_values(n: int, values: str) -> list[float]:
+    """n f64s of the FLOAT_VALUES kind `values`."""
+    if values == "unit":
+        return [random.random() for _ in range(n)]
+    if values == "decimal":
+        return [round(random.uniform(-1e4, 1e4), random.randint(0, 4)) for _ in range(n)]
+    return list(array.array("d", random.getrandbits(64 * n).to_bytes(8 * n, "little")))
+
+
+def _float_text(size: int, values: str, form: str, special_rate: float) -> bytes:
+    """About `size` bytes of float lines. plain and exponent write repr's
+    digits in the other notation; a special line is one of the fixed ones
+    below, a third of them invalid."""
+    specials = ["inf", "-Infinity", "NaN", "1e400", "", "1.2.3", "0x1p3"]
+    lines: list[str] = []
+    total = 0
+    while total < size:
+        if random.random() < special_rate:
+            line = random.choice(specials)
+        else:
+            v = _float_values(1, values)[0]
+            line = repr(v)
+            if form != "repr" and math.isfinite(v):
+                line = format(decimal.Decimal(line), "f" if form == "plain" else "e")
+        lines.append(line)
+        total += len(line) + 1
+    return "".join(f"{line}\n" for line in lines).encode()
+
+
+def _ip_lines(size: int, v6_rate: float, edge_rate: float) -> bytes:
+    """About `size` bytes of address lines. IPv6 is written compressed
+    (ipaddress's "::" placement) or, one time in four, fully exploded with
+    leading zeros; the edge cases are the ones in rust/ip_parse.rs."""
+    edges = ["0.0.0.0", "1.2.3.0", "255.255.255.255", "01.2.3.4", "1.2.3.04", "256.1.1.1",
+             "1.2.3", "1.2.3.4.5", "1..3.4", "4294967295", " 1.2.3.4", "", "::", "1::",
+             "1:2:3:4:5:6:7::", "::0:0:0:0:0:0:0", "1:2:3:4:5:6:7:8:9", "1:2:3:4:5:6:7::8",
+             "1::2::3", "abcd0::1", ":1::2", "1::2:", "::ffff:1.2.3.4", "::1.2.3.4",
+             "1:2:3:4:5:6:1.2.3.4", "::ffff:01.2.3.4", "1:2:3:4:5:6:7:1.2.3.4", "[::1]",
+             "fe80::1%eth0", "FE80::ABCD"]
+    lines: list[str] = []
+    total = 0
+    while total < size:
+        r = random.random()
+        if r < edge_rate:
+            line = random.choice(edges)
+        elif r < edge_rate + v6_rate:
+            if random.random() < 0.1:
+                v4 = ipaddress.IPv4Address(random.getrandbits(32))
+                line = random.choice(["::ffff:", "64:ff9b::"]) + str(v4)
+            else:
+                # Runs of zero groups, so "::" lands in different places.
+                groups = [random.getrandbits(16) if random.random() < 0.6 else 0
+                          for _ in range(8)]
+                v6 = ipaddress.IPv6Address(b"".join(g.to_bytes(2, "big") for g in groups))
+                line = v6.exploded if random.random() < 0.25 else v6.compressed
+        else:
+            line = str(ipaddress.IPv4Address(random.getrandbits(32)))
+        lines.append(line)
+        total += len(line) + 1
+    return "".join(f"{line}\n" for line in lines).encode()
+
+
+def _url_lines(size: int, query_rate: float, fragment_rate: float, port_rate: float,
+               malformed_rate: float) -> bytes:
+    """About `size` bytes of URLs: https 70% of the time, else http; one host
+    in ten an IPv4 or IPv6 literal and the rest names, some needing IDNA; one
+    URL in twenty with userinfo and one in ten with no path; 0-4 path
+    segments, some percent-encoded or non-ASCII."""
+    names = ["example.com", "www.example.org", "api.test.net", "cdn.example.co.uk",
+             "bücher.example", "münchen.example", "xn--nxasmq6b.example"]
+    segments = ["api", "v1", "users", "search", "static", "img", "docs", "a%20b", "caf%C3%A9",
+                "straße", "..", "index.html"]
+    keys = ["q", "page", "id", "lang", "sort", "utm_source", "a b"]
+    malformed = ["http://", "https://exa mple.com/", "//no-scheme.example/", "http://[::1/",
+                 "https://example.com:99999/", "not a url", ""]
+    lines: list[str] = []
+    total = 0
+    while total < size:
+        if random.random() < malformed_rate:
+            line = random.choice(malformed)
+        else:
+            r = random.random()
+            if r < 0.05:
+                host = ".".join(str(random.randrange(256)) for _ in range(4))
+            elif r < 0.1:
+                host = f"[2001:db8::{random.getrandbits(16):x}]"
+            else:
+                host = random.choice(names)
+            if random.random() < 0.05:
+                host = "user:secret@" + host
+            if random.random() < port_rate:
+                host += f":{random.randint(1, 65535)}"
+            line = ("https" if random.random() < 0.7 else "http") + "://" + host
+            if random.random() >= 0.1:
+                line += "/" + "/".join(random.choices(segments, k=random.randint(0, 4)))
+            if random.random() < query_rate:
+                line += "?" + "&".join(f"{random.choice(keys)}={random.randrange(1000)}"
+                                       for _ in range(random.randint(1, 5)))
+            if random.random() < fragment_rate:
+                line += "#" + random.choice(segments)
+        lines.append(line)
+        total += len(line.encode()) + 1
+    return "".join(f"{line}\n" for line in lines).encode()
+
+
+def _timestamps(size: int, min_year: int, max_year: int, forms: str, invalid_rate: float
+                ) -> bytes:
+    """About `size` bytes of timestamp lines, uniform over the seconds of
+    min_year through max_year. A mixed-form offset is a multiple of 15
+    minutes within 14 hours, applied to the local time written; one that
+    would leave years 1-9999 is dropped."""
+    epoch = datetime.datetime(1970, 1, 1)
+    lo = int((datetime.datetime(max(1, min_year), 1, 1) - epoch).total_seconds())
+    hi = int((datetime.datetime(min(9999, max_year), 12, 31, 23, 59, 59) - epoch).total_seconds())
+
+    def fmt(t: datetime.datetime) -> str:
+        return f"{t.year:04d}-{t.month:02d}-{t.day:02d}T{t.hour:02d}:{t.minute:02d}:{t.second:02d}"
+
+    lines: list[str] = []
+    total = 0
+    while total < size:
+        t = epoch + datetime.timedelta(seconds=random.randint(lo, hi))
+        if random.random() < invalid_rate:
+            kind = random.randrange(5)
+            if kind == 0:
+                line = f"{t.year:04d}-13{fmt(t)[7:]}Z"
+            elif kind == 1:
+                line = f"{fmt(t)[:8]}32{fmt(t)[10:]}Z"
+            elif kind == 2:
+                year = t.year
+                while calendar.isleap(year):
+                    year -= 1
+                line = f"{year:04d}-02-29{fmt(t)[10:]}Z"
+            elif kind == 3:
+                line = f"{fmt(t)[:11]}24{fmt(t)[13:]}Z"
+            else:
+                line = fmt(t)[:random.randrange(19)]
+        else:
+            line = fmt(t)
+            suffix = "Z"
+            if forms == "mixed" and random.random() < 0.5:
+                minutes = 15 * random.randint(-56, 56)
+                try:
+                    line = fmt(t + datetime.timedelta(minutes=minutes))
+                    sign = "-" if minutes < 0 else "+"
+                    suffix = f"{sign}{abs(minutes) // 60:02d}:{abs(minutes) % 60:02d}"
+                except OverflowError:
+                    pass
+            if forms == "mixed" and random.random() < 0.5:
+                line += "." + "".join(random.choices("0123456789", k=random.randint(1, 9)))
+            line += suffix
+        lines.append(line)
+        total += len(line) + 1
+    return "".join(f"{line}\n" for line in lines).encode()
+
+
+def _graph_edges(size: int, graph: str, degree: float, edge_bytes: int
+                 ) -> tuple[int, list[tuple[int, int]], int, int]:
+    """(node count, edges, source, target) filling about `size` bytes of
+    bfs-style input with `edge_bytes` per edge. A grid is side x side nodes,
+    row-major, with the edges shuffled and the far corner as target."""
+    m = max(1, (size - 16) // edge_bytes)
+    if graph == "grid":
+        side = max(1, int((m / 2) ** 0.5))
+        edges = [(r * side + c, r * side + c + 1) for r in range(side) for c in range(side - 1)]
+        edges += [(r * side + c, (r + 1) * side + c) for r in range(side - 1) for c in range(side)]
+        random.shuffle(edges)
+        return side * side, edges, 0, side * side - 1
+    n = max(1, int(2 * m / degree))
+    edges = [(random.randrange(n), random.randrange(n)) for _ in range(m)]
+    return n, edges, random.randrange(n), random.randrange(n)
+
+
+def _trie_input(size: int, hit_rate: float) -> bytes:
+    """About size/2 bytes of words, each one of 200 random roots plus a
+    random tail (so prefixes are shared and some words repeat), a blank line,
+    then queries up to `size`."""
+    letters = "abcdefghijklmnopqrstuvwxyz"
+
+    def word(lo: int, hi: int) -> str:
+        return "".join(random.choices(letters, k=random.randint(lo, hi)))
+
+    roots = [word(2, 5) for _ in range(200)]
+    words: list[str] = []
+    total = 0
+    while total < size // 2:
+        words.append(random.choice(roots) + word(0, 6))
+        total += len(words[-1]) + 1
+    queries: list[str] = []
+    total += 1
+    while total < size:
+        if random.random() < hit_rate:
+            w = random.choice(words)
+            queries.append(w[:random.randint(1, len(w))])
+        else:
+            # A digit never occurs in a word, so this prefix counts 0.
+            queries.append(random.choice(roots) + random.choice("0123456789") + word(0, 3))
+        total += len(queries[-1]) + 1
+    return ("\n".join(words) + "\n\n" + "\n".join(queries) + "\n").encode()
+
+
+def _splitmix64(x: int) -> int:
+    x = (x + 0x9E3779B97F4A7C15) & 0xFFFF_FFFF_FFFF_FFFF
+    x = ((x ^ (x >> 30)) * 0xBF58476D1CE4E5B9) & 0xFFFF_FFFF_FFFF_FFFF
+    x = ((x ^ (x >> 27)) * 0x94D049BB133111EB) & 0xFFFF_FFFF_FFFF_FFFF
+    return x ^ (x >> 31)
+
+
+def _map_records(n: int, keys: str, lookup_rate: float, range_rate: float = 0.0,
+                 key_space: int = 0) -> bytes:
+    """n (key, op) records for the map benchmarks. uniform and zipf pick
+    among n/4 ids, scrambled into u64 keys (reduced mod key_space if given);
+    sequential inserts 0, 1, 2, ... and looks up (or ranges) uniformly in
+    0..n, so lookups past the keys inserted so far miss."""
+    ids = max(1, n // 4)
+    if keys == "zipf":
+        weights = list(itertools.accumulate(1 / (rank + 1) for rank in range(ids)))
+        picks = random.choices(range(ids), cum_weights=weights, k=n)
+    else:
+        picks = [random.randrange(ids) for _ in range(n)]
+    out = bytearray()
+    next_key = 0
+    for pick in picks:
+        r = random.random()
+        op = 1 if r < lookup_rate else 2 if r < lookup_rate + range_rate else 0
+        if keys != "sequential":
+            key = _splitmix64(pick) % key_space if key_space else _splitmix64(pick)
+        elif op:
+            key = random.randrange(max(1, n))
+        else:
+            key, next_key = next_key, next_key + 1
+        out += struct.pack("<QI", key, op)
+    return bytes(out)
+
+
+def _varint_values(n: int, distribution: str) -> list[int]:
+    if distribution == "uniform":
+        return [random.getrandbits(64) for _ in range(n)]
+    # "small": bit lengths roughly exponential with mean 8, so most values
+    # fit a 1- or 2-byte varint but every length up to 10 bytes occurs.
+    return [random.getrandbits(min(64, int(random.expovariate(1 / 8)))) for _ in range(n)]
+
+
+def _varint(v: int) -> bytes:
+    out = bytearray()
+    while v >= 0x80:
+        out.append(v & 0x7F | 0x80)
+        v >>= 7
+    out.append(v)
+    return bytes(out)
+
+
+def _json_node(depth: int, width: int) -> Any:
+    if depth == 0:
+        kind = random.random()
+        if kind < 0.4:
+            return "".join(random.choices("abcdefghijklmnopqrstuvwxyz ", k=random.randint(0, 16)))
+        if kind < 0.7:
+            return random.randint(-10**6, 10**6)
+        if kind < 0.8:
+            return random.random()
+        return random.choice([True, False, None])
+    if random.random() < 0.5:
+        return {f"k{i}": _json_node(depth - 1, width) for i in range(width)}
+    return [_json_node(depth - 1, width) for _ in range(width)]
+
+
+# Reads stdin to EOF and exits; its median is the per-implementation startup
+# cost that --subtract-baseline removes.
+NOOP_BENCHMARK = "noop"
+# The generator for benchmarks whose programs take arbitrary bytes.
+RANDOM_BYTES_GENERATOR = "bytes"
+
+
+def generate_input_data(
+    benchmark: str, size_kb: int, seed: int = 42, params: dict[str, Any] | None = None
+) -> InputData:
+    """Generate input data for a specific benchmark.
+
+    `benchmark` names the generator: a benchmark's own format, or
+    RANDOM_BYTES_GENERATOR for programs that take any bytes. Output depends
+    only on (benchmark, size_kb, seed, params), so the same arguments always
+    produce the same bytes. Raises ValueError for an unknown generator.
+    """
     random.seed(seed)
     size = size_kb * 1024
+    p = parse_gen_params(benchmark, [])
+    p.update(params or {})
 
-    if benchmark == "sum_bytes":
+    if benchmark in ("sum_bytes", "sum_bytes_scalar"):
         data = bytes(random.randint(0, 255) for _ in range(size))
-    elif benchmark == "word_count":
+    elif benchmark in ("word_count", "word_count_fast", "tokenize"):
         words = ["the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog",
                  "hello", "world", "python", "rust", "code", "test", "benchmark"]
         out = bytearray()
@@ -213,44 +1478,1044 @@ def generate_input_data(benchmark: str, size_kb: int, seed: int = 42) -> InputDa
                 out.append(32)
             out.extend(random.choice(words).encode())
             first = False
-            if random.random() < 0.1:
+            if random.random() < p["break_rate"]:
                 if len(out) >= size:
                     break
                 out.append(32)
                 out.append(10)
         data = bytes(out[:size])
+    elif benchmark == "word_count_unicode":
+        # UTF-8 text mixing ASCII separators with Unicode whitespace
+        # (NBSP, VT, FF, EM SPACE, IDEOGRAPHIC SPACE) and non-ASCII words.
+        words = ["the", "quick", "brown", "fox", "café", "straße", "naïve", "日本語",
+                 "hello", "world", "привет", "benchmark"]
+        separators = [" ", " ", " ", "\n", "\t", "\u00a0", "\u000b", "\u000c",
+                      "\u2003", "\u3000"]
+        out = bytearray()
+        while len(out) < size:
+            out.extend(random.choice(words).encode())
+            out.extend(random.choice(separators).encode())
+        data = bytes(out[:size])
+    elif benchmark in ("reverse_bytes", "reverse_utf8"):
+        # Words and spaces, cut back to the last whole character. mixed adds
+        # 2- to 4-byte characters (U+1F600 and U+1F680 among them) and words
+        # ending in U+0301 COMBINING ACUTE ACCENT.
+        words = ["the", "quick", "brown", "fox", "hello", "world", "benchmark"]
+        if p["content"] == "mixed":
+            words += ["café", "straße", "привет", "日本語", "e\u0301t\u0301e\u0301",
+                      "\U0001f600", "\U0001f680\U0001f30d"]
+        out = bytearray()
+        while len(out) < size:
+            out.extend(random.choice(words).encode())
+            out.extend(b"\n" if random.random() < 0.1 else b" ")
+        data = bytes(out[:size]).decode("utf-8", "ignore").encode()
     elif benchmark == "rle_encode":
         data_list = []
         while len(data_list) < size:
             byte_val = random.randint(0, 255)
-            run_len = random.randint(1, min(50, size - len(data_list)))
+            run_len = random.randint(1, min(p["max_run"], size - len(data_list)))
             data_list.extend([byte_val] * run_len)
         data = bytes(data_list[:size])
+    elif benchmark == "rle_decode":
+        # Input format: (count, byte) pairs as emitted by rle_encode
+        out = bytearray()
+        while len(out) + 2 <= size:
+            out.append(random.randint(1, p["max_run"]))
+            out.append(random.randint(0, 255))
+        data = bytes(out)
+    elif benchmark in ("bwt", "bwt_inverse", "suffix_array"):
+        # bwt_inverse gets the transform of what bwt would get.
+        data = _bwt_input(size, p["content"])
+        if benchmark == "bwt_inverse":
+            data = _bwt(data)
+    elif benchmark in ("hashmap_bench", "hashmap_fx"):
+        # Input format: 12-byte records, u64 LE key + u32 LE op (0 insert, 1 lookup)
+        data = _map_records(size // 12, p["keys"], p["lookup_rate"])
+    elif benchmark == "bfs":
+        # Input format: u32 LE N, u32 LE M, M (u, v) u32 LE pairs, source, target
+        n, edges, source, target = _graph_edges(size, p["graph"], p["degree"], 8)
+        data = struct.pack(f"<II{2 * len(edges)}III", n, len(edges),
+                           *itertools.chain.from_iterable(edges), source, target)
+    elif benchmark == "dijkstra":
+        # Input format: bfs's with a u32 LE weight after each edge's endpoints
+        n, edges, source, target = _graph_edges(size, p["graph"], p["degree"], 12)
+        if p["graph"] == "grid":
+            weights = [1 if v == u + 1 else 2 for u, v in edges]
+        else:
+            weights = [random.randint(0, p["max_weight"]) for _ in edges]
+        data = struct.pack(f"<II{3 * len(edges)}III", n, len(edges),
+                           *itertools.chain.from_iterable((u, v, w) for (u, v), w in zip(edges, weights)),
+                           source, target)
+    elif benchmark == "bloom":
+        # Input format: u32 LE N, then lines: N keys, then queries. Half the
+        # bytes are keys; absent queries use a prefix no key has.
+        keys = [f"key-{random.getrandbits(40):x}" for _ in range(max(1, size // 2 // 15))]
+        queries = [random.choice(keys) if random.random() < p["hit_rate"]
+                   else f"miss-{random.getrandbits(40):x}" for _ in range(len(keys))]
+        data = struct.pack("<I", len(keys)) + "".join(f"{line}\n" for line in keys + queries).encode()
+    elif benchmark == "btree_bench":
+        # The same records plus op 2 (range sum over [key, key + 1000)); keys
+        # fall in 0..256 * ids so a range spans about four of them.
+        n = size // 12
+        data = _map_records(n, p["keys"], p["lookup_rate"], p["range_rate"],
+                            key_space=256 * max(1, n // 4))
+    elif benchmark == "heap_bench":
+        # Input format: mode byte (see HEAP_MODES) + i64 LE values: uniform
+        # non-negative pushes, or -n to pop n times
+        values = [-random.randint(1, p["max_pop"]) if random.random() < p["pop_rate"]
+                  else random.getrandbits(63) for _ in range(size // 8)]
+        data = bytes([HEAP_MODES.index(p["mode"])]) + struct.pack(f"<{len(values)}q", *values)
+    elif benchmark in ("huffman_encode", "huffman_decode"):
+        # Bytes with Zipf(skew) frequencies over a shuffled ranking of all 256
+        # values; huffman_decode gets them encoded.
+        symbols = list(range(256))
+        random.shuffle(symbols)
+        weights = [1 / (rank + 1) ** p["skew"] for rank in range(256)]
+        data = bytes(random.choices(symbols, weights=weights, k=size))
+        if benchmark == "huffman_decode":
+            data = _huffman_encode(data)
+    elif benchmark in ("rle2_encode", "rle2_decode"):
+        # Runs uniform in 1..max_run, as for rle_encode; rle2_decode gets
+        # them already encoded, so its input is smaller than `size`.
+        out = bytearray()
+        while len(out) < size:
+            out.extend([random.randint(0, 255)] * random.randint(1, p["max_run"]))
+        data = bytes(out[:size])
+        if benchmark == "rle2_decode":
+            data = _packbits(data)
     elif benchmark == "byte_freq":
         data = bytes(random.randint(0, 255) for _ in range(size))
-    elif benchmark == "fibonacci":
-        n = min(46, size_kb * 10)
-        data = struct.pack("<I", n)
-    elif benchmark == "regex_is_match" or benchmark == "regex_count":
-        # Input format: 4 bytes (pat_len) + pattern + text
+    elif benchmark == "bigram_freq":
+        if p["content"] == "uniform":
+            data = random.getrandbits(8 * size).to_bytes(size, "little")
+        else:
+            data = bytes(random.choices(b"etaoinsr", weights=[128, 64, 32, 16, 8, 4, 2, 2], k=size))
+    elif benchmark == "slice_bytes":
+        # Input format: u64 LE offset + u64 LE length + --size random bytes
+        offset = p["offset"] if p["offset"] is not None else size // 4
+        length = p["length"] if p["length"] is not None else size // 2
+        payload = random.getrandbits(8 * size).to_bytes(size, "little")
+        data = struct.pack("<QQ", offset, length) + payload
+    elif benchmark in ("crc32", "crc32_slice8", "adler32"):
+        # Checksums: uniform random bytes
+        data = bytes(random.randint(0, 255) for _ in range(size))
+    elif benchmark == "base64_encode":
+        data = bytes(random.randint(0, 255) for _ in range(size))
+    elif benchmark == "base64_decode":
+        # Input format: padded RFC 4648 base64 text, no line breaks
+        raw = bytes(random.randint(0, 255) for _ in range(size * 3 // 4))
+        data = base64.b64encode(raw)
+    elif benchmark == "bsearch":
+        # Input format: mode byte (see BSEARCH_MODES), u32 LE K, K u32 LE
+        # queries, then the sorted u32 LE haystack
+        data = _bsearch_input(size, p["mode"], p["haystack_mib"])
+    elif benchmark in ("life", "life_bitpacked"):
+        # Input format: u32 LE width, height, generations, then 0/1 cell
+        # bytes of the largest square grid that fits
+        side = max(1, int(max(0, size - 12) ** 0.5))
+        if p["pattern"] == "soup":
+            cells = bytearray(int(random.random() < p["density"]) for _ in range(side * side))
+        else:
+            cells = bytearray(side * side)
+            shape = ([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] if p["pattern"] == "glider"
+                     else [(side // 2 - 1, side // 2), (side // 2, side // 2), (side // 2 + 1, side // 2)])
+            for x, y in shape:
+                cells[y % side * side + x % side] = 1
+        data = struct.pack("<3I", side, side, p["generations"]) + bytes(cells)
+    elif benchmark == "fft":
+        # Input format: u32 LE log2 N, then N (re, im) f64 LE pairs, for the
+        # largest power-of-two N that fits
+        k = max(0, (max(16, size - 4) // 16).bit_length() - 1)
+        n = 1 << k
+        samples = array.array("d")
+        if p["signal"] == "noise":
+            samples.extend(random.uniform(-1, 1) for _ in range(2 * n))
+        elif p["signal"] == "sine":
+            m = n // 8
+            for t in range(n):
+                z = cmath.exp(2j * math.pi * m * t / n)
+                samples.extend((z.real, z.imag))
+        else:
+            samples.extend([0.0] * (2 * n))
+            samples[0] = 1.0
+        if sys.byteorder == "big":
+            samples.byteswap()
+        data = struct.pack("<I", k) + samples.tobytes()
+    elif benchmark == "kmeans":
+        # Input format: u32 LE N, D, K, iterations, then N * D f64 LE: points
+        # around `clusters` random centres in [0, 100)^D, standard deviation 5
+        d = max(1, p["dims"])
+        n = max(0, size - 16) // (8 * d)
+        k = min(p["clusters"], n)
+        centres = [[random.uniform(0, 100) for _ in range(d)] for _ in range(max(1, p["clusters"]))]
+        coords = array.array("d")
+        for _ in range(n):
+            coords.extend(random.gauss(c, 5) for c in random.choice(centres))
+        if sys.byteorder == "big":
+            coords.byteswap()
+        data = struct.pack("<4I", n, d, k, p["iterations"]) + coords.tobytes()
+    elif benchmark == "parse_ints":
+        # Input format: mode byte (see PARSE_INTS_MODES) + newline-separated
+        # decimal lines
+        data = bytes([PARSE_INTS_MODES.index(p["mode"])]) + _parse_ints_text(
+            size, p["digits"], p["bad_rate"])
+    elif benchmark == "parse_floats":
+        # Input format: newline-separated decimal floats
+        data = _float_text(size, p["values"], p["form"], p["special_rate"])
+    elif benchmark == "format_floats":
+        # Input format: packed f64 LE values
+        values = array.array("d", _float_values(size // 8, p["values"]))
+        if sys.byteorder == "big":
+            values.byteswap()
+        data = values.tobytes()
+    elif benchmark == "popcount":
+        # Input format: mode byte (see POPCOUNT_MODES) + uniform random bytes
+        body = bytes(random.randint(0, 255) for _ in range(max(0, size - 1)))
+        data = bytes([POPCOUNT_MODES.index(p["mode"])]) + body
+    elif benchmark == "trie_bench":
+        # Input format: words, a blank line, then query prefixes, one per line
+        data = _trie_input(size, p["hit_rate"])
+    elif benchmark == "varint_encode":
+        # Input format: packed u64 LE values
+        values = _varint_values(size // 8, p["distribution"])
+        data = struct.pack(f"<{len(values)}Q", *values)
+    elif benchmark == "varint_decode":
+        # Input format: LEB128 varints, at least `size` bytes of them
+        out = bytearray()
+        while len(out) < size:
+            out.extend(b"".join(_varint(v) for v in _varint_values(1024, p["distribution"])))
+        data = bytes(out)
+    elif benchmark == "xor_cipher":
+        # Input format: 4 bytes (key_len) + key + plaintext
+        key = bytes(random.randint(0, 255) for _ in range(p["key_len"]))
+        text = bytes(random.randint(0, 255) for _ in range(max(0, size - 4 - len(key))))
+        data = write_frames([key], text)
+    elif benchmark == "ip_parse":
+        # Input format: mode byte (see IP_PARSE_MODES) + newline-separated
+        # IPv4 and IPv6 addresses
+        data = bytes([IP_PARSE_MODES.index(p["mode"])]) + _ip_lines(
+            size, p["v6_rate"], p["edge_rate"])
+    elif benchmark == "url_parse":
+        # Input format: newline-separated URLs
+        data = _url_lines(size, p["query_rate"], p["fragment_rate"], p["port_rate"],
+                          p["malformed_rate"])
+    elif benchmark in ("datetime_parse", "datetime_parse_crate"):
+        # Input format: newline-separated RFC 3339 timestamps
+        data = _timestamps(size, p["min_year"], p["max_year"], p["forms"], p["invalid_rate"])
+    elif benchmark in ("csv_stats", "csv_stats_crate"):
+        # Input format: '\n'-terminated records of `columns` fields. Quoted
+        # fields hold the cases a naive split gets wrong: ',', '""', and '\n'.
+        words = ["alpha", "beta", "gamma", "delta", "12345", "-0.5", ""]
+        specials = [",", '""', "\n"]
+        out = []
+        total = 0
+        while total < size:
+            fields = []
+            for _ in range(p["columns"]):
+                if random.random() < p["quote_rate"]:
+                    body = random.choice(words) + random.choice(specials) + random.choice(words)
+                    fields.append(f'"{body}"')
+                else:
+                    fields.append(random.choice(words))
+            line = ",".join(fields) + "\n"
+            out.append(line)
+            total += len(line)
+        data = "".join(out).encode()
+    elif benchmark == "to_upper":
+        # Input format: mode byte (0 branchy, 1 branchless) + data, either
+        # mixed-case ASCII text or uniform random bytes
+        if p["content"] == "text":
+            alphabet = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ ,.\n"
+            body = bytes(random.choices(alphabet, k=max(0, size - 1)))
+        else:
+            body = bytes(random.randint(0, 255) for _ in range(max(0, size - 1)))
+        data = bytes([TO_UPPER_MODES.index(p["mode"])]) + body
+    elif benchmark == "rot13":
+        # Input format: mode byte (see ROT13_MODES) + a 256-byte table for
+        # mode 1 + data, the same text or random bytes as to_upper's
+        head = bytes([ROT13_MODES.index(p["mode"])])
+        if p["mode"] == "table":
+            table = list(range(256))
+            random.shuffle(table)
+            head += bytes(table)
+        n = max(0, size - len(head))
+        if p["content"] == "text":
+            alphabet = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ ,.\n"
+            body = bytes(random.choices(alphabet, k=n))
+        else:
+            body = random.getrandbits(8 * n).to_bytes(n, "little")
+        data = head + body
+    elif benchmark == "hex_encode":
+        data = bytes(random.randint(0, 255) for _ in range(size))
+    elif benchmark == "hex_decode":
+        # Input format: hex digits in the requested case, optionally broken
+        # into '\n'-terminated lines
+        digits = bytes(random.randint(0, 255) for _ in range(size // 2)).hex()
+        if p["case"] == "upper":
+            digits = digits.upper()
+        elif p["case"] == "mixed":
+            digits = "".join(c.upper() if random.random() < 0.5 else c for c in digits)
+        if p["line_len"] > 0:
+            step = p["line_len"]
+            digits = "".join(digits[i:i + step] + "\n" for i in range(0, len(digits), step))
+        data = digits.encode()
+    elif benchmark == "json_count":
+        # Input format: a JSON array of nested records; slightly over `size`
+        # bytes rather than truncated, so the document stays valid
+        parts = []
+        total = 2
+        while total < size:
+            part = json.dumps(_json_node(p["depth"], p["width"]), ensure_ascii=False)
+            parts.append(part)
+            total += len(part.encode()) + 1
+        data = ("[" + ",".join(parts) + "]").encode()
+    elif benchmark in ("sort_u32", "sort_u32_radix"):
+        # Input format: packed u32 LE values
+        n = size // 4
+        dist = p["distribution"]
+        if dist == "few_unique":
+            pool = [random.getrandbits(32) for _ in range(16)]
+            values = [random.choice(pool) for _ in range(n)]
+        else:
+            values = [random.getrandbits(32) for _ in range(n)]
+        if dist == "sorted":
+            values.sort()
+        elif dist == "reverse":
+            values.sort(reverse=True)
+        data = struct.pack(f"<{n}I", *values)
+    elif benchmark == "window_max":
+        # Input format: 4 bytes (window) + packed i64 LE values
+        n = max(0, size - 4) // 8
+        dist = p["distribution"]
+        if dist == "sawtooth":
+            values = [i % (2 * p["window"]) - p["window"] for i in range(n)]
+        else:
+            values = [random.getrandbits(64) - 2**63 for _ in range(n)]
+        if dist == "sorted":
+            values.sort()
+        elif dist == "reverse":
+            values.sort(reverse=True)
+        data = struct.pack(f"<I{n}q", p["window"], *values)
+    elif benchmark == "sort_lines":
+        # Input format: '\n'-terminated lines, mostly lowercase ASCII with
+        # some bytes >= 0x80 so not every line is valid UTF-8
+        alphabet = b"abcdefghijklmnopqrstuvwxyz" * 4 + bytes(range(0x80, 0x100, 8))
+        out = bytearray()
+        while len(out) < size:
+            n = random.randint(p["min_line"], p["max_line"])
+            out.extend(random.choices(alphabet, k=n))
+            out.append(10)
+        data = bytes(out[:size])
+    elif benchmark == NOOP_BENCHMARK:
+        # Startup cost only; the input size is deliberately ignored.
+        data = b""
+    elif benchmark == "knucleotide":
+        # Input format: FASTA-style lines of bases drawn with the Benchmarks
+        # Game homo sapiens frequencies. Newlines reset the k-mer window.
+        seq = bytes(random.choices(b"ACGT", weights=[0.3029549426680, 0.1979883004921,
+                                                      0.1975473066391, 0.3015094502008], k=size))
+        if p["line_len"] > 0:
+            step = p["line_len"]
+            seq = b"\n".join(seq[i:i + step] for i in range(0, len(seq), step)) + b"\n"
+        data = seq[:size]
+    elif benchmark in ("levenshtein", "lcs"):
+        # Input format: 4 bytes (len A) + A + B. A and B are independent
+        # mutations of a common ancestor, so the distance tracks mutation_rate.
+        # The DP is quadratic, so the length is a parameter rather than --size.
+        alphabet = b"abcdefghijklmnopqrstuvwxyz"
+        ancestor = bytes(random.choices(alphabet, k=p["length"]))
+        a = _mutate(ancestor, p["mutation_rate"], alphabet)
+        b = _mutate(ancestor, p["mutation_rate"], alphabet)
+        data = write_frames([a], b)
+        if benchmark == "lcs":
+            # lcs reads a mode byte (see LCS_MODES) first.
+            data = bytes([LCS_MODES.index(p["mode"])]) + data
+    elif benchmark == "fannkuch":
+        data = struct.pack("<I", p["n"])
+    elif benchmark in ("spectralnorm", "spectralnorm_threads"):
+        data = struct.pack("<I", p["n"])
+    elif benchmark in ("mandelbrot", "mandelbrot_threads"):
+        data = struct.pack("<I", p["n"])
+    elif benchmark == "raytrace":
+        data = struct.pack("<I", p["n"])
+    elif benchmark in ("binary_trees", "binary_trees_arena"):
+        data = struct.pack("<I", p["depth"])
+    elif benchmark == "nqueens":
+        # Input format: mode byte (see NQUEENS_MODES) + u32 LE N. N follows
+        # --size like sieve's limit, one more per doubling: 100 KiB is N = 14.
+        n = p["n"] if p["n"] is not None else min(15, 8 + max(1, size_kb).bit_length() - 1)
+        data = bytes([NQUEENS_MODES.index(p["mode"])]) + struct.pack("<I", n)
+    elif benchmark == "collatz":
+        # Input format: mode byte (see COLLATZ_MODES) + u64 LE start + u64 LE
+        # count; the count scales with --size like sieve's limit.
+        count = p["count"] if p["count"] is not None else size
+        data = bytes([COLLATZ_MODES.index(p["mode"])]) + struct.pack("<QQ", p["start"], count)
+    elif benchmark == "bincode_roundtrip":
+        # Input format: mode byte (see BINCODE_MODES) + records in the wire
+        # format in rust_cargo/bincode_roundtrip
+        body = _wire_records(max(0, size - 1), p["records"], p["name_len"], p["max_tags"],
+                             p["tag_len"])
+        data = bytes([BINCODE_MODES.index(p["mode"])]) + body
+    elif benchmark == "gcd_batch":
+        # Input format: mode byte (see GCD_MODES) + packed (u64 LE, u64 LE) pairs
+        pairs = _gcd_pairs(max(0, size - 1) // 16, p["distribution"])
+        data = bytes([GCD_MODES.index(p["mode"])]) + struct.pack(
+            f"<{2 * len(pairs)}Q", *itertools.chain.from_iterable(pairs))
+    elif benchmark == "sieve":
+        # The limit scales with --size (so --sweep varies it); ~1 GiB is N = 10^9.
+        data = struct.pack("<Q", p["limit"] if p["limit"] is not None else size)
+    elif benchmark in ("matmul", "matmul_blocked"):
+        # Header only: the matrices are generated from the seed (see rust/matmul.rs).
+        data = struct.pack("<II", p["n"], seed & 0xFFFFFFFF)
+    elif benchmark == "knapsack":
+        # Input format: mode byte (see KNAPSACK_MODES) + u32 LE capacity +
+        # u32 LE item count + (u32 LE weight, u32 LE value) pairs filling
+        # --size; values are uniform in 1..1000.
+        max_weight = p["max_weight"] if p["max_weight"] is not None else max(1, p["capacity"] // 10)
+        n = max(0, size - 9) // 8
+        items = [(random.randint(1, max_weight), random.randint(1, 1000)) for _ in range(n)]
+        data = bytes([KNAPSACK_MODES.index(p["mode"])]) + struct.pack(
+            f"<II{2 * n}I", p["capacity"], n, *itertools.chain.from_iterable(items))
+    elif benchmark == "maze":
+        # Header only: the maze is carved from the seed (see rust/maze.rs).
+        width = p["width"] if p["width"] is not None else min(4096, math.isqrt(size))
+        height = p["height"] if p["height"] is not None else width
+        data = struct.pack("<III", width, height, seed & 0xFFFFFFFF)
+    elif benchmark == "ackermann":
+        # Input format: mode byte (see ACKERMANN_MODES) + u32 LE m + u32 LE n.
+        # n follows --size like nqueens' N; each step roughly quadruples the
+        # recursive mode's work for m = 3.
+        n = p["n"] if p["n"] is not None else min(11, 4 + max(1, size_kb).bit_length() - 1)
+        data = bytes([ACKERMANN_MODES.index(p["mode"])]) + struct.pack("<II", p["m"], n)
+    elif benchmark == "pi_digits":
+        # digit count as u32 LE, scaling with --size like fibonacci's n
+        data = struct.pack("<I", p["n"] if p["n"] is not None else size_kb * 10)
+    elif benchmark in ("bigint_factorial", "bigint_factorial_crate"):
+        # n as u32 LE, scaling with --size like fibonacci's
+        data = struct.pack("<I", p["n"] if p["n"] is not None else size_kb * 10)
+    elif benchmark in ("fibonacci", "fibonacci_fast"):
+        # n as u64 LE; the output wraps mod 2^64, so n is not capped.
+        n = p["n"] if p["n"] is not None else size_kb * 10
+        data = struct.pack("<Q", n)
+    elif benchmark in ("regex_is_match", "regex_is_match_bytes", "regex_count"):
+        # Input format: 4 bytes (pat_len) + pattern + text, or v3 with flags
         pattern = b"[a-z]+"
+        flags = p["flags"] or None
+        text_size = max(1, size - len(write_frames([pattern], b"", flags=flags)))
+        text = _regex_text(text_size, p["match_density"])
+        data = write_frames([pattern], text, flags=flags)
+    elif benchmark in ("regex_grep", "grep_fixed"):
+        # Input format: 4 bytes (pat_len) + pattern + newline-separated text
+        pattern = (p["needle"] if p["pattern"] is None else p["pattern"]).encode()
+        text = _log_lines(max(1, size - 4 - len(pattern)), p["needle"], p["match_rate"])
+        data = write_frames([pattern], text)
+    elif benchmark == "regex_captures":
+        # Input format: 4 bytes (pat_len) + pattern + text. Group 2 nests
+        # groups 3 and 4; group 5 is optional. Filler words never match.
+        pattern = rb"(\w+)@((\w+)\.(com|org))(:\d+)?"
+        words = ["lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing"]
         text_size = max(1, size - 4 - len(pattern))
-        text = "".join(random.choices("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ ", k=text_size)).encode()
-        data = struct.pack("<I", len(pattern)) + pattern + text
-    elif benchmark == "regex_replace":
-        # Input format: 4 bytes (pat_len) + 4 bytes (repl_len) + pattern + replacement + text
+        out = bytearray()
+        while len(out) < text_size:
+            if random.random() < p["match_rate"]:
+                token = f"{random.choice(words)}@{random.choice(words)}.{random.choice(['com', 'org'])}"
+                if random.random() < 0.5:
+                    token += f":{random.randint(1, 65535)}"
+            else:
+                token = random.choice(words)
+            out.extend(token.encode())
+            out.append(32)
+        data = write_frames([pattern], bytes(out[:text_size]))
+    elif benchmark in ("regex_replace", "regex_replace_bytes"):
+        # Input format: 4 bytes (pat_len) + 4 bytes (repl_len) + pattern + replacement + text,
+        # or v3 with flags
         pattern = b"[a-z]+"
-        replacement = b"X"
-        header_size = 4 + 4 + len(pattern) + len(replacement)
+        replacement = p["replacement"].encode()
+        if p["replace_mode"] == "literal":
+            flags = p["flags"] | REGEX_LITERAL_REPLACEMENT
+        else:
+            flags = p["flags"] or None
+        header_size = len(write_frames([pattern, replacement], b"", flags=flags))
         text_size = max(1, size - header_size)
-        text = "".join(random.choices("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ ", k=text_size)).encode()
-        data = struct.pack("<I", len(pattern)) + struct.pack("<I", len(replacement)) + pattern + replacement + text
-    else:
+        text = _regex_text(text_size, p["match_density"])
+        data = write_frames([pattern, replacement], text, flags=flags)
+    elif benchmark == RANDOM_BYTES_GENERATOR:
         data = bytes(random.randint(0, 255) for _ in range(size))
+    else:
+        raise ValueError(f"unknown input generator {benchmark!r}")
 
     return InputData(name=f"{benchmark}_{size_kb}kb", data=data, size_kb=len(data) / 1024)
 
 
+GOLDEN_SCHEMA_VERSION = "x07-perf-compare.golden@0.1.0"
+GOLDEN_MANIFEST = "golden_outputs.json"
+
+# Read size for hashing and diffing spooled outputs.
+_OUTPUT_CHUNK = 1 << 20
+
+
+@dataclass(frozen=True)
+class OutputDigest:
+    """SHA-256 of a program's stdout, spooled to `path` rather than held in memory."""
+    sha256: str
+    size: int
+    path: Path
+
+
+def _feed_stdin(pipe: Any, data: bytes) -> None:
+    try:
+        pipe.write(data)
+        pipe.close()
+    except BrokenPipeError:
+        pass  # the child exited without reading all of its input
+
+
+def _run_to_file(
+    cmd: list[str], input_data: bytes, out_path: Path, length_prefixed: bool = False
+) -> OutputDigest:
+    """Run `cmd`, hashing its stdout into `out_path` as it arrives, so an
+    output is never held in memory whole.
+
+    Enforces the child timeout and placement like _run_child. With
+    `length_prefixed` (the X07 direct ABI), stdout starts with its length as a
+    u32 LE; that header is stripped and anything past the length ignored.
+    """
+    h = hashlib.sha256()
+    size = 0
+    expected_len: int | None = None
+    header = b""
+    timed_out = threading.Event()
+    with tempfile.TemporaryFile() as err, out_path.open("wb") as out, subprocess.Popen(
+        cmd,
+        stdin=subprocess.PIPE,
+        stdout=subprocess.PIPE,
+        stderr=err,
+        start_new_session=True,
+        preexec_fn=_place_child if child_placement() else None,
+    ) as proc:
+        def on_timeout() -> None:
+            timed_out.set()
+            _kill_process_group(proc)
+
+        timer = threading.Timer(_child_timeout_s, on_timeout) if _child_timeout_s else None
+        if timer is not None:
+            timer.start()
+        feeder = threading.Thread(target=_feed_stdin, args=(proc.stdin, input_data), daemon=True)
+        feeder.start()
+        try:
+            if length_prefixed:
+                header = proc.stdout.read(4)
+                if len(header) == 4:
+                    expected_len = struct.unpack("<I", header)[0]
+            while chunk := proc.stdout.read(_OUTPUT_CHUNK):
+                if expected_len is not None:
+                    chunk = chunk[:max(0, expected_len - size)]
+                h.update(chunk)
+                out.write(chunk)
+                size += len(chunk)
+            proc.wait()
+            feeder.join()
+        finally:
+            if timer is not None:
+                timer.cancel()
+        if timed_out.is_set():
+            raise BenchmarkTimeout(cmd, _child_timeout_s)
+        if proc.returncode != 0:
+            err.seek(0)
+            raise RuntimeError(
+                f"execution failed (exit {proc.returncode}): {err.read().decode(errors='replace')}"
+            )
+    if length_prefixed and expected_len is None:
+        raise RuntimeError(f"X07 output too short: {len(header)} bytes")
+    if expected_len is not None and size < expected_len:
+        raise RuntimeError(f"X07 output truncated: expected {expected_len}, got {size}")
+    return OutputDigest(sha256=h.hexdigest(), size=size, path=out_path)
+
+
+def _x07_direct_input(input_data: bytes) -> bytes:
+    """Stdin for a compiled X07 binary: the input behind its u32 LE length."""
+    return struct.pack("<I", len(input_data)) + input_data
+
+
+def default_output_store() -> Path:
+    return Path(tempfile.gettempdir()) / "x07-perf-compare-outputs"
+
+
+# Outputs larger than this aren't kept in the output store.
+OUTPUT_STORE_MAX_FILE_BYTES = 256 * 1024 * 1024
+
+
+def store_output(store_dir: Path, digest: OutputDigest) -> None:
+    """Keep a copy of an output that matched its expected hash, under that
+    hash, so a later mismatch can be diffed against it."""
+    path = store_dir / f"{digest.sha256}.out"
+    if digest.size > OUTPUT_STORE_MAX_FILE_BYTES or path.is_file():
+        return
+    store_dir.mkdir(parents=True, exist_ok=True)
+    partial = path.with_name(f"{path.name}.{os.getpid()}.tmp")
+    shutil.copyfile(digest.path, partial)
+    os.replace(partial, path)
+
+
+def stored_output(store_dir: Path, sha256: str) -> Path | None:
+    """The stored output with this hash, if store_output kept one."""
+    path = store_dir / f"{sha256}.out"
+    return path if path.is_file() else None
+
+
+def first_difference(a: Path, b: Path) -> int | None:
+    """Offset of the first differing byte, or None if the files are identical.
+
+    If one file is a prefix of the other, the offset is the shorter length.
+    """
+    offset = 0
+    with a.open("rb") as fa, b.open("rb") as fb:
+        while True:
+            ca = fa.read(_OUTPUT_CHUNK)
+            cb = fb.read(_OUTPUT_CHUNK)
+            if ca != cb:
+                n = min(len(ca), len(cb))
+                return offset + next((i for i in range(n) if ca[i] != cb[i]), n)
+            if not ca:
+                return None
+            offset += len(ca)
+
+
+@dataclass
+class GoldenManifest:
+    """Expected output hashes, keyed by benchmark and then input SHA-256."""
+    path: Path
+    outputs: dict[str, dict[str, dict[str, Any]]] = field(default_factory=dict)
+
+    @classmethod
+    def load(cls, path: Path) -> GoldenManifest:
+        if not path.exists():
+            return cls(path=path)
+        doc = json.loads(path.read_text())
+        if doc.get("schema_version") != GOLDEN_SCHEMA_VERSION:
+            raise ValueError(f"{path}: unsupported schema_version {doc.get('schema_version')!r}")
+        return cls(path=path, outputs=doc.get("outputs", {}))
+
+    def expected(self, benchmark: str, input_sha256: str) -> dict[str, Any] | None:
+        return self.outputs.get(benchmark, {}).get(input_sha256)
+
+    def record(self, benchmark: str, input_sha256: str, digest: OutputDigest) -> None:
+        self.outputs.setdefault(benchmark, {})[input_sha256] = {
+            "sha256": digest.sha256,
+            "bytes": digest.size,
+        }
+
+    def save(self) -> None:
+        doc = {"schema_version": GOLDEN_SCHEMA_VERSION, "outputs": self.outputs}
+        self.path.write_text(json.dumps(doc, indent=2, sort_keys=True) + "\n")
+
+
+def load_input_file(path: Path) -> InputData:
+    """Load benchmark input from a file instead of generating it."""
+    data = path.read_bytes()
+    return InputData(name=path.name, data=data, size_kb=len(data) / 1024)
+
+
+def default_input_cache() -> Path:
+    return Path(tempfile.gettempdir()) / "x07-perf-compare-inputs"
+
+
+def cached_input_data(
+    cache_dir: Path, benchmark: str, size_kb: int, seed: int, params: dict[str, Any]
+) -> InputData:
+    """generate_input_data, memoized on disk by (benchmark, size, seed, params).
+
+    Entries are never invalidated; clear the cache after changing a generator.
+    """
+    params_key = hashlib.sha256(json.dumps(params, sort_keys=True).encode()).hexdigest()[:12]
+    path = cache_dir / f"{benchmark}-{size_kb}k-seed{seed}-{params_key}.bin"
+    if path.is_file():
+        return InputData(name=benchmark, data=path.read_bytes(), size_kb=size_kb)
+
+    generated = generate_input_data(benchmark, size_kb, seed=seed, params=params)
+    cache_dir.mkdir(parents=True, exist_ok=True)
+    partial = path.with_name(f"{path.name}.{os.getpid()}.tmp")
+    partial.write_bytes(generated.data)
+    os.replace(partial, path)
+    return generated
+
+
+def discover_benchmarks(perf_repo_root: Path) -> list[str]:
+    """Every benchmark in benchmarks.toml; a program no entry names isn't run.
+
+    Sorted, except that a manifest group is listed together where its
+    alphabetically first member would be.
+    """
+    manifest = load_manifest(perf_repo_root)
+    names = sorted(manifest)
+    ordered: list[str] = []
+    for name in names:
+        if name in ordered:
+            continue
+        ordered.append(name)
+        group = manifest[name].get("group")
+        if group is not None:
+            ordered += [m for m in names if m != name and manifest[m].get("group") == group]
+    return ordered
+
+
+# Globs for the programs in the source directories; validate-manifest
+# reports any that no entry names.
+SOURCE_PROGRAMS = [
+    "x07/*.x07.json",
+    "projects/*/src/*.x07.json",
+    "c/*.c",
+    "rust/*.rs",
+    # Only binary crates are benchmarks; library crates (e.g. proto) are not.
+    "rust_cargo/*/src/main.rs",
+    "go/*.go",
+]
+
+
+BENCHMARK_MANIFEST = "benchmarks.toml"
+
+
+class ManifestError(ValueError):
+    """A benchmarks.toml that doesn't parse, or an entry the runner can't use."""
+
+
+def _is_int(v: Any) -> bool:
+    return isinstance(v, int) and not isinstance(v, bool)
+
+
+def _is_number(v: Any) -> bool:
+    return _is_int(v) or isinstance(v, float)
+
+
+def _is_program_path(v: Any) -> bool:
+    if not isinstance(v, str) or not v:
+        return False
+    path = PurePosixPath(v)
+    return not path.is_absolute() and ".." not in path.parts
+
+
+def _is_size(v: Any) -> bool:
+    if _is_int(v):
+        return v >= 0
+    try:
+        parse_size_kb(v)
+    except (argparse.ArgumentTypeError, AttributeError):
+        return False
+    return True
+
+
+# Every key a benchmarks.toml entry may have: (what it must be, check).
+MANIFEST_FIELDS: dict[str, tuple[str, Callable[[Any], bool]]] = {
+    "notes": ("a string", lambda v: isinstance(v, str)),
+    "kind": ('"latency" or "throughput"', lambda v: v in ("latency", "throughput")),
+    "compare_to": ("a benchmark name", lambda v: isinstance(v, str)),
+    "diff_allowed": ("a string", lambda v: isinstance(v, str)),
+    "diff_epsilon": ("a non-negative number", lambda v: _is_number(v) and v >= 0),
+    "diff_byte_tolerance": ("a non-negative integer", lambda v: _is_int(v) and v >= 0),
+    "source": ("a benchmark name", lambda v: isinstance(v, str)),
+    "gen_params": ("a table of KEY = VALUE", lambda v: isinstance(v, dict)),
+    "group": ("a string", lambda v: isinstance(v, str)),
+    "tags": ("a list of strings",
+             lambda v: isinstance(v, list) and all(isinstance(t, str) for t in v)),
+    "size": ('a size such as 100 (KiB) or "1MiB"', _is_size),
+    "seed": ("an integer", _is_int),
+    "samples": ("a positive integer", lambda v: _is_int(v) and v > 0),
+    "timeout": ("a number of seconds, 0 for none", lambda v: _is_number(v) and v >= 0),
+    "x07": ("a path under the repo root", _is_program_path),
+    "c": ("a path under the repo root", _is_program_path),
+    "rust": ("a path under the repo root", _is_program_path),
+    "go": ("a path under the repo root", _is_program_path),
+    "generator": ("an input generator name", lambda v: isinstance(v, str) and v != ""),
+    "output_sha256": ("a lowercase hex SHA-256",
+                      lambda v: isinstance(v, str) and re.fullmatch("[0-9a-f]{64}", v) is not None),
+}
+
+# The manifest key holding each language's program, in the order a run
+# builds them.
+IMPLEMENTATION_KEYS = {"X07": "x07", "C": "c", "Rust": "rust", "Go": "go"}
+
+
+def _manifest_line(lines: list[str], benchmark: str, key: str | None = None) -> int | None:
+    """The 1-based line of benchmark's [benchmarks.NAME] header, or of `key`
+    within that entry (the header if the key isn't on a line of its own)."""
+    header = f"[benchmarks.{benchmark}]"
+    for i, line in enumerate(lines):
+        if line.strip() != header:
+            continue
+        for j in range(i + 1, len(lines) if key is not None else i + 1):
+            stripped = lines[j].strip()
+            if stripped.startswith("["):
+                break
+            if stripped.partition("=")[0].strip() == key:
+                return j + 1
+        return i + 1
+    return None
+
+
+def manifest_error(path: Path, lines: list[str], benchmark: str, key: str | None,
+                   message: str) -> ManifestError:
+    """A ManifestError located at benchmark's entry (or its key), quoting the line."""
+    where = f"benchmarks.{benchmark}" + (f".{key}" if key is not None else "")
+    line = _manifest_line(lines, benchmark, key)
+    if line is None:
+        return ManifestError(f"{path.name}: {where}: {message}")
+    return ManifestError(f"{path.name}:{line}: {where}: {message}\n  {lines[line - 1].strip()}")
+
+
+def load_manifest(perf_repo_root: Path) -> dict[str, dict[str, Any]]:
+    """Per-benchmark metadata from benchmarks.toml, keyed by benchmark name.
+
+    Raises ManifestError, with the offending line, for TOML that doesn't
+    parse, for entries with unknown keys or values of the wrong type, and
+    for entries that name no programs, either their own or a source's.
+    """
+    path = perf_repo_root / BENCHMARK_MANIFEST
+    if not path.exists():
+        return {}
+    text = path.read_text()
+    try:
+        entries = tomllib.loads(text).get("benchmarks", {})
+    except tomllib.TOMLDecodeError as e:
+        lines = text.splitlines()
+        at = re.search(r"at line (\d+)", str(e))
+        if at is None or not 0 < int(at.group(1)) <= len(lines):
+            raise ManifestError(f"{path.name}: {e}") from None
+        raise ManifestError(f"{path.name}: {e}\n  {lines[int(at.group(1)) - 1].strip()}") from None
+    lines = text.splitlines()
+    for name, entry in entries.items():
+        if not isinstance(entry, dict):
+            raise manifest_error(path, lines, name, None, "expected a [benchmarks.NAME] table")
+        for key, value in entry.items():
+            if key not in MANIFEST_FIELDS:
+                known = ", ".join(MANIFEST_FIELDS)
+                raise manifest_error(path, lines, name, key, f"unknown key (known: {known})")
+            what, check = MANIFEST_FIELDS[key]
+            if not check(value):
+                raise manifest_error(path, lines, name, key, f"expected {what}, got {value!r}")
+        programs = [key for key in IMPLEMENTATION_KEYS.values() if key in entry]
+        if "source" in entry and programs:
+            raise manifest_error(path, lines, name, programs[0],
+                                 "an entry with a source runs the source's programs")
+        if "source" not in entry and not programs:
+            keys = ", ".join(IMPLEMENTATION_KEYS.values())
+            raise manifest_error(path, lines, name, None, f"no programs: set one of {keys}, "
+                                                          "or a source")
+    return entries
+
+
+def is_latency_benchmark(manifest: dict[str, dict[str, Any]], benchmark: str) -> bool:
+    """Whether a benchmark is reported in microseconds rather than MiB/s."""
+    return manifest.get(benchmark, {}).get("kind") == "latency"
+
+
+def benchmark_tags(manifest: dict[str, dict[str, Any]], benchmark: str) -> list[str]:
+    """The manifest entry's tags, then "latency" or "throughput" from its kind."""
+    kind = "latency" if is_latency_benchmark(manifest, benchmark) else "throughput"
+    return list(manifest.get(benchmark, {}).get("tags", [])) + [kind]
+
+
+def _comma_list(text: str) -> list[str]:
+    return [part.strip() for part in text.split(",") if part.strip()]
+
+
+def add_selection_args(ap: argparse.ArgumentParser) -> None:
+    """The --filter/--tag/--exclude/--exclude-tag flags select_benchmarks reads."""
+    ap.add_argument("--filter", dest="filters", type=_comma_list, action="extend", default=[],
+                    metavar="GLOBS",
+                    help="Only benchmarks whose name matches one of these globs, "
+                         "e.g. 'regex_*,*sort*' (repeatable)")
+    ap.add_argument("--tag", dest="tags", type=_comma_list, action="extend", default=[],
+                    metavar="TAGS",
+                    help="Only benchmarks with one of these benchmarks.toml tags (repeatable)")
+    ap.add_argument("--exclude", dest="exclude", type=_comma_list, action="extend", default=[],
+                    metavar="GLOBS", help="Skip benchmarks whose name matches one of these globs")
+    ap.add_argument("--exclude-tag", dest="exclude_tags", type=_comma_list, action="extend",
+                    default=[], metavar="TAGS", help="Skip benchmarks with one of these tags")
+
+
+def select_benchmarks(
+    benchmarks: list[str], manifest: dict[str, dict[str, Any]], args: argparse.Namespace
+) -> list[str]:
+    """The benchmarks, in order, that pass add_selection_args' flags.
+
+    A benchmark is kept if it matches any --filter glob and has any --tag
+    (each check skipped when its flag is absent), and matches no --exclude
+    glob and has no --exclude-tag. Raises ValueError when nothing is left,
+    since a filter that matches nothing is almost always a typo.
+    """
+    def named(b: str, globs: list[str]) -> bool:
+        return any(fnmatch.fnmatchcase(b, g) for g in globs)
+
+    def tagged(b: str, tags: list[str]) -> bool:
+        return any(t in tags for t in benchmark_tags(manifest, b))
+
+    selected = [
+        b for b in benchmarks
+        if (not args.filters or named(b, args.filters))
+        and (not args.tags or tagged(b, args.tags))
+        and not named(b, args.exclude)
+        and not tagged(b, args.exclude_tags)
+    ]
+    if not selected:
+        flags = [f"{flag} {','.join(values)}" for flag, values in (
+            ("--filter", args.filters), ("--tag", args.tags),
+            ("--exclude", args.exclude), ("--exclude-tag", args.exclude_tags),
+        ) if values]
+        raise ValueError(f"no benchmarks match {' '.join(flags) or 'the selection'}")
+    return selected
+
+
+def benchmark_source(manifest: dict[str, dict[str, Any]], benchmark: str) -> str:
+    """The benchmark whose programs and input generator `benchmark` uses."""
+    return manifest.get(benchmark, {}).get("source", benchmark)
+
+
+def benchmark_generator(manifest: dict[str, dict[str, Any]], benchmark: str) -> str:
+    """The input generator `benchmark` uses: its entry's generator, else its
+    source's, else the one named after its source."""
+    source = benchmark_source(manifest, benchmark)
+    for name in (benchmark, source):
+        if "generator" in manifest.get(name, {}):
+            return manifest[name]["generator"]
+    return source
+
+
+def resolve_gen_params(
+    manifest: dict[str, dict[str, Any]], benchmark: str, pairs: list[str]
+) -> dict[str, Any]:
+    """parse_gen_params for `benchmark`'s generator, with the manifest entry's
+    gen_params applied before the KEY=VALUE overrides."""
+    fixed = [f"{k}={v}" for k, v in manifest.get(benchmark, {}).get("gen_params", {}).items()]
+    return parse_gen_params(benchmark_generator(manifest, benchmark), fixed + pairs)
+
+
+def _first_set(*values: Any) -> Any:
+    return next(v for v in values if v is not None)
+
+
+def implementation_paths(
+    perf_repo_root: Path, manifest: dict[str, dict[str, Any]], benchmark: str
+) -> dict[str, Path]:
+    """The program run_benchmark builds for each language, keyed by its
+    language name: the paths in the entry of `benchmark`'s source."""
+    entry = manifest.get(benchmark_source(manifest, benchmark), {})
+    return {language: perf_repo_root / entry[key]
+            for language, key in IMPLEMENTATION_KEYS.items() if key in entry}
+
+
+@dataclass
+class BenchmarkSpec:
+    """How to run one benchmark, from its benchmarks.toml entry. None means
+    the runner's flag or default applies."""
+    name: str
+    source: str
+    implementations: dict[str, Path]
+    generator: str
+    output_sha256: str | None
+    kind: str
+    tags: list[str]
+    gen_params: dict[str, Any]
+    size_kb: int | None
+    seed: int | None
+    samples: int | None
+    timeout_s: float | None
+    diff_epsilon: float | None
+    diff_byte_tolerance: int | None
+
+    @classmethod
+    def load(
+        cls, perf_repo_root: Path, manifest: dict[str, dict[str, Any]], name: str
+    ) -> BenchmarkSpec:
+        entry = manifest.get(name, {})
+        size = entry.get("size")
+        source = benchmark_source(manifest, name)
+        return cls(
+            name=name,
+            source=source,
+            implementations=implementation_paths(perf_repo_root, manifest, name),
+            generator=benchmark_generator(manifest, name),
+            output_sha256=entry.get("output_sha256"),
+            kind=entry.get("kind", "throughput"),
+            tags=benchmark_tags(manifest, name),
+            gen_params=dict(entry.get("gen_params", {})),
+            size_kb=parse_size_kb(str(size)) if size is not None else None,
+            seed=entry.get("seed"),
+            samples=entry.get("samples"),
+            timeout_s=entry.get("timeout"),
+            diff_epsilon=entry.get("diff_epsilon"),
+            diff_byte_tolerance=entry.get("diff_byte_tolerance"),
+        )
+
+    def run_settings(self, args: argparse.Namespace) -> tuple[int, int, int, float]:
+        """(size KiB, seed, samples, timeout seconds, 0 for none) for a run:
+        each from its flag, else this entry, else the runner's default."""
+        return (
+            _first_set(args.size, self.size_kb, DEFAULT_SIZE_KB),
+            _first_set(args.seed, self.seed, DEFAULT_SEED),
+            _first_set(args.samples, self.samples, DEFAULT_SAMPLES),
+            _first_set(args.timeout, self.timeout_s, DEFAULT_TIMEOUT_S),
+        )
+
+
+def validate_manifest(perf_repo_root: Path) -> list[str]:
+    """Problems with benchmarks.toml beyond its schema (which load_manifest
+    checks): program paths that don't exist, sources and compare_to names
+    that aren't entries, default inputs the generator can't make, an
+    output_sha256 that disagrees with golden_outputs.json, and programs in
+    the source directories that no entry names."""
+    manifest = load_manifest(perf_repo_root)
+    path = perf_repo_root / BENCHMARK_MANIFEST
+    lines = path.read_text().splitlines() if path.exists() else []
+    golden = GoldenManifest.load(perf_repo_root / GOLDEN_MANIFEST)
+    problems = []
+
+    def problem(name: str, key: str | None, message: str) -> None:
+        problems.append(str(manifest_error(path, lines, name, key, message)))
+
+    for name, entry in manifest.items():
+        for key in ("source", "compare_to"):
+            other = entry.get(key)
+            if other is not None and other not in manifest:
+                problem(name, key, f"unknown benchmark {other!r}")
+        if entry.get("source") not in (None, *manifest):
+            continue
+        for key in IMPLEMENTATION_KEYS.values():
+            if key in entry and not (perf_repo_root / entry[key]).exists():
+                problem(name, key, f"{entry[key]} does not exist")
+        spec = BenchmarkSpec.load(perf_repo_root, manifest, name)
+        try:
+            params = resolve_gen_params(manifest, name, [])
+        except ValueError as e:
+            problem(name, "gen_params", f"input generator {spec.generator!r} rejects them: {e}")
+            continue
+        try:
+            generated = generate_input_data(
+                spec.generator, _first_set(spec.size_kb, DEFAULT_SIZE_KB),
+                seed=_first_set(spec.seed, DEFAULT_SEED), params=params,
+            )
+        except Exception as e:
+            key = "generator" if "generator" in entry else None
+            problem(name, key, f"input generator {spec.generator!r} fails: {e}")
+            continue
+        expected = golden.expected(name, generated.sha256)
+        if spec.output_sha256 is not None and expected is not None \
+                and expected["sha256"] != spec.output_sha256:
+            problem(name, "output_sha256",
+                    f"{GOLDEN_MANIFEST} has {expected['sha256']} for this input")
+
+    named = {(perf_repo_root / entry[key]).resolve()
+             for entry in manifest.values()
+             for key in IMPLEMENTATION_KEYS.values() if key in entry}
+    for pattern in SOURCE_PROGRAMS:
+        for program in sorted(perf_repo_root.glob(pattern)):
+            if program.name == "main.rs":
+                program = program.parent.parent
+            if program.resolve() not in named:
+                relative = program.relative_to(perf_repo_root).as_posix()
+                problems.append(f"{path.name}: no entry names {relative}")
+    return problems
+
+
 class X07Runner:
     """Runner for X07 programs (via host runner)."""
 
@@ -280,7 +2545,7 @@ class X07Runner:
                 "--compiled-out", str(artifact_path),
             ]
 
-            result = subprocess.run(
+            result = _run_child(
                 cmd,
                 capture_output=True,
                 text=True,
@@ -325,7 +2590,7 @@ class X07Runner:
                 "--max-memory-bytes", str(256 * 1024 * 1024),
             ]
 
-            result = subprocess.run(
+            result = _run_child(
                 cmd,
                 capture_output=True,
                 text=True,
@@ -404,7 +2669,7 @@ class X07DirectRunner:
         prefixed_input = struct.pack("<I", input_len) + input_data
 
         start = time.perf_counter()
-        result = subprocess.run(
+        result = _run_child(
             [str(binary_path)],
             input=prefixed_input,
             capture_output=True,
@@ -426,7 +2691,7 @@ class X07DirectRunner:
         output_bytes = raw_output[4:4 + out_len]
         return output_bytes, run_time
 
-    def run_direct_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int]:
+    def run_direct_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int | None]:
         """Run a compiled X07 binary directly and return output plus peak RSS (KB)."""
         input_len = len(input_data)
         prefixed_input = struct.pack("<I", input_len) + input_data
@@ -477,7 +2742,7 @@ class CRunner:
     def run(self, binary_path: Path, input_data: bytes) -> tuple[bytes, float]:
         """Run a compiled C program, returning output and time in ms."""
         start = time.perf_counter()
-        result = subprocess.run(
+        result = _run_child(
             [str(binary_path)],
             input=input_data,
             capture_output=True,
@@ -485,15 +2750,15 @@ class CRunner:
         run_time = (time.perf_counter() - start) * 1000
 
         if result.returncode != 0:
-            raise RuntimeError(f"C execution failed: {result.stderr.decode()}")
+            raise RuntimeError(f"C execution failed (exit {result.returncode}): {result.stderr.decode(errors='replace')}")
 
         return result.stdout, run_time
 
-    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int]:
+    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int | None]:
         """Run a compiled C program and return output plus peak RSS (KB)."""
         res, rss_kb = _run_with_optional_rss([str(binary_path)], input_data, measure_rss=True)
         if res.returncode != 0:
-            raise RuntimeError(f"C execution failed: {res.stderr.decode(errors='replace')}")
+            raise RuntimeError(f"C execution failed (exit {res.returncode}): {res.stderr.decode(errors='replace')}")
         return res.stdout, rss_kb
 
 
@@ -523,7 +2788,7 @@ class RustRunner:
     def run(self, binary_path: Path, input_data: bytes) -> tuple[bytes, float]:
         """Run a compiled Rust program, returning output and time in ms."""
         start = time.perf_counter()
-        result = subprocess.run(
+        result = _run_child(
             [str(binary_path)],
             input=input_data,
             capture_output=True,
@@ -531,15 +2796,15 @@ class RustRunner:
         run_time = (time.perf_counter() - start) * 1000
 
         if result.returncode != 0:
-            raise RuntimeError(f"Rust execution failed: {result.stderr.decode()}")
+            raise RuntimeError(f"Rust execution failed (exit {result.returncode}): {result.stderr.decode(errors='replace')}")
 
         return result.stdout, run_time
 
-    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int]:
+    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int | None]:
         """Run a compiled Rust program and return output plus peak RSS (KB)."""
         res, rss_kb = _run_with_optional_rss([str(binary_path)], input_data, measure_rss=True)
         if res.returncode != 0:
-            raise RuntimeError(f"Rust execution failed: {res.stderr.decode(errors='replace')}")
+            raise RuntimeError(f"Rust execution failed (exit {res.returncode}): {res.stderr.decode(errors='replace')}")
         return res.stdout, rss_kb
 
 
@@ -585,7 +2850,7 @@ class GoRunner:
     def run(self, binary_path: Path, input_data: bytes) -> tuple[bytes, float]:
         """Run a compiled Go program, returning output and time in ms."""
         start = time.perf_counter()
-        result = subprocess.run(
+        result = _run_child(
             [str(binary_path)],
             input=input_data,
             capture_output=True,
@@ -593,15 +2858,15 @@ class GoRunner:
         run_time = (time.perf_counter() - start) * 1000
 
         if result.returncode != 0:
-            raise RuntimeError(f"Go execution failed: {result.stderr.decode()}")
+            raise RuntimeError(f"Go execution failed (exit {result.returncode}): {result.stderr.decode(errors='replace')}")
 
         return result.stdout, run_time
 
-    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int]:
+    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int | None]:
         """Run a compiled Go program and return output plus peak RSS (KB)."""
         res, rss_kb = _run_with_optional_rss([str(binary_path)], input_data, measure_rss=True)
         if res.returncode != 0:
-            raise RuntimeError(f"Go execution failed: {res.stderr.decode(errors='replace')}")
+            raise RuntimeError(f"Go execution failed (exit {res.returncode}): {res.stderr.decode(errors='replace')}")
         return res.stdout, rss_kb
 
 
@@ -636,7 +2901,7 @@ class RustCargoRunner:
     def run(self, binary_path: Path, input_data: bytes) -> tuple[bytes, float]:
         """Run a compiled Rust program, returning output and time in ms."""
         start = time.perf_counter()
-        result = subprocess.run(
+        result = _run_child(
             [str(binary_path)],
             input=input_data,
             capture_output=True,
@@ -644,15 +2909,15 @@ class RustCargoRunner:
         run_time = (time.perf_counter() - start) * 1000
 
         if result.returncode != 0:
-            raise RuntimeError(f"Rust execution failed: {result.stderr.decode()}")
+            raise RuntimeError(f"Rust execution failed (exit {result.returncode}): {result.stderr.decode(errors='replace')}")
 
         return result.stdout, run_time
 
-    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int]:
+    def run_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int | None]:
         """Run a compiled Rust program and return output plus peak RSS (KB)."""
         res, rss_kb = _run_with_optional_rss([str(binary_path)], input_data, measure_rss=True)
         if res.returncode != 0:
-            raise RuntimeError(f"Rust execution failed: {res.stderr.decode(errors='replace')}")
+            raise RuntimeError(f"Rust execution failed (exit {res.returncode}): {res.stderr.decode(errors='replace')}")
         return res.stdout, rss_kb
 
 
@@ -704,7 +2969,7 @@ class X07ProjectRunner:
         prefixed_input = struct.pack("<I", input_len) + input_data
 
         start = time.perf_counter()
-        result = subprocess.run(
+        result = _run_child(
             [str(binary_path)],
             input=prefixed_input,
             capture_output=True,
@@ -725,7 +2990,7 @@ class X07ProjectRunner:
         output_bytes = raw_output[4:4 + out_len]
         return output_bytes, run_time
 
-    def run_direct_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int]:
+    def run_direct_with_rss(self, binary_path: Path, input_data: bytes) -> tuple[bytes, int | None]:
         """Run a compiled X07 project binary directly and return output plus peak RSS (KB)."""
         input_len = len(input_data)
         prefixed_input = struct.pack("<I", input_len) + input_data
@@ -750,6 +3015,35 @@ class X07ProjectRunner:
         return output_bytes, rss_kb
 
 
+def _x07_project_for(perf_repo_root: Path, program: Path) -> tuple[Path, str] | None:
+    """(project file, entry) if `program` is an entry of an X07 project, that
+    is, under a directory below the repo root that has an x07.json."""
+    for parent in program.parents:
+        if parent == perf_repo_root or perf_repo_root not in parent.parents:
+            return None
+        if (parent / "x07.json").exists():
+            return parent / "x07.json", program.relative_to(parent).as_posix()
+    return None
+
+
+def _set_project_entry(project_file: Path, entry: str) -> str:
+    """Point the project at `entry`, returning the original text to restore."""
+    original = project_file.read_text()
+    project_data = json.loads(original)
+    project_data["entry"] = entry
+    project_file.write_text(json.dumps(project_data, indent=2))
+    return original
+
+
+def _record_failure(result: BenchmarkResult, e: Exception) -> None:
+    result.success = False
+    result.error = str(e)
+    if isinstance(e, BenchmarkTimeout):
+        # Samples collected before the hang aren't representative.
+        result.timed_out = True
+        result.times_ms.clear()
+
+
 def run_benchmark(
     benchmark: str,
     input_data: InputData,
@@ -760,351 +3054,2034 @@ def run_benchmark(
     warmup: int = 1,
     direct_mode: bool = False,
     x07_cc_profile: str = "default",
+    golden: GoldenManifest | None = None,
+    bless: bool = False,
+    implementations: dict[str, Path] | None = None,
+    output_sha256: str | None = None,
+    output_store: Path | None = None,
+    results_cache: ResultsCache | None = None,
+    adaptive: AdaptivePolicy | None = None,
 ) -> list[BenchmarkResult]:
-    """Run a benchmark across all languages."""
+    """Run a benchmark across all languages.
+
+    With a golden manifest, each implementation's output is checked before it
+    is timed; a mismatch fails that implementation without timing it. With
+    `bless`, the first implementation's output replaces the expected hash.
+    `output_sha256` is the expected hash where the manifest has none. A
+    mismatch is reported with the first differing byte against an output
+    that matched: an earlier or later implementation's, or one kept in
+    `output_store` by an earlier run.
+    `implementations` are the programs to run, by language (default: the
+    benchmark's in benchmarks.toml).
+    With a results cache, an implementation whose binary and input were timed
+    before takes those samples instead of warming up and sampling again; it
+    is still built, checked and measured for RSS. With an adaptive policy,
+    each implementation takes samples until the policy stops it instead of
+    exactly `iterations`.
+    """
     results = []
+    golden_reference: OutputDigest | None = None
+    plan = adaptive.describe() if adaptive is not None else iterations
+
+    def cache_key(binary: Path, how: str) -> str:
+        assert results_cache is not None
+        return results_cache.key(binary, input_data.sha256, plan, warmup, how)
+
+    def load_cached(result: BenchmarkResult, binary: Path, how: str = "") -> bool:
+        if results_cache is None:
+            return False
+        record = results_cache.load(cache_key(binary, how))
+        if record is None or not record.success:
+            return False
+        if adaptive is None and len(record.samples_ms) != iterations:
+            return False
+        result.times_ms = list(record.samples_ms)
+        result.converged = record.converged
+        result.cached = True
+        return True
+
+    def take_samples(result: BenchmarkResult, run_once: Callable[[], tuple[bytes, float]]) -> bytes:
+        """Append run_once's times to result until there are `iterations`, or
+        until the adaptive policy stops; returns the last run's output."""
+        started = time.perf_counter()
+        while True:
+            output, ms = run_once()
+            result.times_ms.append(ms)
+            if adaptive is None:
+                if len(result.times_ms) >= iterations:
+                    return output
+                continue
+            reason = adaptive.stop(result.times_ms, time.perf_counter() - started)
+            if reason is not None:
+                result.converged = reason == CONVERGED
+                return output
+
+    def timed(run: Callable[[], Any]) -> Callable[[], tuple[bytes, float]]:
+        """run_once for a runner that doesn't time itself (the host runner)."""
+        def run_once() -> tuple[bytes, float]:
+            start = time.perf_counter()
+            output, _metrics = run()
+            return output, (time.perf_counter() - start) * 1000
+        return run_once
+
+    def store_cached(result: BenchmarkResult, binary: Path, how: str = "") -> None:
+        if results_cache is None:
+            return
+        record = ResultRecord.from_result(result, x07_cc_profile)
+        record.input_sha256, record.input_bytes = input_data.sha256, len(input_data.data)
+        results_cache.store(cache_key(binary, how), record)
+
+    # Mismatches with nothing yet to diff against, for a later match to resolve.
+    unresolved: list[tuple[BenchmarkResult, OutputDigest]] = []
+
+    def verify(result: BenchmarkResult, digest: OutputDigest) -> None:
+        nonlocal golden_reference, bless
+        if golden is not None and bless:
+            golden.record(benchmark, input_data.sha256, digest)
+            bless = False
+        recorded = golden.expected(benchmark, input_data.sha256) if golden is not None else None
+        expected = recorded["sha256"] if recorded is not None else output_sha256
+        if expected is None:
+            return
+        if digest.sha256 == expected:
+            if golden_reference is None:
+                golden_reference = digest
+                if output_store is not None:
+                    store_output(output_store, digest)
+            return
+        msg = f"golden mismatch: output sha256 {digest.sha256}, expected {expected}"
+        reference = golden_reference.path if golden_reference is not None else None
+        if reference is None and output_store is not None:
+            reference = stored_output(output_store, expected)
+        if reference is not None:
+            msg += f"; first difference at byte {first_difference(reference, digest.path)}"
+        else:
+            unresolved.append((result, digest))
+        raise RuntimeError(msg)
+
+    def verify_run(result: BenchmarkResult, binary: Path, suffix: str) -> None:
+        """verify() a run of `binary`, its stdout streamed to a file in tmp_dir."""
+        x07 = suffix == "x07"
+        stdin = _x07_direct_input(input_data.data) if x07 else input_data.data
+        out_path = tmp_dir / f"{benchmark}_{suffix}.out"
+        verify(result, _run_to_file([str(binary)], stdin, out_path, length_prefixed=x07))
+
+    def check_output(result: BenchmarkResult, output: bytes) -> None:
+        """Keep a sampled run's output, noting if it differs from the X07 reference."""
+        result.output_bytes = output
+        if reference_output is not None and output != reference_output:
+            result.error = "Output mismatch with reference"
 
     perf_dir = perf_repo_root
     c_runner = CRunner()
     rust_runner = RustRunner()
     go_runner = GoRunner()
 
-    x07_prog = perf_dir / "x07" / f"{benchmark}.x07.json"
-    c_prog = perf_dir / "c" / f"{benchmark}.c"
-    rust_prog = perf_dir / "rust" / f"{benchmark}.rs"
-    go_prog = perf_dir / "go" / f"{benchmark}.go"
+    if implementations is None:
+        implementations = implementation_paths(perf_dir, load_manifest(perf_dir), benchmark)
+    x07_prog = implementations.get("X07")
+    c_prog = implementations.get("C")
+    rust_prog = implementations.get("Rust")
+    go_prog = implementations.get("Go")
 
     # Check for project-based X07 (e.g., regex benchmarks)
-    x07_project = None
-    if benchmark.startswith("regex_"):
-        entry_map = {
-            "regex_is_match": "src/is_match.x07.json",
-            "regex_count": "src/count.x07.json",
-            "regex_replace": "src/replace.x07.json",
-        }
-        if benchmark in entry_map:
-            project_dir = perf_dir / "projects" / "regex"
-            project_file = project_dir / "x07.json"
-            if project_file.exists():
-                x07_project = (project_file, entry_map[benchmark])
+    x07_project = _x07_project_for(perf_dir, x07_prog) if x07_prog is not None else None
 
     # Check for cargo-based Rust (e.g., regex benchmarks)
-    rust_cargo_proj = perf_dir / "rust_cargo" / benchmark
-    rust_cargo_exists = (rust_cargo_proj / "Cargo.toml").exists()
+    rust_cargo_exists = rust_prog is not None and (rust_prog / "Cargo.toml").exists()
 
     reference_output = None
 
     # Priority: project-based X07 over single-file X07
     if x07_project is not None:
         project_file, entry = x07_project
-        result = BenchmarkResult(language="X07", benchmark=benchmark)
-        original_project_text = project_file.read_text()
+        result = BenchmarkResult(
+            language="X07", benchmark=benchmark, source_path=str(project_file.parent / entry)
+        )
+        original_project_text = _set_project_entry(project_file, entry)
         try:
-            # Update project to use the right entry
-            project_dir = project_file.parent
-            project_data = json.loads(original_project_text)
-            project_data["entry"] = entry
-            project_file.write_text(json.dumps(project_data, indent=2))
-
             x07_runner = X07Runner(x07_host_runner, cc_profile=x07_cc_profile)
             project_runner = X07ProjectRunner(x07_host_runner, cc_profile=x07_cc_profile)
             artifact = tmp_dir / f"{benchmark}_x07"
+            result.binary_path = str(artifact)
 
             result.compile_time_ms = project_runner.compile(project_file, artifact)
             result.build_size_bytes = artifact.stat().st_size
 
+            verify_run(result, artifact, "x07")
             output, rss_kb = project_runner.run_direct_with_rss(artifact, input_data.data)
-            result.output_bytes = output
             result.peak_rss_kb = rss_kb
+            result.output_bytes = output
             if reference_output is None:
                 reference_output = output
 
-            for _ in range(warmup):
+            # The host runner is part of what host-runner samples measure.
+            how = "direct" if direct_mode else f"host runner {_file_sha256(x07_host_runner)}"
+            if not load_cached(result, artifact, how):
+                for _ in range(warmup):
+                    if direct_mode:
+                        project_runner.run_direct(artifact, input_data.data)
+                    else:
+                        x07_runner.run_cached(artifact, input_data.data)
+
                 if direct_mode:
-                    project_runner.run_direct(artifact, input_data.data)
+                    output = take_samples(
+                        result, lambda: project_runner.run_direct(artifact, input_data.data))
                 else:
-                    x07_runner.run_cached(artifact, input_data.data)
-
-            if direct_mode:
-                for _ in range(iterations):
-                    output, run_time = project_runner.run_direct(artifact, input_data.data)
-                    result.times_ms.append(run_time)
-            else:
-                for _ in range(iterations):
-                    start = time.perf_counter()
-                    output, _metrics = x07_runner.run_cached(artifact, input_data.data)
-                    result.times_ms.append((time.perf_counter() - start) * 1000)
+                    output = take_samples(
+                        result, timed(lambda: x07_runner.run_cached(artifact, input_data.data)))
+                store_cached(result, artifact, how)
 
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
         except Exception as e:
-            result.success = False
-            result.error = str(e)
+            _record_failure(result, e)
         finally:
             project_file.write_text(original_project_text)
 
         results.append(result)
 
-    elif x07_prog.exists():
-        result = BenchmarkResult(language="X07", benchmark=benchmark)
+    elif x07_prog is not None:
+        result = BenchmarkResult(language="X07", benchmark=benchmark, source_path=str(x07_prog))
         try:
             x07_runner = X07Runner(x07_host_runner, cc_profile=x07_cc_profile)
             direct_runner = X07DirectRunner(x07_host_runner, cc_profile=x07_cc_profile)
             artifact = tmp_dir / f"{benchmark}_x07"
+            result.binary_path = str(artifact)
 
             result.compile_time_ms = direct_runner.compile(x07_prog, artifact)
             result.build_size_bytes = artifact.stat().st_size
 
+            verify_run(result, artifact, "x07")
             output, rss_kb = direct_runner.run_direct_with_rss(artifact, input_data.data)
-            result.output_bytes = output
             result.peak_rss_kb = rss_kb
+            result.output_bytes = output
             if reference_output is None:
                 reference_output = output
 
-            for _ in range(warmup):
+            # The host runner is part of what host-runner samples measure.
+            how = "direct" if direct_mode else f"host runner {_file_sha256(x07_host_runner)}"
+            if not load_cached(result, artifact, how):
+                for _ in range(warmup):
+                    if direct_mode:
+                        direct_runner.run_direct(artifact, input_data.data)
+                    else:
+                        x07_runner.run_cached(artifact, input_data.data)
+
                 if direct_mode:
-                    direct_runner.run_direct(artifact, input_data.data)
+                    output = take_samples(
+                        result, lambda: direct_runner.run_direct(artifact, input_data.data))
                 else:
-                    x07_runner.run_cached(artifact, input_data.data)
-
-            if direct_mode:
-                for _ in range(iterations):
-                    output, run_time = direct_runner.run_direct(artifact, input_data.data)
-                    result.times_ms.append(run_time)
-            else:
-                for _ in range(iterations):
-                    start = time.perf_counter()
-                    output, _metrics = x07_runner.run_cached(artifact, input_data.data)
-                    result.times_ms.append((time.perf_counter() - start) * 1000)
+                    output = take_samples(
+                        result, timed(lambda: x07_runner.run_cached(artifact, input_data.data)))
+                store_cached(result, artifact, how)
 
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
         except Exception as e:
-            result.success = False
-            result.error = str(e)
+            _record_failure(result, e)
 
         results.append(result)
 
-    if c_prog.exists():
-        result = BenchmarkResult(language="C", benchmark=benchmark)
+    if c_prog is not None:
+        result = BenchmarkResult(language="C", benchmark=benchmark, source_path=str(c_prog))
         try:
             binary = tmp_dir / f"{benchmark}_c"
+            result.binary_path = str(binary)
             result.compile_time_ms = c_runner.compile(c_prog, binary)
             result.build_size_bytes = binary.stat().st_size
 
+            verify_run(result, binary, "c")
             output, rss_kb = c_runner.run_with_rss(binary, input_data.data)
             result.peak_rss_kb = rss_kb
-            result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
-            for _ in range(warmup):
-                c_runner.run(binary, input_data.data)
+            if not load_cached(result, binary):
+                for _ in range(warmup):
+                    c_runner.run(binary, input_data.data)
 
-            for _ in range(iterations):
-                output, run_time = c_runner.run(binary, input_data.data)
-                result.times_ms.append(run_time)
+                output = take_samples(result, lambda: c_runner.run(binary, input_data.data))
+                store_cached(result, binary)
             result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
         except Exception as e:
-            result.success = False
-            result.error = str(e)
+            _record_failure(result, e)
 
         results.append(result)
 
     # Priority: cargo-based Rust over single-file Rust
     if rust_cargo_exists:
-        result = BenchmarkResult(language="Rust", benchmark=benchmark)
+        result = BenchmarkResult(
+            language="Rust", benchmark=benchmark, source_path=str(rust_prog)
+        )
         try:
             cargo_runner = RustCargoRunner()
             binary = tmp_dir / f"{benchmark}_rust"
+            result.binary_path = str(binary)
 
-            result.compile_time_ms = cargo_runner.compile(rust_cargo_proj, binary)
+            result.compile_time_ms = cargo_runner.compile(rust_prog, binary)
             result.build_size_bytes = binary.stat().st_size
 
+            verify_run(result, binary, "rust")
             output, rss_kb = cargo_runner.run_with_rss(binary, input_data.data)
             result.peak_rss_kb = rss_kb
-            result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
-            for _ in range(warmup):
-                cargo_runner.run(binary, input_data.data)
+            if not load_cached(result, binary):
+                for _ in range(warmup):
+                    cargo_runner.run(binary, input_data.data)
 
-            for _ in range(iterations):
-                output, run_time = cargo_runner.run(binary, input_data.data)
-                result.times_ms.append(run_time)
+                output = take_samples(result, lambda: cargo_runner.run(binary, input_data.data))
+                store_cached(result, binary)
+            result.output_bytes = output
+            check_output(result, output)
 
         except Exception as e:
-            result.success = False
-            result.error = str(e)
+            _record_failure(result, e)
 
         results.append(result)
 
-    elif rust_prog.exists():
-        result = BenchmarkResult(language="Rust", benchmark=benchmark)
+    elif rust_prog is not None:
+        result = BenchmarkResult(language="Rust", benchmark=benchmark, source_path=str(rust_prog))
         try:
             binary = tmp_dir / f"{benchmark}_rust"
+            result.binary_path = str(binary)
             result.compile_time_ms = rust_runner.compile(rust_prog, binary)
             result.build_size_bytes = binary.stat().st_size
 
+            verify_run(result, binary, "rust")
             output, rss_kb = rust_runner.run_with_rss(binary, input_data.data)
             result.peak_rss_kb = rss_kb
-            result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
-            for _ in range(warmup):
-                rust_runner.run(binary, input_data.data)
+            if not load_cached(result, binary):
+                for _ in range(warmup):
+                    rust_runner.run(binary, input_data.data)
 
-            for _ in range(iterations):
-                output, run_time = rust_runner.run(binary, input_data.data)
-                result.times_ms.append(run_time)
+                output = take_samples(result, lambda: rust_runner.run(binary, input_data.data))
+                store_cached(result, binary)
             result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
         except Exception as e:
-            result.success = False
-            result.error = str(e)
+            _record_failure(result, e)
 
         results.append(result)
 
-    if go_prog.exists():
-        result = BenchmarkResult(language="Go", benchmark=benchmark)
+    if go_prog is not None:
+        result = BenchmarkResult(language="Go", benchmark=benchmark, source_path=str(go_prog))
         try:
             binary = tmp_dir / f"{benchmark}_go"
+            result.binary_path = str(binary)
             result.compile_time_ms = go_runner.compile(go_prog, binary)
             result.build_size_bytes = binary.stat().st_size
 
+            verify_run(result, binary, "go")
             output, rss_kb = go_runner.run_with_rss(binary, input_data.data)
             result.peak_rss_kb = rss_kb
-            result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
-            for _ in range(warmup):
-                go_runner.run(binary, input_data.data)
+            if not load_cached(result, binary):
+                for _ in range(warmup):
+                    go_runner.run(binary, input_data.data)
 
-            for _ in range(iterations):
-                output, run_time = go_runner.run(binary, input_data.data)
-                result.times_ms.append(run_time)
+                output = take_samples(result, lambda: go_runner.run(binary, input_data.data))
+                store_cached(result, binary)
             result.output_bytes = output
-            if reference_output is not None and output != reference_output:
-                result.error = "Output mismatch with reference"
+            check_output(result, output)
 
         except Exception as e:
-            result.success = False
-            result.error = str(e)
+            _record_failure(result, e)
 
         results.append(result)
 
+    for result, digest in unresolved:
+        if golden_reference is not None:
+            offset = first_difference(golden_reference.path, digest.path)
+            result.error += f"; first difference at byte {offset}"
+        else:
+            result.error += "; no output with the expected hash to diff against"
     return results
 
 
+def format_rate(input_bytes: int, median_ms: float, latency: bool) -> str:
+    """The per-benchmark headline metric: MiB/s, or microseconds for latency benchmarks."""
+    if latency:
+        return f"{median_ms * 1000:.1f} us"
+    mib_s = throughput_mib_s(input_bytes, median_ms)
+    return f"{mib_s:.1f} MiB/s" if mib_s is not None else "n/a"
+
+
+def _by_size(results: list[BenchmarkResult]) -> list[tuple[int | None, list[BenchmarkResult]]]:
+    """Group one benchmark's results by input size, in first-seen order."""
+    groups: dict[int | None, list[BenchmarkResult]] = {}
+    for r in results:
+        groups.setdefault(r.size_kb, []).append(r)
+    return list(groups.items())
+
+
+def _x07_mean(results: list[BenchmarkResult]) -> float | None:
+    for r in results:
+        if r.language == "X07" and r.success:
+            return r.mean_time_ms
+    return None
+
+
 def print_results(
     all_results: dict[str, list[BenchmarkResult]],
     input_size_kb: int,
     direct_mode: bool = False,
     x07_cc_profile: str = "default",
+    manifest: dict[str, dict[str, Any]] | None = None,
+    sweep: list[int] | None = None,
+    startup_baselines: dict[str, float] | None = None,
+    adaptive: bool = False,
 ) -> None:
-    """Print benchmark results in a formatted table."""
+    """Print benchmark results in a formatted table.
+
+    With a sweep, each benchmark's rows are grouped by input size. With
+    startup baselines (--subtract-baseline), an Adjusted column shows each
+    median minus that implementation's noop median.
+    """
     print()
     print("=" * 80)
     mode_str = "direct binary" if direct_mode else "host runner"
+    if sweep:
+        size_str = "input sizes: " + ", ".join(format_size_kb(kb) for kb in sweep)
+    else:
+        size_str = f"input size: {input_size_kb} KB"
     print(
-        f"Performance Benchmark Results (input size: {input_size_kb} KB, X07 mode: {mode_str}, cc-profile: {x07_cc_profile})"
+        f"Performance Benchmark Results ({size_str}, X07 mode: {mode_str}, cc-profile: {x07_cc_profile})"
     )
     print("=" * 80)
+    if startup_baselines is not None:
+        measured = ", ".join(f"{lang} {ms:.2f} ms" for lang, ms in startup_baselines.items())
+        print(f"Startup baseline ({NOOP_BENCHMARK} median): {measured or 'none measured'}")
     print()
 
+    size_col = f"{'Size':<8} " if sweep else ""
+    adj_col = f"{'Adjusted':<10} " if startup_baselines is not None else ""
     for benchmark, results in all_results.items():
         print(f"Benchmark: {benchmark}")
+        notes = (manifest or {}).get(benchmark, {}).get("notes")
+        if notes:
+            print(f"Note: {notes}")
         print("-" * 70)
         print(
-            f"{'Language':<12} {'Mean (ms)':<12} {'Min (ms)':<12} {'StdDev':<10} "
+            f"{size_col}{'Language':<12} {'Mean (ms)':<12} {'Median (ms)':<12} {adj_col}{'Min (ms)':<12} "
+            f"{'Max (ms)':<12} {'StdDev':<10} {'Outliers':<9} {'Throughput':<13} "
             f"{'Compile (ms)':<12} {'Build (KiB)':<12} {'RSS (KiB)':<10} {'Status'}"
         )
         print("-" * 70)
 
-        x07_time = None
-        for r in results:
-            if r.language == "X07" and r.success:
-                x07_time = r.mean_time_ms
-                break
-
-        for r in results:
-            status = "OK" if r.success else f"FAIL: {r.error[:30]}"
-            if r.error and r.success:
-                status = f"WARN: {r.error[:25]}"
-
-            speedup = ""
-            if x07_time and r.success and r.mean_time_ms > 0:
-                ratio = x07_time / r.mean_time_ms
-                if r.language != "X07":
-                    speedup = f" ({ratio:.2f}x)"
-
-            build_kib = r.build_size_bytes / 1024 if r.build_size_bytes else 0.0
-            print(
-                f"{r.language:<12} "
-                f"{r.mean_time_ms:<12.2f} "
-                f"{r.min_time_ms:<12.2f} "
-                f"{r.stddev_time_ms:<10.2f} "
-                f"{r.compile_time_ms:<12.1f} "
-                f"{build_kib:<12.1f} "
-                f"{r.peak_rss_kb:<10d} "
-                f"{status}{speedup}"
-            )
+        latency = is_latency_benchmark(manifest or {}, benchmark)
+        for size_kb, group in _by_size(results):
+            x07_time = _x07_mean(group)
+            size = f"{format_size_kb(size_kb):<8} " if sweep and size_kb is not None else ""
+            for r in group:
+                status = "OK" if r.success else f"FAIL: {r.error[:30]}"
+                if r.timed_out:
+                    status = "TIMEOUT"
+                elif r.error and r.success:
+                    status = f"WARN: {r.error[:25]}"
+                elif r.success and r.summary.unstable:
+                    status = "UNSTABLE"
+                if r.success and r.cached:
+                    status += ", cached"
+                if r.success and r.converged is not None:
+                    status += f", {len(r.times_ms)} samples"
+                    status += "" if r.converged else ", not converged"
+
+                speedup = ""
+                if x07_time and r.success and r.mean_time_ms > 0:
+                    ratio = x07_time / r.mean_time_ms
+                    if r.language != "X07":
+                        speedup = f" ({ratio:.2f}x)"
+
+                build_kib = r.build_size_bytes / 1024 if r.build_size_bytes else 0.0
+                adjusted = ""
+                if startup_baselines is not None:
+                    adj = r.adjusted_median_time_ms
+                    adjusted = f"{adj:<10.2f} " if adj is not None and r.success else f"{'-':<10} "
+                if r.success:
+                    timing = (
+                        f"{r.mean_time_ms:<12.2f} "
+                        f"{r.median_time_ms:<12.2f} "
+                        f"{adjusted}"
+                        f"{r.min_time_ms:<12.2f} "
+                        f"{r.max_time_ms:<12.2f} "
+                        f"{r.stddev_time_ms:<10.2f} "
+                        f"{r.summary.outliers:<9d} "
+                        f"{format_rate(r.input_bytes, r.median_time_ms, latency):<13} "
+                    )
+                else:
+                    # Timings from a failed run are not comparable; don't print them.
+                    timing = (
+                        f"{'-':<12} {'-':<12} {adjusted}{'-':<12} {'-':<12} {'-':<10} {'-':<9} "
+                        f"{'-':<13} "
+                    )
+                print(
+                    f"{size}"
+                    f"{r.language:<12} "
+                    f"{timing}"
+                    f"{r.compile_time_ms:<12.1f} "
+                    f"{build_kib:<12.1f} "
+                    f"{r.peak_rss_kb if r.peak_rss_kb is not None else 'n/a':<10} "
+                    f"{status}{speedup}"
+                )
 
         print()
 
     print()
     print("Legend:")
-    print("  - Mean/Min/StdDev: Execution time statistics over multiple runs")
+    print("  - Mean/Median/Min/Max/StdDev: Execution time statistics over multiple runs,")
+    print("    after discarding MAD outliers (counted in Outliers)")
+    print("  - Throughput: Stdin MiB per second of median time; latency benchmarks")
+    print("    (kind = \"latency\" in benchmarks.toml) show the median in microseconds")
+    if startup_baselines is not None:
+        print("  - Adjusted: Median minus the same implementation's noop median, clamped")
+        print("    at zero; '-' where that implementation has no noop")
+    print("  - UNSTABLE: StdDev exceeds 10% of the median")
+    print("  - TIMEOUT: A run exceeded --timeout and was killed; no timings are kept")
+    print("  - cached: Samples reused from an earlier run of the same binary on the")
+    print("    same input (--no-cache to take new ones)")
+    if adaptive:
+        print("  - N samples: How many --adaptive took; 'not converged' where the median's")
+        print("    95% interval was still wider than --adaptive-target at the sample or")
+        print("    time budget")
     print("  - Compile: One-time compilation overhead")
     print("  - Build: Final executable size")
-    print("  - RSS: Peak resident set size (one run)")
+    print("  - RSS: Peak resident set size (one run; n/a where unsupported)")
     print("  - Speedup (Nx): How many times faster than X07")
     print()
 
 
-def print_summary_table(all_results: dict[str, list[BenchmarkResult]]) -> None:
-    """Print a summary comparison table."""
+def print_pairs(
+    all_results: dict[str, list[BenchmarkResult]], manifest: dict[str, dict[str, Any]]
+) -> None:
+    """Print each benchmark against its manifest `compare_to` sibling, matched by
+    implementation and input size, as a ratio of median times."""
+    rows = []
+    for benchmark, results in all_results.items():
+        other = manifest.get(benchmark, {}).get("compare_to")
+        if other not in all_results:
+            continue
+        base = {(r.language, r.size_kb): r for r in all_results[other] if r.success}
+        for r in results:
+            b = base.get((r.language, r.size_kb))
+            if r.success and b is not None and b.median_time_ms > 0:
+                size = f" @ {format_size_kb(r.size_kb)}" if r.size_kb is not None else ""
+                rows.append((f"{benchmark} vs {other}{size}", r.language,
+                             r.median_time_ms / b.median_time_ms))
+    if not rows:
+        return
+
+    print("Paired benchmarks (median time relative to compare_to in benchmarks.toml):")
+    for label, language, ratio in rows:
+        print(f"  {label:<48} {language:<6} {ratio:.2f}x ({(ratio - 1) * 100:+.1f}%)")
+    print()
+
+
+def print_summary_table(
+    all_results: dict[str, list[BenchmarkResult]], sweep: list[int] | None = None
+) -> None:
+    """Print a summary comparison table, with one row per input size in a sweep."""
     print()
     print("=" * 60)
     print("Summary: Relative Performance (X07 = 1.0x)")
     print("=" * 60)
     print()
-    print(f"{'Benchmark':<20} {'X07':<12} {'C':<12} {'Rust':<12} {'Go':<12}")
+    size_col = f"{'Size':<8} " if sweep else ""
+    print(f"{'Benchmark':<20} {size_col}{'X07':<12} {'C':<12} {'Rust':<12} {'Go':<12}")
     print("-" * 60)
 
     for benchmark, results in all_results.items():
-        row = {"X07": "1.0x", "C": "N/A", "Rust": "N/A", "Go": "N/A"}
+        for size_kb, group in _by_size(results):
+            row = {"X07": "1.0x", "C": "N/A", "Rust": "N/A", "Go": "N/A"}
 
-        x07_time = None
-        for r in results:
-            if r.language == "X07" and r.success:
-                x07_time = r.mean_time_ms
-                break
+            x07_time = _x07_mean(group)
+            if x07_time and x07_time > 0:
+                for r in group:
+                    if r.success and r.mean_time_ms > 0:
+                        ratio = x07_time / r.mean_time_ms
+                        row[r.language] = f"{ratio:.2f}x"
 
-        if x07_time and x07_time > 0:
-            for r in results:
-                if r.success and r.mean_time_ms > 0:
-                    ratio = x07_time / r.mean_time_ms
-                    row[r.language] = f"{ratio:.2f}x"
+            size = f"{format_size_kb(size_kb):<8} " if sweep and size_kb is not None else ""
+            print(
+                f"{benchmark:<20} {size}{row['X07']:<12} {row['C']:<12} {row['Rust']:<12} "
+                f"{row['Go']:<12}"
+            )
+
+    print()
+
+
+RESULTS_SCHEMA_VERSION = "x07-perf-compare.results@0.1.0"
+
+
+@dataclass
+class ResultRecord:
+    """One (benchmark, implementation) entry of a results file.
+
+    Field names and meanings are part of the results schema
+    (RESULTS_SCHEMA_VERSION); add fields rather than renaming them.
+    """
+    benchmark: str
+    language: str
+    success: bool
+    error: str
+    timed_out: bool
+    samples_ms: list[float]
+    mean_time_ms: float
+    median_time_ms: float
+    min_time_ms: float
+    max_time_ms: float
+    stddev_time_ms: float
+    outliers: int
+    unstable: bool
+    compile_time_ms: float
+    build_size_bytes: int
+    peak_rss_kb: int | None
+    input_sha256: str
+    input_bytes: int
+    throughput_mib_s: float | None
+    startup_baseline_ms: float | None
+    adjusted_median_time_ms: float | None
+    size_kb: int | None
+    source_path: str
+    binary_path: str
+    x07_cc_profile: str | None
+    cached: bool
+    converged: bool | None
+    median_ci_ms: list[float] | None
+
+    @classmethod
+    def from_result(
+        cls, r: BenchmarkResult, x07_cc_profile: str, latency: bool = False
+    ) -> ResultRecord:
+        summary = r.summary
+        return cls(
+            benchmark=r.benchmark,
+            language=r.language,
+            success=r.success,
+            error=r.error,
+            timed_out=r.timed_out,
+            samples_ms=list(r.times_ms),
+            mean_time_ms=summary.mean,
+            median_time_ms=summary.median,
+            min_time_ms=summary.min,
+            max_time_ms=summary.max,
+            stddev_time_ms=summary.stddev,
+            outliers=summary.outliers,
+            unstable=summary.unstable,
+            compile_time_ms=r.compile_time_ms,
+            build_size_bytes=r.build_size_bytes,
+            peak_rss_kb=r.peak_rss_kb,
+            input_sha256=r.input_sha256,
+            input_bytes=r.input_bytes,
+            throughput_mib_s=None if latency else r.throughput_mib_s,
+            startup_baseline_ms=r.startup_baseline_ms,
+            adjusted_median_time_ms=r.adjusted_median_time_ms,
+            size_kb=r.size_kb,
+            source_path=r.source_path,
+            binary_path=r.binary_path,
+            x07_cc_profile=x07_cc_profile if r.language == "X07" else None,
+            cached=r.cached,
+            converged=r.converged,
+            median_ci_ms=list(bootstrap_median_ci(r.times_ms))
+            if r.converged is not None and r.success else None,
+        )
+
+    @property
+    def compared_time_ms(self) -> float:
+        """The median compare uses: startup-subtracted when the run recorded it."""
+        if self.adjusted_median_time_ms is not None:
+            return self.adjusted_median_time_ms
+        return self.median_time_ms
+
+    @classmethod
+    def from_json(cls, benchmark: str, obj: dict[str, Any]) -> ResultRecord:
+        """Parse a record; fields missing from older files get neutral defaults."""
+        return cls(
+            benchmark=obj.get("benchmark", benchmark),
+            language=obj["language"],
+            success=obj.get("success", True),
+            error=obj.get("error", ""),
+            timed_out=obj.get("timed_out", False),
+            samples_ms=list(obj.get("samples_ms", [])),
+            mean_time_ms=obj.get("mean_time_ms", 0.0),
+            median_time_ms=obj.get("median_time_ms", obj.get("mean_time_ms", 0.0)),
+            min_time_ms=obj.get("min_time_ms", 0.0),
+            max_time_ms=obj.get("max_time_ms", 0.0),
+            stddev_time_ms=obj.get("stddev_time_ms", 0.0),
+            outliers=obj.get("outliers", 0),
+            unstable=obj.get("unstable", False),
+            compile_time_ms=obj.get("compile_time_ms", 0.0),
+            build_size_bytes=obj.get("build_size_bytes", 0),
+            peak_rss_kb=obj.get("peak_rss_kb"),
+            input_sha256=obj.get("input_sha256", ""),
+            input_bytes=obj.get("input_bytes", 0),
+            throughput_mib_s=obj.get("throughput_mib_s"),
+            startup_baseline_ms=obj.get("startup_baseline_ms"),
+            adjusted_median_time_ms=obj.get("adjusted_median_time_ms"),
+            size_kb=obj.get("size_kb"),
+            source_path=obj.get("source_path", ""),
+            binary_path=obj.get("binary_path", ""),
+            x07_cc_profile=obj.get("x07_cc_profile"),
+            cached=obj.get("cached", False),
+            converged=obj.get("converged"),
+            median_ci_ms=obj.get("median_ci_ms"),
+        )
+
+
+@dataclass
+class SuiteSummary:
+    """Geometric means of X07/Rust median ratios over a results set.
+
+    Each benchmark (or benchmark@size, as in _comparison_key) with a
+    successful, non-zero median on both sides contributes one ratio to the
+    overall mean and to the mean of every tag it has; the rest are listed in
+    `excluded` with the reason. Ratios 2.0 and 8.0 give 4.0. A mean over no
+    ratios is None rather than NaN.
+    """
+    geomean: float | None
+    count: int
+    by_tag: dict[str, tuple[float | None, int]]
+    excluded: list[tuple[str, str]]
+
+    @classmethod
+    def from_records(
+        cls, records: list[ResultRecord], manifest: dict[str, dict[str, Any]]
+    ) -> SuiteSummary:
+        sides: dict[str, dict[str, ResultRecord]] = {}
+        benchmark_of: dict[str, str] = {}
+        for r in records:
+            name, language = _comparison_key(r, records)
+            sides.setdefault(name, {})[language] = r
+            benchmark_of[name] = r.benchmark
+
+        ratios: dict[str, float] = {}
+        excluded = []
+        for name, by_language in sides.items():
+            problems = []
+            for language in ("X07", "Rust"):
+                r = by_language.get(language)
+                if r is None:
+                    problems.append(f"no {language} result")
+                elif not r.success:
+                    problems.append(f"{language} failed")
+                elif r.compared_time_ms <= 0:
+                    problems.append(f"{language} median is 0")
+            if problems:
+                excluded.append((name, ", ".join(problems)))
+                continue
+            x07, rust = by_language["X07"], by_language["Rust"]
+            ratios[name] = x07.compared_time_ms / rust.compared_time_ms
+
+        tagged: dict[str, list[float]] = {}
+        for name, ratio in ratios.items():
+            for tag in benchmark_tags(manifest, benchmark_of[name]):
+                tagged.setdefault(tag, []).append(ratio)
+        return cls(
+            geomean=_geomean(list(ratios.values())),
+            count=len(ratios),
+            by_tag={tag: (_geomean(rs), len(rs)) for tag, rs in sorted(tagged.items())},
+            excluded=excluded,
+        )
+
+    def to_json(self) -> dict[str, Any]:
+        return {
+            "x07_over_rust_geomean": self.geomean,
+            "benchmarks": self.count,
+            "by_tag": {tag: {"x07_over_rust_geomean": g, "benchmarks": n}
+                       for tag, (g, n) in self.by_tag.items()},
+            "excluded": [{"benchmark": b, "reason": why} for b, why in self.excluded],
+        }
+
+
+def _describe_ratio(ratio: float | None) -> str:
+    """X07/Rust as "1.15x (X07 15.0% slower)", or "n/a" with no benchmarks."""
+    if ratio is None:
+        return "n/a"
+    pct = (ratio - 1.0) * 100.0
+    if f"{abs(pct):.1f}" == "0.0":
+        return f"{ratio:.2f}x (even)"
+    return f"{ratio:.2f}x (X07 {abs(pct):.1f}% {'slower' if pct > 0 else 'faster'})"
+
+
+def print_suite_summary(summary: SuiteSummary) -> None:
+    """Print the suite-wide X07/Rust geomean, per tag, and what it left out."""
+    print("Suite summary: geometric mean of X07/Rust median ratios")
+    print("-" * 60)
+    print(f"  {'overall':<14} {summary.count:>4} benchmark(s)  {_describe_ratio(summary.geomean)}")
+    for tag, (geomean, count) in summary.by_tag.items():
+        print(f"  {tag:<14} {count:>4} benchmark(s)  {_describe_ratio(geomean)}")
+    if summary.excluded:
+        print(f"  Excluded ({len(summary.excluded)}):")
+        for name, why in summary.excluded:
+            print(f"    {name}: {why}")
+    print()
+
+
+def results_document(
+    records: list[ResultRecord], config: dict[str, Any], summary: SuiteSummary | None = None
+) -> dict[str, Any]:
+    """Build the top-level results JSON document."""
+    doc = {
+        "schema_version": RESULTS_SCHEMA_VERSION,
+        "config": config,
+        "results": [asdict(rec) for rec in records],
+    }
+    if summary is not None:
+        doc["summary"] = summary.to_json()
+    return doc
+
+
+# CSV column orders. Columns may be appended but never reordered or renamed,
+# so spreadsheets that address them by position keep working. Each is a
+# ResultRecord field (or, for comparisons, a ComparisonEntry attribute)
+# except `sample`, the 0-based index into samples_ms, and `time_ms`, that
+# sample. Missing values (such as a null peak_rss_kb) are empty cells.
+CSV_SAMPLE_COLUMNS = (
+    "benchmark", "language", "size_kb", "input_bytes", "sample", "time_ms",
+)
+CSV_SUMMARY_COLUMNS = (
+    "benchmark", "language", "size_kb", "input_bytes", "success", "error", "timed_out",
+    "samples", "mean_time_ms", "median_time_ms", "min_time_ms", "max_time_ms",
+    "stddev_time_ms", "outliers", "unstable", "throughput_mib_s", "startup_baseline_ms",
+    "adjusted_median_time_ms", "compile_time_ms", "build_size_bytes", "peak_rss_kb", "cached",
+    "converged", "median_ci_low_ms", "median_ci_high_ms",
+)
+CSV_COMPARISON_COLUMNS = (
+    "benchmark", "language", "baseline_ms", "current_ms", "baseline_mib_s", "current_mib_s",
+    "change_pct", "regressed", "baseline_rss_kb", "current_rss_kb", "rss_change_pct",
+    "rss_regressed",
+)
+
+
+def _csv_writer(out: Any) -> Any:
+    """An RFC 4180 writer: CRLF rows, fields with commas, quotes or newlines quoted."""
+    return csv.writer(out, lineterminator="\r\n")
+
+
+def _csv_cell(value: Any) -> Any:
+    return "" if value is None else value
+
+
+def write_samples_csv(out: Any, records: list[ResultRecord]) -> None:
+    """One CSV_SAMPLE_COLUMNS row per timed sample; failed runs have none."""
+    writer = _csv_writer(out)
+    writer.writerow(CSV_SAMPLE_COLUMNS)
+    for rec in records:
+        for i, ms in enumerate(rec.samples_ms):
+            row = {**asdict(rec), "sample": i, "time_ms": ms}
+            writer.writerow([_csv_cell(row[c]) for c in CSV_SAMPLE_COLUMNS])
+
+
+def write_summary_csv(out: Any, records: list[ResultRecord]) -> None:
+    """One CSV_SUMMARY_COLUMNS row per record; `samples` is the sample count,
+    and the median_ci columns are median_ci_ms's ends."""
+    writer = _csv_writer(out)
+    writer.writerow(CSV_SUMMARY_COLUMNS)
+    for rec in records:
+        low, high = rec.median_ci_ms or (None, None)
+        row = {**asdict(rec), "samples": len(rec.samples_ms),
+               "median_ci_low_ms": low, "median_ci_high_ms": high}
+        writer.writerow([_csv_cell(row[c]) for c in CSV_SUMMARY_COLUMNS])
+
+
+def load_results(path: Path) -> tuple[dict[str, Any], list[ResultRecord]]:
+    """Load a results file, returning (config, records).
+
+    Also accepts the older snapshot layout ({benchmark: [records]}) used by
+    the files under snapshots/.
+    """
+    doc = json.loads(path.read_text())
+    if "schema_version" not in doc:
+        records = [ResultRecord.from_json(b, obj) for b, objs in doc.items() for obj in objs]
+        return {}, records
+    if doc["schema_version"] != RESULTS_SCHEMA_VERSION:
+        raise ValueError(f"{path}: unsupported results schema {doc['schema_version']!r}")
+    records = [ResultRecord.from_json(obj["benchmark"], obj) for obj in doc["results"]]
+    return doc.get("config", {}), records
+
+
+# Part of every results cache key. Bump it when a runner change (how a sample
+# is timed, what a warmup run does) makes earlier samples incomparable.
+RUNNER_VERSION = 1
+
+DEFAULT_CACHE_MAX_SIZE_KB = 64 * 1024
+DEFAULT_CACHE_MAX_AGE_DAYS = 30.0
+
+
+def default_results_cache() -> Path:
+    return Path(tempfile.gettempdir()) / "x07-perf-compare-results"
+
+
+def _file_sha256(path: Path) -> str:
+    digest = hashlib.sha256()
+    with path.open("rb") as f:
+        for chunk in iter(lambda: f.read(1 << 20), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+class ResultsCache:
+    """Timed samples from earlier runs, reused while nothing that shaped them changed.
+
+    An entry is keyed by the sha256 of the binary and of its input, the sample
+    and warmup counts, how the binary is run and RUNNER_VERSION, and holds the
+    ResultRecord of the run that timed it, as one JSON file. On each store,
+    entries measured more than max_age_s ago are dropped, then the oldest
+    until the rest fit in max_bytes. A hit does not refresh an entry's age:
+    samples go stale with the machine, however often they are reused.
+    """
+
+    def __init__(
+        self,
+        root: Path,
+        max_bytes: int = DEFAULT_CACHE_MAX_SIZE_KB * 1024,
+        max_age_s: float = DEFAULT_CACHE_MAX_AGE_DAYS * 86400,
+    ) -> None:
+        self.root = root
+        self.max_bytes = max_bytes
+        self.max_age_s = max_age_s
+
+    def key(self, binary: Path, input_sha256: str, samples: int, warmup: int, how: str) -> str:
+        parts = [_file_sha256(binary), input_sha256, str(samples), str(warmup), how,
+                 str(RUNNER_VERSION)]
+        if child_placement():
+            parts.append(child_placement())
+        return hashlib.sha256("\0".join(parts).encode()).hexdigest()
+
+    def load(self, key: str) -> ResultRecord | None:
+        """The record under key, if any; an unreadable entry is deleted as a miss."""
+        path = self.root / f"{key}.json"
+        try:
+            obj = json.loads(path.read_text())
+            return ResultRecord.from_json(obj["benchmark"], obj)
+        except FileNotFoundError:
+            return None
+        except (OSError, ValueError, KeyError, TypeError):
+            path.unlink(missing_ok=True)
+            return None
+
+    def store(self, key: str, record: ResultRecord) -> None:
+        self.root.mkdir(parents=True, exist_ok=True)
+        path = self.root / f"{key}.json"
+        partial = path.with_name(f"{path.name}.{os.getpid()}.tmp")
+        partial.write_text(json.dumps(asdict(record)))
+        os.replace(partial, path)
+        self.evict()
+
+    def evict(self) -> None:
+        entries = []
+        for path in self.root.glob("*.json"):
+            try:
+                st = path.stat()
+            except FileNotFoundError:
+                continue
+            entries.append((st.st_mtime, st.st_size, path))
+        now = time.time()
+        kept = 0
+        for mtime, size, path in sorted(entries, key=lambda e: e[0], reverse=True):
+            if now - mtime > self.max_age_s:
+                path.unlink(missing_ok=True)
+                continue
+            kept += size
+            if kept > self.max_bytes:
+                path.unlink(missing_ok=True)
+
+    def clear(self) -> int:
+        """Delete every entry, returning how many there were."""
+        removed = 0
+        for path in list(self.root.glob("*.json")) + list(self.root.glob("*.tmp")):
+            path.unlink(missing_ok=True)
+            removed += path.suffix == ".json"
+        return removed
+
+
+@dataclass
+class ComparisonEntry:
+    """One benchmark/implementation matched across two results files."""
+    benchmark: str
+    language: str
+    baseline_ms: float
+    current_ms: float
+    regressed: bool
+    baseline_rss_kb: int | None = None
+    current_rss_kb: int | None = None
+    rss_regressed: bool = False
+    baseline_mib_s: float | None = None
+    current_mib_s: float | None = None
+
+    @property
+    def by_throughput(self) -> bool:
+        return self.baseline_mib_s is not None and self.current_mib_s is not None
+
+    @property
+    def change_pct(self) -> float:
+        """How much slower the current run is (negative: faster), by throughput
+        when both runs have it and by median time otherwise."""
+        if self.by_throughput:
+            return (self.baseline_mib_s / self.current_mib_s - 1.0) * 100.0
+        return (self.current_ms / self.baseline_ms - 1.0) * 100.0
+
+    @property
+    def rss_change_pct(self) -> float | None:
+        """Peak RSS change, or None when either run has no RSS measurement."""
+        if not self.baseline_rss_kb or not self.current_rss_kb:
+            return None
+        return (self.current_rss_kb / self.baseline_rss_kb - 1.0) * 100.0
+
+
+@dataclass
+class Comparison:
+    entries: list[ComparisonEntry] = field(default_factory=list)
+    only_in_baseline: list[tuple[str, str]] = field(default_factory=list)
+    only_in_current: list[tuple[str, str]] = field(default_factory=list)
+    failed: list[tuple[str, str]] = field(default_factory=list)
+
+    @property
+    def regressions(self) -> list[ComparisonEntry]:
+        return [e for e in self.entries if e.regressed or e.rss_regressed]
+
+
+def parse_percent(text: str) -> float:
+    """Parse a threshold like "5%" or "5" into percent."""
+    value = float(text.strip().removesuffix("%"))
+    if value < 0:
+        raise ValueError(f"threshold must be non-negative: {text}")
+    return value
+
+
+def _comparison_key(rec: ResultRecord, records: list[ResultRecord]) -> tuple[str, str]:
+    """(benchmark, language), with the size appended to the benchmark name when
+    the same file records that benchmark at more than one size."""
+    sizes = {r.size_kb for r in records if r.benchmark == rec.benchmark}
+    if len(sizes) > 1 and rec.size_kb is not None:
+        return f"{rec.benchmark}@{format_size_kb(rec.size_kb)}", rec.language
+    return rec.benchmark, rec.language
+
+
+def compare_results(
+    baseline: list[ResultRecord],
+    current: list[ResultRecord],
+    fail_over_pct: float,
+    rss_fail_over_pct: float | None = None,
+    latency_benchmarks: frozenset[str] = frozenset(),
+) -> Comparison:
+    """Match records by (benchmark, language) and compare them.
+
+    Benchmarks are compared on throughput, so runs over different input
+    sizes stay comparable; those in latency_benchmarks (or without an input
+    byte count) are compared on median time. Startup-subtracted medians are
+    used where recorded; one clamped to zero is not compared.
+
+    Files from a size sweep are matched per size too: a benchmark recorded
+    at several sizes is compared as `name@size` (see _comparison_key).
+
+    A benchmark regresses when it is more than fail_over_pct slower than the
+    baseline; exactly at the threshold is not a regression. With
+    rss_fail_over_pct, peak RSS growth beyond that threshold is a separate
+    regression; records without an RSS measurement are never flagged.
+    """
+    base = {_comparison_key(r, baseline): r for r in baseline}
+    cur = {_comparison_key(r, current): r for r in current}
+
+    cmp = Comparison()
+    cmp.only_in_baseline = sorted(base.keys() - cur.keys())
+    cmp.only_in_current = sorted(cur.keys() - base.keys())
+
+    for key in sorted(base.keys() & cur.keys()):
+        b, c = base[key], cur[key]
+        if not (b.success and c.success) or b.compared_time_ms <= 0 or c.compared_time_ms <= 0:
+            cmp.failed.append(key)
+            continue
+        entry = ComparisonEntry(
+            benchmark=key[0],
+            language=key[1],
+            baseline_ms=b.compared_time_ms,
+            current_ms=c.compared_time_ms,
+            regressed=False,
+            baseline_rss_kb=b.peak_rss_kb,
+            current_rss_kb=c.peak_rss_kb,
+        )
+        if b.benchmark not in latency_benchmarks:
+            entry.baseline_mib_s = throughput_mib_s(b.input_bytes, b.compared_time_ms)
+            entry.current_mib_s = throughput_mib_s(c.input_bytes, c.compared_time_ms)
+        # Rounded so that exactly the threshold, which the divisions can
+        # turn into 5.000000000000004%, is not over it.
+        entry.regressed = round(entry.change_pct, 9) > fail_over_pct
+        rss_change = entry.rss_change_pct
+        entry.rss_regressed = (
+            rss_fail_over_pct is not None and rss_change is not None
+            and round(rss_change, 9) > rss_fail_over_pct
+        )
+        cmp.entries.append(entry)
+
+    return cmp
+
+
+def write_comparison_csv(out: Any, cmp: Comparison) -> None:
+    """One CSV_COMPARISON_COLUMNS row per matched benchmark and implementation."""
+    writer = _csv_writer(out)
+    writer.writerow(CSV_COMPARISON_COLUMNS)
+    for e in cmp.entries:
+        writer.writerow([_csv_cell(getattr(e, c)) for c in CSV_COMPARISON_COLUMNS])
+
+
+def print_suite_comparison(baseline: SuiteSummary, current: SuiteSummary) -> None:
+    """Print the X07/Rust geomeans of two results sets side by side, overall and per tag."""
+    print("Suite geomean of X07/Rust median ratios (baseline -> current):")
+    rows = [("overall", (baseline.geomean, baseline.count), (current.geomean, current.count))]
+    for tag in sorted(baseline.by_tag.keys() | current.by_tag.keys()):
+        rows.append((tag, baseline.by_tag.get(tag, (None, 0)), current.by_tag.get(tag, (None, 0))))
+    for label, (b, b_count), (c, c_count) in rows:
+        b_text = f"{b:.2f}x" if b is not None else "n/a"
+        c_text = f"{c:.2f}x" if c is not None else "n/a"
+        change = f"{(c / b - 1.0) * 100.0:+.1f}%" if b is not None and c is not None else "n/a"
+        print(f"  {label:<14} {b_text:>7} -> {c_text:<7} {change:<8} "
+              f"({b_count} -> {c_count} benchmark(s))")
+    print()
 
+
+def print_comparison(
+    cmp: Comparison,
+    fail_over_pct: float,
+    top: int,
+    rss_fail_over_pct: float | None = None,
+    suites: tuple[SuiteSummary, SuiteSummary] | None = None,
+) -> None:
+    """Print a comparison as a table plus a biggest-movers summary, and with
+    `suites` (baseline, current) the change in the suite geomeans."""
+    print()
+    print("=" * 80)
+    rss_note = f", RSS threshold: {rss_fail_over_pct:g}%" if rss_fail_over_pct is not None else ""
+    print(
+        f"Comparison (throughput, or median time for latency benchmarks; "
+        f"regression threshold: {fail_over_pct:g}%{rss_note})"
+    )
+    print("=" * 80)
+    print()
+    print(
+        f"{'Benchmark':<20} {'Language':<10} {'Baseline':<14} "
+        f"{'Current':<14} {'Change':<10} {'RSS Change':<11} {'Status'}"
+    )
+    print("-" * 80)
+    for e in cmp.entries:
+        if e.regressed and e.rss_regressed:
+            status = "REGRESSED (time, RSS)"
+        elif e.regressed:
+            status = "REGRESSED"
+        elif e.rss_regressed:
+            status = "REGRESSED (RSS)"
+        elif e.change_pct < 0:
+            status = "faster"
+        else:
+            status = "ok"
+        rss = f"{e.rss_change_pct:+.1f}" if e.rss_change_pct is not None else "n/a"
+        if e.by_throughput:
+            baseline, current = f"{e.baseline_mib_s:.1f} MiB/s", f"{e.current_mib_s:.1f} MiB/s"
+        else:
+            baseline, current = f"{e.baseline_ms * 1000:.1f} us", f"{e.current_ms * 1000:.1f} us"
         print(
-            f"{benchmark:<20} {row['X07']:<12} {row['C']:<12} {row['Rust']:<12} {row['Go']:<12}"
+            f"{e.benchmark:<20} {e.language:<10} {baseline:<14} "
+            f"{current:<14} {e.change_pct:<+10.1f} {rss:<11} {status}"
         )
+    print()
+
+    movers = sorted(cmp.entries, key=lambda e: abs(e.change_pct), reverse=True)[:top]
+    if movers:
+        print(f"Biggest movers (top {len(movers)}):")
+        for e in movers:
+            print(f"  {e.benchmark}/{e.language}: {e.change_pct:+.1f}%")
+        print()
+
+    for label, keys in [
+        ("Only in baseline", cmp.only_in_baseline),
+        ("Only in current", cmp.only_in_current),
+        ("Failed in either run (not compared)", cmp.failed),
+    ]:
+        if keys:
+            print(f"{label}:")
+            for benchmark, language in keys:
+                print(f"  {benchmark}/{language}")
+            print()
+
+    if suites is not None:
+        print_suite_comparison(*suites)
+
+    n = len(cmp.regressions)
+    print(f"{n} regression(s)" if n else "No regressions")
+
+
+def compare_main(argv: list[str]) -> int:
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py compare",
+        description="Compare two results files and detect regressions",
+    )
+    ap.add_argument("baseline", type=Path, help="Baseline results JSON (e.g. baseline.json)")
+    ap.add_argument("current", type=Path, help="Current results JSON")
+    ap.add_argument("--fail-over", type=parse_percent, default=5.0, metavar="PCT",
+                    help="Fail if any benchmark is more than PCT slower, by throughput or "
+                         "latency (default: 5%%)")
+    ap.add_argument("--fail-rss-over", type=parse_percent, default=None, metavar="PCT",
+                    help="Also fail if any benchmark's peak RSS grew by more than PCT")
+    ap.add_argument("--top", type=int, default=5,
+                    help="Number of biggest movers to summarize (default: 5)")
+    ap.add_argument("--format", choices=["table", "csv"], default="table",
+                    help="Output format: csv has a row per matched benchmark and "
+                         "implementation (default: table)")
+    args = ap.parse_args(argv)
+
+    try:
+        baseline_config, baseline = load_results(args.baseline)
+        current_config, current = load_results(args.current)
+    except (OSError, ValueError, KeyError) as e:
+        ap.error(str(e))
+    # Adjusted and raw times differ by the startup cost; never compare across modes.
+    subtracted = {bool(c.get("subtract_baseline")) for c in (baseline_config, current_config)}
+    if len(subtracted) > 1:
+        ap.error("one results file was recorded with --subtract-baseline and the other "
+                 "without; rerun both in the same mode")
+
+    manifest = load_manifest(_perf_repo_root())
+    latency = frozenset(b for b in manifest if is_latency_benchmark(manifest, b))
+    cmp = compare_results(baseline, current, args.fail_over, args.fail_rss_over, latency)
+    if args.format == "csv":
+        write_comparison_csv(sys.stdout, cmp)
+        return 1 if cmp.regressions else 0
+    suites = (SuiteSummary.from_records(baseline, manifest),
+              SuiteSummary.from_records(current, manifest))
+    print_comparison(cmp, args.fail_over, args.top, args.fail_rss_over, suites)
+    return 1 if cmp.regressions else 0
+
+
+def _geomean(values: list[float]) -> float | None:
+    positive = [v for v in values if v > 0]
+    if not positive:
+        return None
+    return math.exp(sum(math.log(v) for v in positive) / len(positive))
+
+
+def _report_group(manifest: dict[str, dict[str, Any]], benchmark: str) -> str:
+    """The tag a report lists a benchmark under: its first declared tag, else its kind."""
+    return benchmark_tags(manifest, benchmark)[0]
+
+
+def _delta_cell(change: float | None) -> str:
+    """A current/baseline median ratio as "▲ +4.1%" (slower) or "▼ -3.0%" (faster)."""
+    if change is None:
+        return "-"
+    pct = (change - 1.0) * 100.0
+    if f"{pct:.1f}" in ("0.0", "-0.0"):
+        return "0.0%"
+    return f"{'▲' if pct > 0 else '▼'} {pct:+.1f}%"
+
+
+def render_markdown_report(
+    current: list[ResultRecord],
+    manifest: dict[str, dict[str, Any]],
+    baseline: list[ResultRecord] | None = None,
+    top: int | None = None,
+) -> str:
+    """A GitHub-flavored markdown table of Rust and X07 medians per benchmark
+    and input size, one table per tag (see _report_group), each ending in a
+    geometric-mean row.
+
+    With a baseline, each median also gets its change against the baseline
+    record of the same benchmark, implementation and size. With top, only
+    the `top` rows with the biggest change (or, without a baseline, the
+    biggest X07/Rust gap) are listed; the geomean rows still cover every row.
+    """
+    def medians(records: list[ResultRecord]) -> dict[tuple[str, int | None, str], float]:
+        return {(r.benchmark, r.size_kb, r.language): r.compared_time_ms
+                for r in records if r.success and r.compared_time_ms > 0}
+
+    cur = medians(current)
+    base = medians(baseline) if baseline is not None else {}
+    sizes = {(r.benchmark, r.size_kb): r.input_bytes for r in current}
+
+    rows = []
+    for benchmark, size_kb in sizes:
+        row: dict[str, Any] = {"benchmark": benchmark, "size_kb": size_kb}
+        for language in ("Rust", "X07"):
+            ms = cur.get((benchmark, size_kb, language))
+            was = base.get((benchmark, size_kb, language))
+            row[language] = ms
+            row[f"{language} change"] = ms / was if ms is not None and was is not None else None
+        if row["Rust"] is None and row["X07"] is None:
+            continue
+        row["ratio"] = row["X07"] / row["Rust"] if row["X07"] and row["Rust"] else None
+        if baseline is not None:
+            changes = [row[f"{lang} change"] for lang in ("Rust", "X07")]
+            row["rank"] = max((abs(math.log(c)) for c in changes if c), default=0.0)
+        else:
+            row["rank"] = abs(math.log(row["ratio"])) if row["ratio"] else 0.0
+        rows.append(row)
+
+    shown = rows
+    if top is not None:
+        keep = {id(r) for r in sorted(rows, key=lambda r: r["rank"], reverse=True)[:top]}
+        shown = [r for r in rows if id(r) in keep]
+
+    def cells(row: dict[str, Any]) -> list[str]:
+        out = []
+        for language in ("Rust", "X07"):
+            ms = row[language]
+            out.append(f"{ms:.3f}" if ms is not None else "-")
+            if baseline is not None:
+                out.append(_delta_cell(row[f"{language} change"]))
+        out.append(f"{row['ratio']:.2f}x" if row["ratio"] is not None else "-")
+        return out
+
+    header = ["Benchmark", "Size", "Rust median (ms)"]
+    if baseline is not None:
+        header.append("Rust change")
+    header.append("X07 median (ms)")
+    if baseline is not None:
+        header.append("X07 change")
+    header.append("X07/Rust")
+
+    groups: dict[str, list[dict[str, Any]]] = {}
+    for row in rows:
+        groups.setdefault(_report_group(manifest, row["benchmark"]), []).append(row)
+
+    lines = []
+    for group, members in groups.items():
+        listed = [r for r in members if any(r is s for s in shown)]
+        if not listed:
+            continue
+        lines += [f"### {group}", ""]
+        lines.append("| " + " | ".join(header) + " |")
+        lines.append("|" + "|".join(["---"] + ["---:"] * (len(header) - 1)) + "|")
+        for row in listed:
+            size_kb, input_bytes = row["size_kb"], sizes[(row["benchmark"], row["size_kb"])]
+            if size_kb is not None:
+                size = format_size_kb(size_kb)
+            else:
+                size = f"{input_bytes} B" if input_bytes else "-"
+            lines.append("| " + " | ".join([f"`{row['benchmark']}`", size] + cells(row)) + " |")
+        summary: dict[str, Any] = {"Rust": None, "X07": None}
+        for key in ("Rust change", "X07 change", "ratio"):
+            summary[key] = _geomean([r[key] for r in members if r[key] is not None])
+        summary_cells = ["-" if c == "-" else f"**{c}**" for c in cells(summary)]
+        label = f"geomean ({len(members)})"
+        if len(listed) < len(members):
+            label = f"geomean ({len(members)}, {len(listed)} shown)"
+        lines.append("| " + " | ".join([f"**{label}**", ""] + summary_cells) + " |")
+        lines.append("")
+
+    note = ("Medians are startup-subtracted where the run recorded them; X07/Rust is X07's "
+            "median over Rust's (above 1.00x: X07 is slower).")
+    if baseline is not None:
+        note = f"▲ slower than the baseline, ▼ faster. {note}"
+    lines.append(note)
+    return "\n".join(lines) + "\n"
+
+
+# HTML report changes within this many percent of the baseline are left
+# uncoloured: run-to-run noise, not a result.
+HTML_DELTA_NOISE_PCT = 2.0
+
+_HTML_STYLE = """
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 3px 8px; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+th { background: #eee; cursor: pointer; user-select: none; }
+td.slower { background: #f8d0d0; }
+td.faster { background: #d0f0d0; }
+.charts { display: flex; flex-wrap: wrap; gap: 1.5em; }
+"""
+
+# Click-to-sort for the results table: numeric cells carry data-sort.
+_HTML_SORT_SCRIPT = """
+document.querySelectorAll("table.sortable th").forEach((th, col) => {
+  th.addEventListener("click", () => {
+    const body = th.closest("table").tBodies[0];
+    const key = td => td.dataset.sort !== undefined ? parseFloat(td.dataset.sort) : td.textContent;
+    const up = th.dataset.dir !== "up";
+    th.dataset.dir = up ? "up" : "down";
+    const rows = Array.from(body.rows).sort((a, b) => {
+      const x = key(a.cells[col]), y = key(b.cells[col]);
+      return (x < y ? -1 : x > y ? 1 : 0) * (up ? 1 : -1);
+    });
+    rows.forEach(r => body.appendChild(r));
+  });
+});
+"""
+
+
+def render_html_report(
+    current: list[ResultRecord],
+    manifest: dict[str, dict[str, Any]],
+    baseline: list[ResultRecord] | None = None,
+    title: str = "x07-perf-compare results",
+) -> str:
+    """A self-contained HTML page: the suite summary, a sortable table of
+    every record, a bar chart of each benchmark's medians per input size,
+    and, for benchmarks recorded at several sizes, a line chart of how each
+    implementation scales. Charts are inline SVG from svg_charts; the only
+    script is the table sort.
+
+    With a baseline, records gain a change column (coloured beyond
+    HTML_DELTA_NOISE_PCT) matched by benchmark, implementation and size.
+    """
+    def key(r: ResultRecord) -> tuple[str, int | None, str]:
+        return r.benchmark, r.size_kb, r.language
+
+    was = {key(r): r.compared_time_ms for r in baseline or []
+           if r.success and r.compared_time_ms > 0}
+
+    def change_pct(r: ResultRecord) -> float | None:
+        before = was.get(key(r))
+        if not r.success or r.compared_time_ms <= 0 or before is None:
+            return None
+        return (r.compared_time_ms / before - 1.0) * 100.0
+
+    def size_text(r: ResultRecord) -> str:
+        if r.size_kb is not None:
+            return format_size_kb(r.size_kb)
+        return f"{r.input_bytes} B" if r.input_bytes else "-"
+
+    def num(value: float | None, text: str) -> str:
+        sort = "" if value is None else f' data-sort="{value}"'
+        return f'<td class="num"{sort}>{html.escape(text)}</td>'
+
+    out = [
+        "<!DOCTYPE html>",
+        '<html lang="en"><head><meta charset="utf-8">',
+        f"<title>{html.escape(title)}</title>",
+        f"<style>{_HTML_STYLE}</style>",
+        "</head><body>",
+        f"<h1>{html.escape(title)}</h1>",
+    ]
+
+    summary = SuiteSummary.from_records(current, manifest)
+    before = SuiteSummary.from_records(baseline, manifest) if baseline is not None else None
+    out.append("<h2>Suite summary</h2>")
+    out.append("<p>Geometric mean of X07/Rust median ratios"
+               + (" (baseline → current)" if before is not None else "") + ":</p><ul>")
+    rows = [("overall", (summary.geomean, summary.count),
+             (before.geomean, before.count) if before is not None else None)]
+    for tag, value in summary.by_tag.items():
+        rows.append((tag, value, before.by_tag.get(tag, (None, 0)) if before is not None else None))
+    for label, (geomean, count), old in rows:
+        text = f"{label}: {_describe_ratio(geomean)} over {count} benchmark(s)"
+        if old is not None:
+            text = f"{label}: {_describe_ratio(old[0])} → {_describe_ratio(geomean)}"
+            text += f" over {count} benchmark(s)"
+        out.append(f"<li>{html.escape(text)}</li>")
+    out.append("</ul>")
+    if summary.excluded:
+        excluded = ", ".join(f"{name} ({why})" for name, why in summary.excluded)
+        out.append(f"<p>Excluded: {html.escape(excluded)}</p>")
+
+    header = ["Benchmark", "Size", "Language", "Median (ms)", "Throughput (MiB/s)",
+              "Peak RSS (KiB)"]
+    if baseline is not None:
+        header.append("Change")
+    header.append("Status")
+    out.append("<h2>Results</h2>")
+    out.append('<table class="sortable"><thead><tr>'
+               + "".join(f"<th>{html.escape(h)}</th>" for h in header)
+               + "</tr></thead><tbody>")
+    for r in current:
+        cells = [
+            f"<td>{html.escape(r.benchmark)}</td>",
+            num(r.size_kb if r.size_kb is not None else r.input_bytes / 1024, size_text(r)),
+            f"<td>{html.escape(r.language)}</td>",
+        ]
+        if r.success:
+            mib_s = throughput_mib_s(r.input_bytes, r.compared_time_ms)
+            if is_latency_benchmark(manifest, r.benchmark):
+                mib_s = None
+            cells.append(num(r.compared_time_ms, f"{r.compared_time_ms:.3f}"))
+            cells.append(num(mib_s, f"{mib_s:.1f}" if mib_s is not None else "-"))
+        else:
+            cells += [num(None, "-"), num(None, "-")]
+        rss = r.peak_rss_kb
+        cells.append(num(rss, str(rss) if rss is not None else "-"))
+        if baseline is not None:
+            pct = change_pct(r)
+            cls = ""
+            if pct is not None and abs(pct) > HTML_DELTA_NOISE_PCT:
+                cls = " slower" if pct > 0 else " faster"
+            text = f"{pct:+.1f}%" if pct is not None else "-"
+            sort = f' data-sort="{pct}"' if pct is not None else ""
+            cells.append(f'<td class="num{cls}"{sort}>{html.escape(text)}</td>')
+        if r.timed_out:
+            status = "TIMEOUT"
+        elif not r.success:
+            status = f"FAIL: {r.error}"
+        else:
+            status = "UNSTABLE" if r.unstable else "OK"
+            status += ", cached" if r.cached else ""
+            status += ", not converged" if r.converged is False else ""
+        cells.append(f"<td>{html.escape(status)}</td>")
+        out.append("<tr>" + "".join(cells) + "</tr>")
+    out.append("</tbody></table>")
+
+    by_benchmark: dict[str, list[ResultRecord]] = {}
+    for r in current:
+        by_benchmark.setdefault(r.benchmark, []).append(r)
+    out.append("<h2>Benchmarks</h2>")
+    for benchmark, records in by_benchmark.items():
+        ok = [r for r in records if r.success and r.compared_time_ms > 0]
+        if not ok:
+            continue
+        out.append(f"<h3>{html.escape(benchmark)}</h3>")
+        out.append('<div class="charts">')
+        sizes = list(dict.fromkeys(r.size_kb for r in ok))
+        for size_kb in sizes:
+            at_size = [r for r in ok if r.size_kb == size_kb]
+            notes = {}
+            for r in at_size:
+                pct = change_pct(r)
+                if pct is not None:
+                    notes[r.language] = f"({pct:+.1f}%)"
+            label = f" @ {format_size_kb(size_kb)}" if size_kb is not None else ""
+            out.append(svg_charts.bar_chart(
+                f"{benchmark}{label}: median",
+                [(r.language, r.compared_time_ms) for r in at_size],
+                "median time (ms)",
+                notes=notes,
+            ))
+        if len([s for s in sizes if s is not None]) > 1:
+            latency = is_latency_benchmark(manifest, benchmark)
+            series: dict[str, list[tuple[float, float]]] = {}
+            for r in ok:
+                if r.size_kb is None or r.size_kb <= 0:
+                    continue
+                y = r.compared_time_ms if latency else throughput_mib_s(
+                    r.input_bytes, r.compared_time_ms)
+                if y is not None:
+                    series.setdefault(r.language, []).append((float(r.size_kb), y))
+            out.append(svg_charts.line_chart(
+                f"{benchmark}: scaling",
+                series,
+                "input size (KiB)",
+                "median time (ms)" if latency else "throughput (MiB/s)",
+            ))
+        out.append("</div>")
+
+    out.append(f"<script>{_HTML_SORT_SCRIPT}</script>")
+    out.append("</body></html>")
+    return "\n".join(out) + "\n"
+
+
+def report_main(argv: list[str]) -> int:
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py report",
+        description="Render results files as markdown tables for a pull request, or as a "
+                    "self-contained HTML page with charts",
+    )
+    ap.add_argument("results", type=Path, nargs="+", metavar="RESULTS",
+                    help="A results JSON file, or a baseline and a current one to compare")
+    ap.add_argument("--format", choices=["markdown", "html"], default="markdown",
+                    help="Output format (default: markdown)")
+    ap.add_argument("--top", type=int, default=None, metavar="N",
+                    help="List only the N rows with the biggest change against the baseline "
+                         "(with one file: the biggest X07/Rust gap)")
+    args = ap.parse_args(argv)
+    if len(args.results) > 2:
+        ap.error("expected one results file, or a baseline and a current one")
+    if args.top is not None and args.format != "markdown":
+        ap.error("--top applies to --format markdown only")
+
+    try:
+        loaded = [load_results(path) for path in args.results]
+    except (OSError, ValueError, KeyError) as e:
+        ap.error(str(e))
+    subtracted = {bool(config.get("subtract_baseline")) for config, _ in loaded}
+    if len(subtracted) > 1:
+        ap.error("one results file was recorded with --subtract-baseline and the other "
+                 "without; rerun both in the same mode")
+
+    baseline = loaded[0][1] if len(loaded) == 2 else None
+    manifest = load_manifest(_perf_repo_root())
+    if args.format == "html":
+        sys.stdout.write(render_html_report(loaded[-1][1], manifest, baseline))
+    else:
+        sys.stdout.write(render_markdown_report(loaded[-1][1], manifest, baseline, args.top))
+    return 0
+
+
+def render_scaling_plot(records: list[ResultRecord], benchmark: str) -> str:
+    """A log-log SVG of benchmark's median time against input size, one
+    series per implementation, with error bars from the fastest to the
+    slowest sample. Raises ValueError when there is nothing to plot.
+
+    Points need a recorded size and a positive time (a log axis has no
+    zero); failed runs and such points are left out.
+    """
+    runs = [r for r in records if r.benchmark == benchmark]
+    if not runs:
+        names = ", ".join(sorted({r.benchmark for r in records})) or "none"
+        raise ValueError(f"no results for {benchmark!r} (the file has: {names})")
+    series: dict[str, list[tuple[float, float, float, float]]] = {}
+    for r in runs:
+        if not r.success or r.size_kb is None or r.size_kb <= 0:
+            continue
+        low = min(r.samples_ms, default=r.min_time_ms)
+        high = max(r.samples_ms, default=r.max_time_ms)
+        if min(low, r.median_time_ms) <= 0:
+            continue
+        series.setdefault(r.language, []).append(
+            (float(r.size_kb), r.median_time_ms, low, high))
+    if not series:
+        raise ValueError(f"{benchmark!r} has no successful runs with a recorded size to plot")
+    return svg_charts.log_log_chart(
+        f"{benchmark}: median time by input size",
+        series,
+        "input size",
+        "median time (ms)",
+        x_format=lambda x: format_size_kb(int(x)),
+    ) + "\n"
+
+
+def plot_main(argv: list[str]) -> int:
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py plot",
+        description="Plot one benchmark's median time against input size from a results "
+                    "file (e.g. one recorded with --sweep) as a log-log SVG",
+    )
+    ap.add_argument("results", type=Path, metavar="RESULTS", help="A results JSON file")
+    ap.add_argument("--bench", required=True, metavar="NAME", help="The benchmark to plot")
+    ap.add_argument("--out", type=Path, required=True, metavar="PATH",
+                    help="Where to write the SVG (- for stdout)")
+    args = ap.parse_args(argv)
+
+    try:
+        _, records = load_results(args.results)
+        svg = render_scaling_plot(records, args.bench)
+    except (OSError, ValueError, KeyError) as e:
+        ap.error(str(e))
+    if str(args.out) == "-":
+        sys.stdout.write(svg)
+    else:
+        args.out.write_text(svg, encoding="utf-8")
+        print(f"wrote {args.out}", file=sys.stderr)
+    return 0
+
+
+def _command_output(cmd: list[str], cwd: Path | None = None) -> str | None:
+    """cmd's stripped stdout, or None if it can't be run or fails."""
+    try:
+        res = subprocess.run(cmd, cwd=cwd, capture_output=True, text=True, timeout=30)
+    except (OSError, subprocess.TimeoutExpired):
+        return None
+    return (res.stdout.strip() or None) if res.returncode == 0 else None
+
+
+def history_run_info(
+    perf_repo_root: Path, started_at: str, config: dict[str, Any]
+) -> history_db.RunInfo:
+    return history_db.RunInfo(
+        started_at=started_at,
+        git_commit=_command_output(["git", "rev-parse", "HEAD"], cwd=perf_repo_root),
+        hostname=socket.gethostname(),
+        rustc_version=_command_output(["rustc", "--version"]),
+        config=json.dumps(config, sort_keys=True),
+    )
+
+
+RATIO_COLUMN = "X07/Rust"
+
+
+def history_main(argv: list[str]) -> int:
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py history",
+        description="Print a benchmark's medians across the runs recorded with --db, and "
+                    "the largest slowdown from one commit to the next",
+    )
+    ap.add_argument("--db", type=Path, required=True, metavar="PATH",
+                    help="History database written by runs with --db")
+    ap.add_argument("--bench", required=True, metavar="NAME", help="The benchmark to show")
+    ap.add_argument("--host", default=None, metavar="NAME",
+                    help="Only runs recorded on this host (default: all)")
+    args = ap.parse_args(argv)
+    if not args.db.is_file():
+        ap.error(f"{args.db}: no such file")
+
+    try:
+        conn = history_db.connect(args.db)
+        points = history_db.history(conn, args.bench, args.host)
+    except history_db.HistoryError as e:
+        ap.error(str(e))
+    if not points:
+        where = f" from {args.host}" if args.host else ""
+        ap.error(f"{args.db}: no successful runs of {args.bench!r}{where}")
+
+    sizes = sorted({p.size_kb for p in points}, key=lambda k: (k is None, k or 0))
+    languages = sorted({p.language for p in points})
+
+    def column(language: str, size_kb: int | None) -> str:
+        if len(sizes) == 1:
+            return language
+        return f"{language}@{format_size_kb(size_kb) if size_kb is not None else 'file'}"
+
+    columns = [(lang, size) for size in sizes for lang in languages
+               if any(p.language == lang and p.size_kb == size for p in points)]
+    # The gap this history is mostly kept for, per size where both ran.
+    columns += [(RATIO_COLUMN, size) for size in sizes
+                if ("X07", size) in columns and ("Rust", size) in columns]
+    runs: dict[int, dict[tuple[str, int | None], history_db.HistoryPoint]] = {}
+    for p in points:
+        runs.setdefault(p.run_id, {})[(p.language, p.size_kb)] = p
+    widths = [max(10, len(column(*c))) for c in columns]
+
+    print(f"{args.bench}: median time (ms) per run, oldest first")
+    header = f"{'Run':>5}  {'Started (UTC)':<25}  {'Commit':<12}  {'Host':<16}"
+    print(header + "".join(f"  {column(*c):>{w}}" for c, w in zip(columns, widths)))
+    for run_id, row in runs.items():
+        first = next(iter(row.values()))
+        cells = []
+        for lang, size in columns:
+            if lang == RATIO_COLUMN:
+                x07, rust = row.get(("X07", size)), row.get(("Rust", size))
+                ok = x07 is not None and rust is not None and rust.median_time_ms > 0
+                cells.append(f"{x07.median_time_ms / rust.median_time_ms:.2f}x" if ok else "-")
+            else:
+                cells.append(f"{row[lang, size].median_time_ms:.3f}" if (lang, size) in row
+                             else "-")
+        print(f"{run_id:>5}  {first.started_at:<25}  {(first.git_commit or '-')[:12]:<12}  "
+              f"{first.hostname[:16]:<16}" + "".join(f"  {v:>{w}}" for v, w in zip(cells, widths)))
+    print()
+
+    worst = history_db.largest_regression(points)
+    if worst is None:
+        print("No median went up from one commit to the next.")
+    else:
+        where = f" @ {format_size_kb(worst.size_kb)}" if worst.size_kb is not None else ""
+        print(f"Largest single-commit regression: {worst.language}{where} {worst.pct:+.1f}% "
+              f"({worst.before.median_time_ms:.3f} -> {worst.after.median_time_ms:.3f} ms) "
+              f"at {worst.after.git_commit[:12]} (run {worst.after.run_id}), after "
+              f"{worst.before.git_commit[:12]} (run {worst.before.run_id}) "
+              f"on {worst.after.hostname}")
+    return 0
+
+
+def hex_context(data: bytes, offset: int, radius: int = 8) -> str:
+    """Hex dump of data around offset, e.g. "00000010: 61 62 [63] 64"."""
+    start = max(0, offset - radius)
+    window = data[start : offset + radius]
+    cells = [
+        f"[{b:02x}]" if start + i == offset else f"{b:02x}" for i, b in enumerate(window)
+    ]
+    if offset >= len(data):
+        cells.append("[EOF]")
+    return f"{start:08x}: " + " ".join(cells)
+
+
+def first_f64_mismatch(a: bytes, b: bytes, epsilon: float) -> int | None:
+    """Index of the first f64 LE element where a and b differ by more than
+    epsilon relative to the larger magnitude (absolute below 1), or None if
+    every element is within it. Both lengths must be a multiple of 8."""
+    x, y = array.array("d", a), array.array("d", b)
+    if sys.byteorder == "big":
+        x.byteswap()
+        y.byteswap()
+    return next((i for i, (u, v) in enumerate(zip(x, y))
+                 if not abs(u - v) <= epsilon * max(1.0, abs(u), abs(v))), None)
+
+
+def _run_x07_binary(binary: Path, data: bytes) -> tuple[int, bytes]:
+    """Run an X07 binary via its length-prefixed ABI, returning (exit, output).
+
+    The output is unwrapped only on success; otherwise raw stdout is returned.
+    """
+    res = _run_child([str(binary)], input=struct.pack("<I", len(data)) + data,
+                     capture_output=True)
+    raw = res.stdout
+    if res.returncode != 0 or len(raw) < 4:
+        return res.returncode, raw
+    out_len = struct.unpack("<I", raw[:4])[0]
+    return res.returncode, raw[4 : 4 + out_len]
+
+
+def _build_x07(
+    perf_repo_root: Path, program: Path, host_runner: Path, cc_profile: str, artifact: Path
+) -> None:
+    """Compile an X07 program, or the project it is the entry of."""
+    project = _x07_project_for(perf_repo_root, program)
+    if project is not None:
+        project_file, entry = project
+        original = _set_project_entry(project_file, entry)
+        try:
+            X07ProjectRunner(host_runner, cc_profile=cc_profile).compile(project_file, artifact)
+        finally:
+            project_file.write_text(original)
+        return
+    X07DirectRunner(host_runner, cc_profile=cc_profile).compile(program, artifact)
+
+
+def _build_rust(program: Path, binary: Path) -> None:
+    """Build a Rust program: a rust_cargo/ crate, or a single file."""
+    if (program / "Cargo.toml").exists():
+        RustCargoRunner().compile(program, binary)
+    else:
+        RustRunner().compile(program, binary)
+
+
+def has_x07_and_rust(spec: BenchmarkSpec) -> bool:
+    return "X07" in spec.implementations and "Rust" in spec.implementations
+
+
+def output_mismatch(
+    rust_out: bytes,
+    x07_out: bytes,
+    epsilon: float | None = None,
+    byte_tolerance: int | None = None,
+) -> tuple[int, str] | None:
+    """Where two outputs first differ, as (byte offset, description), or None
+    if they agree. Equal-length outputs may be compared within a tolerance:
+    `epsilon` as f64 LE values (see first_f64_mismatch), `byte_tolerance` as
+    bytes differing by at most that much, e.g. image channels."""
+    if rust_out == x07_out:
+        return None
+    if len(rust_out) == len(x07_out):
+        if epsilon is not None and len(rust_out) % 8 == 0:
+            index = first_f64_mismatch(rust_out, x07_out, epsilon)
+            if index is None:
+                return None
+            return 8 * index, f"stdout differs at f64 {index} by more than {epsilon:g} (relative)"
+        if byte_tolerance is not None:
+            pairs = enumerate(zip(rust_out, x07_out))
+            offset = next((i for i, (a, b) in pairs if abs(a - b) > byte_tolerance), None)
+            if offset is None:
+                return None
+            return offset, f"stdout differs at byte {offset} by more than {byte_tolerance}"
+    n = min(len(x07_out), len(rust_out))
+    offset = next((i for i in range(n) if x07_out[i] != rust_out[i]), n)
+    return offset, f"stdout differs at byte {offset} (rust {len(rust_out)} bytes, x07 {len(x07_out)} bytes)"
+
+
+def diff_benchmark(
+    spec: BenchmarkSpec,
+    input_data: InputData,
+    host_runner: Path,
+    perf_repo_root: Path,
+    tmp_dir: Path,
+    cc_profile: str = "default",
+) -> list[str]:
+    """Run the Rust and X07 versions on the same input; return the differences.
+    stdout is compared byte for byte unless the spec gives a tolerance (see
+    output_mismatch)."""
+    x07_bin = tmp_dir / f"{spec.name}_x07"
+    rust_bin = tmp_dir / f"{spec.name}_rust"
+    _build_x07(perf_repo_root, spec.implementations["X07"], host_runner, cc_profile, x07_bin)
+    _build_rust(spec.implementations["Rust"], rust_bin)
+
+    x07_exit, x07_out = _run_x07_binary(x07_bin, input_data.data)
+    rust = _run_child([str(rust_bin)], input=input_data.data, capture_output=True)
+    rust_exit, rust_out = rust.returncode, rust.stdout
+
+    problems = []
+    if x07_exit != rust_exit:
+        problems.append(f"exit status differs: rust {rust_exit}, x07 {x07_exit}")
+    mismatch = output_mismatch(rust_out, x07_out, spec.diff_epsilon, spec.diff_byte_tolerance)
+    if mismatch is not None:
+        offset, description = mismatch
+        problems.append(description)
+        problems.append(f"  rust {hex_context(rust_out, offset)}")
+        problems.append(f"  x07  {hex_context(x07_out, offset)}")
+    return problems
+
+
+def diff_main(argv: list[str]) -> int:
+    perf_repo_root = _perf_repo_root()
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py diff",
+        description="Check that the Rust and X07 versions of each benchmark agree",
+    )
+    ap.add_argument("--x07-host-runner", type=Path, default=None,
+                    help="Path to x07-host-runner (env: X07_HOST_RUNNER; default: search PATH)")
+    ap.add_argument("--x07-toolchain", type=Path, default=None,
+                    help="Path to extracted X07 toolchain dir (env: X07_TOOLCHAIN)")
+    ap.add_argument("--x07-cc-profile", choices=["default", "size"],
+                    default=os.environ.get("X07_CC_PROFILE", "default").lower(),
+                    help="Pass through to x07-host-runner --cc-profile (default: default)")
+    ap.add_argument("--benchmarks", nargs="+", default=None,
+                    help="Benchmarks to check (default: all with Rust and X07 versions)")
+    ap.add_argument("--size", type=parse_size_kb, default=100,
+                    help="Generated input size, e.g. 100 (KiB) or 1MiB (default: 100)")
+    ap.add_argument("--seed", type=int, default=42, help="Input generator seed (default: 42)")
+    ap.add_argument("--input", type=Path, default=None,
+                    help="Use this file as every benchmark's input instead of generating one")
+    args = ap.parse_args(argv)
+
+    try:
+        host_runner = _resolve_x07_host_runner(
+            perf_repo_root, args.x07_host_runner, args.x07_toolchain
+        )
+    except Exception as e:
+        ap.error(str(e))
+
+    manifest = load_manifest(perf_repo_root)
+    specs = {b: BenchmarkSpec.load(perf_repo_root, manifest, b)
+             for b in discover_benchmarks(perf_repo_root)}
+    paired = [b for b, spec in specs.items() if has_x07_and_rust(spec)]
+    benchmarks = args.benchmarks if args.benchmarks else paired
+    unpaired = [b for b in benchmarks if b not in paired]
+    if unpaired:
+        ap.error(f"no Rust and X07 pair for: {', '.join(unpaired)}")
+    if args.input is not None and not args.input.is_file():
+        ap.error(f"input file not found: {args.input}")
+
+    failures = 0
+    with tempfile.TemporaryDirectory(prefix="perf_compare_diff_") as tmp:
+        for benchmark in benchmarks:
+            spec = specs[benchmark]
+            if args.input is not None:
+                input_data = load_input_file(args.input)
+            else:
+                input_data = generate_input_data(spec.generator, args.size, seed=args.seed,
+                                                 params=resolve_gen_params(manifest, benchmark, []))
+            try:
+                problems = diff_benchmark(
+                    spec, input_data, host_runner, perf_repo_root, Path(tmp),
+                    cc_profile=args.x07_cc_profile,
+                )
+            except Exception as e:
+                problems = [f"error: {e}"]
+
+            allowed = manifest.get(benchmark, {}).get("diff_allowed")
+            if not problems:
+                print(f"{benchmark}: same")
+            elif allowed:
+                print(f"{benchmark}: differs (allowed: {allowed})")
+            else:
+                failures += 1
+                print(f"{benchmark}: DIFFERS")
+            for line in problems:
+                print(f"  {line}")
 
     print()
+    print(f"{failures} benchmark(s) differ" if failures else "No unexpected differences")
+    return 1 if failures else 0
+
+
+def gen_main(argv: list[str]) -> int:
+    perf_repo_root = _perf_repo_root()
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py gen",
+        description="Write a benchmark's generated input to a file",
+    )
+    ap.add_argument("--bench", required=True, help="Benchmark whose input format to generate")
+    ap.add_argument("--size", type=parse_size_kb, default=None,
+                    help=f"Input size, e.g. 100 (KiB), 512KiB, 64MiB (default: the benchmark's "
+                         f"size in {BENCHMARK_MANIFEST}, else {DEFAULT_SIZE_KB})")
+    ap.add_argument("--seed", type=int, default=None,
+                    help=f"PRNG seed (default: the benchmark's seed, else {DEFAULT_SEED})")
+    ap.add_argument("--param", dest="params", action="append", default=[], metavar="KEY=VALUE",
+                    help="Distribution parameter override (repeatable)")
+    ap.add_argument("--out", type=Path, required=True, help="Output file")
+    args = ap.parse_args(argv)
+
+    if args.bench not in discover_benchmarks(perf_repo_root):
+        ap.error(f"unknown benchmark: {args.bench}")
+    manifest = load_manifest(perf_repo_root)
+    try:
+        params = resolve_gen_params(manifest, args.bench, args.params)
+    except ValueError as e:
+        ap.error(str(e))
+
+    spec = BenchmarkSpec.load(perf_repo_root, manifest, args.bench)
+    input_data = generate_input_data(
+        spec.generator,
+        _first_set(args.size, spec.size_kb, DEFAULT_SIZE_KB),
+        seed=_first_set(args.seed, spec.seed, DEFAULT_SEED),
+        params=params,
+    )
+    args.out.parent.mkdir(parents=True, exist_ok=True)
+    args.out.write_bytes(input_data.data)
+    print(f"{args.out}: {len(input_data.data)} bytes, sha256 {input_data.sha256}", file=sys.stderr)
+    return 0
+
+
+def cache_main(argv: list[str]) -> int:
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py cache",
+        description="Manage the results cache (see --no-cache)",
+    )
+    ap.add_argument("action", choices=["clear"], help="clear: delete every cached entry")
+    ap.add_argument("--results-cache", type=Path, default=None, metavar="DIR",
+                    help=f"Results cache directory (default: {default_results_cache()})")
+    args = ap.parse_args(argv)
+
+    root = args.results_cache or default_results_cache()
+    removed = ResultsCache(root).clear()
+    print(f"{root}: removed {removed} cached result(s)", file=sys.stderr)
+    return 0
+
+
+def list_main(argv: list[str]) -> int:
+    perf_repo_root = _perf_repo_root()
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py list",
+        description="List the benchmarks a run with the same selection flags would run",
+    )
+    ap.add_argument("--benchmarks", nargs="+", default=None,
+                    help="Benchmarks to choose from (default: all)")
+    add_selection_args(ap)
+    args = ap.parse_args(argv)
+
+    all_benchmarks = discover_benchmarks(perf_repo_root)
+    benchmarks = args.benchmarks if args.benchmarks else all_benchmarks
+    unknown = [b for b in benchmarks if b not in all_benchmarks]
+    if unknown:
+        ap.error(f"unknown benchmark(s): {', '.join(unknown)}")
+    manifest = load_manifest(perf_repo_root)
+    try:
+        benchmarks = select_benchmarks(benchmarks, manifest, args)
+    except ValueError as e:
+        ap.error(str(e))
+
+    for benchmark in benchmarks:
+        print(f"{benchmark:<28} {','.join(benchmark_tags(manifest, benchmark))}")
+    return 0
+
+
+def validate_manifest_main(argv: list[str]) -> int:
+    perf_repo_root = _perf_repo_root()
+    ap = argparse.ArgumentParser(
+        prog="run_benchmarks.py validate-manifest",
+        description=f"Check {BENCHMARK_MANIFEST}: its schema, the benchmarks and programs it "
+                    "names, and that each entry's input generator runs",
+    )
+    ap.parse_args(argv)
+
+    problems = validate_manifest(perf_repo_root)
+    for problem in problems:
+        print(problem)
+    entries = len(load_manifest(perf_repo_root))
+    print(f"{len(problems)} problem(s)" if problems else f"{entries} entries OK", file=sys.stderr)
+    return 1 if problems else 0
 
 
 def main(argv: list[str]) -> int:
+    if argv and argv[0] == "compare":
+        return compare_main(argv[1:])
+    if argv and argv[0] == "gen":
+        return gen_main(argv[1:])
+    if argv and argv[0] == "diff":
+        return diff_main(argv[1:])
+    if argv and argv[0] == "cache":
+        return cache_main(argv[1:])
+    if argv and argv[0] == "list":
+        return list_main(argv[1:])
+    if argv and argv[0] == "validate-manifest":
+        return validate_manifest_main(argv[1:])
+    if argv and argv[0] == "report":
+        return report_main(argv[1:])
+    if argv and argv[0] == "plot":
+        return plot_main(argv[1:])
+    if argv and argv[0] == "history":
+        return history_main(argv[1:])
+
     ap = argparse.ArgumentParser(description="Run performance comparison benchmarks")
     ap.add_argument(
         "--x07-host-runner",
@@ -1118,14 +5095,82 @@ def main(argv: list[str]) -> int:
         default=None,
         help="Path to extracted X07 toolchain dir (env: X07_TOOLCHAIN)",
     )
-    ap.add_argument("--size", type=int, default=100, help="Input size in KB (default: 100)")
-    ap.add_argument("--iterations", type=int, default=5, help="Number of iterations (default: 5)")
-    ap.add_argument("--warmup", type=int, default=2, help="Warmup iterations (default: 2)")
+    ap.add_argument("--size", type=parse_size_kb, default=None,
+                    help=f"Input size, e.g. 100 (KiB) or 64MiB (default: the benchmark's size "
+                         f"in {BENCHMARK_MANIFEST}, else {DEFAULT_SIZE_KB})")
+    ap.add_argument("--seed", type=int, default=None,
+                    help=f"Input generator seed (default: the benchmark's seed, "
+                         f"else {DEFAULT_SEED})")
+    ap.add_argument("--gen-param", dest="gen_params", action="append", default=[],
+                    metavar="KEY=VALUE", help="Input generator parameter override (repeatable)")
+    ap.add_argument("--sweep", type=parse_sweep, default=None, metavar="SIZES",
+                    help="Run every benchmark at each of these sizes instead of --size, "
+                         "e.g. 1KiB,64KiB,1MiB")
+    ap.add_argument("--input-cache", type=Path, default=None, metavar="DIR",
+                    help="Where --sweep keeps generated inputs between runs "
+                         f"(default: {default_input_cache()})")
+    ap.add_argument("--input", type=Path, default=None,
+                    help="Feed this file on stdin instead of generated input data; "
+                         "generated first if missing (needs a single --benchmarks entry)")
+    ap.add_argument("--samples", "--iterations", dest="samples", type=int, default=None,
+                    help=f"Timed samples per benchmark (default: the benchmark's samples, "
+                         f"else {DEFAULT_SAMPLES})")
+    ap.add_argument("--warmup", type=int, default=2,
+                    help="Untimed warmup runs discarded before sampling (default: 2)")
+    ap.add_argument("--adaptive", action="store_true",
+                    help="Sample until the median's bootstrap 95%% interval is within "
+                         "--adaptive-target of it, with --samples as the minimum")
+    ap.add_argument("--adaptive-target", type=parse_percent, default=2.0, metavar="PCT",
+                    help="How close the interval must be, e.g. 2%% (default: 2%%)")
+    ap.add_argument("--max-samples", type=int, default=200, metavar="N",
+                    help="With --adaptive, stop at N samples (default: 200)")
+    ap.add_argument("--max-sample-time", type=float, default=30.0, metavar="SECS",
+                    help="With --adaptive, stop once an implementation's sampling has taken "
+                         "SECS seconds (default: 30)")
+    ap.add_argument("--db", type=Path, default=None, metavar="PATH",
+                    help="Also append this run's results to a SQLite history database "
+                         "(created if missing; see the history subcommand)")
+    ap.add_argument("--no-cache", action="store_true",
+                    help="Take new samples even where the results cache has them")
+    ap.add_argument("--results-cache", type=Path, default=None, metavar="DIR",
+                    help=f"Where timed samples are kept for reuse "
+                         f"(default: {default_results_cache()})")
+    ap.add_argument("--cache-max-size", type=parse_size_kb,
+                    default=DEFAULT_CACHE_MAX_SIZE_KB, metavar="SIZE",
+                    help="Evict the oldest cached results beyond this total, e.g. 64MiB "
+                         f"(default: {format_size_kb(DEFAULT_CACHE_MAX_SIZE_KB)})")
+    ap.add_argument("--cache-max-age", type=float, default=DEFAULT_CACHE_MAX_AGE_DAYS,
+                    metavar="DAYS",
+                    help="Evict cached results measured more than DAYS ago "
+                         f"(default: {DEFAULT_CACHE_MAX_AGE_DAYS:g})")
     ap.add_argument("--benchmarks", nargs="+", default=None,
                     help="Specific benchmarks to run (default: all)")
-    ap.add_argument("--json", action="store_true", help="Output results as JSON")
+    add_selection_args(ap)
+    ap.add_argument("--format", choices=["table", "json", "csv", "csv-summary"], default="table",
+                    help="Output format: csv has a row per timed sample, csv-summary a row "
+                         "per implementation and size with its statistics (default: table)")
+    ap.add_argument("--json", dest="format", action="store_const", const="json",
+                    help="Shorthand for --format json")
     ap.add_argument("--direct", action="store_true",
                     help="Run X07 binaries directly (no host runner overhead)")
+    ap.add_argument("--timeout", type=float, default=None, metavar="SECS",
+                    help=f"Kill a benchmark process after SECS seconds; 0 disables "
+                         f"(default: the benchmark's timeout, else {DEFAULT_TIMEOUT_S:g})")
+    ap.add_argument("--strict", action="store_true",
+                    help="Exit non-zero if any implementation failed or timed out")
+    ap.add_argument("--pin", type=int, default=None, metavar="CORE",
+                    help="Pin every benchmark process to CPU CORE (Linux; elsewhere a "
+                         "warning and no pinning)")
+    ap.add_argument("--nice", type=int, default=None, metavar="N",
+                    help="Run benchmark processes at nice increment N; a negative N raises "
+                         "their priority and needs root or CAP_SYS_NICE")
+    ap.add_argument("--subtract-baseline", action="store_true",
+                    help=f"Also report each median minus the same implementation's "
+                         f"{NOOP_BENCHMARK} median (startup cost)")
+    ap.add_argument("--golden", type=Path, default=None,
+                    help=f"Expected-output manifest (default: {GOLDEN_MANIFEST} in the repo)")
+    ap.add_argument("--bless", action="store_true",
+                    help="Record the first implementation's output hash in the golden manifest")
     ap.add_argument(
         "--x07-cc-profile",
         choices=["default", "size"],
@@ -1133,6 +5178,22 @@ def main(argv: list[str]) -> int:
         help="Pass through to x07-host-runner --cc-profile (default: default)",
     )
     args = ap.parse_args(argv)
+    started_at = datetime.datetime.now(datetime.timezone.utc).isoformat(timespec="seconds")
+    if args.max_samples < 1:
+        ap.error("--max-samples must be at least 1")
+
+    def adaptive_policy(samples: int) -> AdaptivePolicy | None:
+        if not args.adaptive:
+            return None
+        return AdaptivePolicy(target=args.adaptive_target / 100,
+                              min_samples=min(samples, args.max_samples),
+                              max_samples=args.max_samples,
+                              max_seconds=args.max_sample_time)
+    try:
+        for warning in set_child_placement(args.pin, args.nice):
+            print(f"warning: {warning}", file=sys.stderr)
+    except ValueError as e:
+        ap.error(str(e))
 
     perf_repo_root = _perf_repo_root()
     try:
@@ -1142,72 +5203,223 @@ def main(argv: list[str]) -> int:
     except Exception as e:
         ap.error(str(e))
 
-    all_benchmarks = [
-        "sum_bytes",
-        "word_count",
-        "rle_encode",
-        "byte_freq",
-        "fibonacci",
-        # "regex_is_match",
-        # "regex_count",
-        # "regex_replace",
-    ]
+    all_benchmarks = discover_benchmarks(perf_repo_root)
     benchmarks = args.benchmarks if args.benchmarks else all_benchmarks
+    unknown = [b for b in benchmarks if b not in all_benchmarks]
+    if unknown:
+        ap.error(f"unknown benchmark(s): {', '.join(unknown)}")
+    manifest = load_manifest(perf_repo_root)
+    try:
+        benchmarks = select_benchmarks(benchmarks, manifest, args)
+    except ValueError as e:
+        ap.error(str(e))
+    specs = {b: BenchmarkSpec.load(perf_repo_root, manifest, b)
+             for b in benchmarks + [NOOP_BENCHMARK]}
+    generators = {b: specs[b].generator for b in benchmarks}
+    size_kb_default = _first_set(args.size, DEFAULT_SIZE_KB)
+    # Each override applies to the selected benchmarks that define it.
+    for pair in args.gen_params:
+        key = pair.partition("=")[0]
+        if not any(key in GEN_PARAMS.get(generators[b], {}) for b in benchmarks):
+            ap.error(f"--gen-param {pair}: no selected benchmark takes {key!r}")
+    try:
+        gen_params = {
+            b: resolve_gen_params(
+                manifest, b,
+                [kv for kv in args.gen_params
+                 if kv.partition("=")[0] in GEN_PARAMS.get(generators[b], {})],
+            )
+            for b in benchmarks
+        }
+    except ValueError as e:
+        ap.error(str(e))
+
+    def default_input(benchmark: str) -> bool:
+        """Whether benchmark gets the input its entry describes, which is the
+        one its output_sha256 is for."""
+        return (args.input is None and args.sweep is None and args.size is None
+                and args.seed is None
+                and gen_params[benchmark] == resolve_gen_params(manifest, benchmark, []))
+
+    if args.sweep and args.input is not None:
+        ap.error("--sweep and --input are mutually exclusive")
+    if args.input is not None and not args.input.exists():
+        if len(benchmarks) != 1:
+            ap.error(f"input file not found: {args.input} "
+                     "(pass a single benchmark to generate it)")
+        spec = specs[benchmarks[0]]
+        generated = generate_input_data(
+            spec.generator, _first_set(args.size, spec.size_kb, DEFAULT_SIZE_KB),
+            seed=_first_set(args.seed, spec.seed, DEFAULT_SEED), params=gen_params[benchmarks[0]],
+        )
+        args.input.parent.mkdir(parents=True, exist_ok=True)
+        args.input.write_bytes(generated.data)
+        print(f"Generated {args.input} (sha256 {generated.sha256})", file=sys.stderr)
+    if args.input is not None and not args.input.is_file():
+        ap.error(f"input file not found: {args.input}")
+
+    golden_path = args.golden if args.golden is not None else perf_repo_root / GOLDEN_MANIFEST
+    try:
+        golden = GoldenManifest.load(golden_path)
+    except (OSError, ValueError) as e:
+        ap.error(str(e))
+
+    results_cache = None
+    if not args.no_cache:
+        results_cache = ResultsCache(
+            args.results_cache or default_results_cache(),
+            max_bytes=args.cache_max_size * 1024,
+            max_age_s=args.cache_max_age * 86400,
+        )
+
+    db = None
+    if args.db is not None:
+        try:
+            db = history_db.connect(args.db)
+        except history_db.HistoryError as e:
+            ap.error(str(e))
 
     all_results: dict[str, list[BenchmarkResult]] = {}
 
     with tempfile.TemporaryDirectory(prefix="perf_compare_") as tmp:
         tmp_dir = Path(tmp)
 
+        startup_baselines: dict[str, float] | None = None
+        if args.subtract_baseline:
+            print(f"Measuring startup baseline ({NOOP_BENCHMARK})...", file=sys.stderr)
+            _, _, samples, timeout = specs[NOOP_BENCHMARK].run_settings(args)
+            set_child_timeout(timeout or None)
+            startup_baselines = {
+                r.language: r.median_time_ms
+                for r in run_benchmark(
+                    NOOP_BENCHMARK,
+                    generate_input_data(specs[NOOP_BENCHMARK].generator, 0),
+                    x07_host_runner,
+                    perf_repo_root,
+                    tmp_dir,
+                    iterations=samples,
+                    warmup=args.warmup,
+                    direct_mode=args.direct,
+                    x07_cc_profile=args.x07_cc_profile,
+                    implementations=specs[NOOP_BENCHMARK].implementations,
+                    results_cache=results_cache,
+                    adaptive=adaptive_policy(samples),
+                )
+                if r.success
+            }
+
+        input_cache = args.input_cache or default_input_cache()
         for benchmark in benchmarks:
-            print(f"Running benchmark: {benchmark}...", file=sys.stderr)
-
-            input_data = generate_input_data(benchmark, args.size)
-
-            results = run_benchmark(
-                benchmark,
-                input_data,
-                x07_host_runner,
-                perf_repo_root,
-                tmp_dir,
-                iterations=args.iterations,
-                warmup=args.warmup,
-                direct_mode=args.direct,
-                x07_cc_profile=args.x07_cc_profile,
-            )
-
-            all_results[benchmark] = results
-
-    if args.json:
-        output = {}
-        for benchmark, results in all_results.items():
-            output[benchmark] = [
-                {
-                    "language": r.language,
-                    "mean_time_ms": r.mean_time_ms,
-                    "min_time_ms": r.min_time_ms,
-                    "stddev_time_ms": r.stddev_time_ms,
-                    "compile_time_ms": r.compile_time_ms,
-                    "build_size_bytes": r.build_size_bytes,
-                    "peak_rss_kb": r.peak_rss_kb,
-                    "success": r.success,
-                    "error": r.error,
-                    "x07_cc_profile": args.x07_cc_profile if r.language == "X07" else None,
-                }
-                for r in results
-            ]
-        print(json.dumps(output, indent=2))
+            all_results[benchmark] = []
+            size, seed, samples, timeout = specs[benchmark].run_settings(args)
+            set_child_timeout(timeout or None)
+            for size_kb in args.sweep or [size]:
+                if args.sweep:
+                    print(f"Running benchmark: {benchmark} ({format_size_kb(size_kb)})...",
+                          file=sys.stderr)
+                    input_data = cached_input_data(
+                        input_cache, generators[benchmark], size_kb, seed, gen_params[benchmark]
+                    )
+                else:
+                    print(f"Running benchmark: {benchmark}...", file=sys.stderr)
+                    if args.input is not None:
+                        input_data = load_input_file(args.input)
+                    else:
+                        input_data = generate_input_data(
+                            generators[benchmark], size_kb, seed=seed, params=gen_params[benchmark]
+                        )
+
+                results = run_benchmark(
+                    benchmark,
+                    input_data,
+                    x07_host_runner,
+                    perf_repo_root,
+                    tmp_dir,
+                    iterations=samples,
+                    warmup=args.warmup,
+                    direct_mode=args.direct,
+                    x07_cc_profile=args.x07_cc_profile,
+                    golden=golden,
+                    bless=args.bless,
+                    implementations=specs[benchmark].implementations,
+                    output_sha256=(specs[benchmark].output_sha256
+                                   if default_input(benchmark) else None),
+                    output_store=default_output_store(),
+                    results_cache=results_cache,
+                    adaptive=adaptive_policy(samples),
+                )
+
+                for r in results:
+                    r.input_sha256 = input_data.sha256
+                    r.input_bytes = len(input_data.data)
+                    r.size_kb = size_kb if args.input is None else None
+                    if startup_baselines is not None:
+                        r.startup_baseline_ms = startup_baselines.get(r.language)
+                all_results[benchmark].extend(results)
+
+    if args.bless:
+        golden.save()
+        print(f"Updated {golden_path}", file=sys.stderr)
+
+    records = [
+        ResultRecord.from_result(
+            r, args.x07_cc_profile, latency=is_latency_benchmark(manifest, r.benchmark)
+        )
+        for results in all_results.values()
+        for r in results
+    ]
+    config = {
+        "size_kb": size_kb_default,
+        "sweep": args.sweep,
+        "input_file": str(args.input) if args.input is not None else None,
+        "seed": _first_set(args.seed, DEFAULT_SEED),
+        "gen_params": args.gen_params,
+        "samples": _first_set(args.samples, DEFAULT_SAMPLES),
+        "warmup": args.warmup,
+        "direct": args.direct,
+        "subtract_baseline": args.subtract_baseline,
+        "x07_cc_profile": args.x07_cc_profile,
+        "adaptive": {
+            "target": args.adaptive_target / 100,
+            "max_samples": args.max_samples,
+            "max_seconds": args.max_sample_time,
+        } if args.adaptive else None,
+        "environment": measurement_environment(),
+    }
+    if db is not None:
+        run_id = history_db.insert_run(
+            db, history_run_info(perf_repo_root, started_at, config), records)
+        print(f"Recorded run {run_id} in {args.db}", file=sys.stderr)
+    if args.format == "csv":
+        write_samples_csv(sys.stdout, records)
+    elif args.format == "csv-summary":
+        write_summary_csv(sys.stdout, records)
+    elif args.format == "json":
+        summary = SuiteSummary.from_records(records, manifest)
+        print(json.dumps(results_document(records, config, summary), indent=2))
     else:
         print_results(
             all_results,
-            args.size,
+            size_kb_default,
             direct_mode=args.direct,
             x07_cc_profile=args.x07_cc_profile,
+            manifest=manifest,
+            sweep=args.sweep,
+            startup_baselines=startup_baselines,
+            adaptive=args.adaptive,
         )
-        print_summary_table(all_results)
+        print_pairs(all_results, manifest)
+        print_summary_table(all_results, sweep=args.sweep)
+        print_suite_summary(SuiteSummary.from_records(records, manifest))
 
+    if args.strict and any(not r.success for rs in all_results.values() for r in rs):
+        return 1
     return 0
 
 
 if __name__ == "__main__":
-    raise SystemExit(main(sys.argv[1:]))
+    try:
+        raise SystemExit(main(sys.argv[1:]))
+    except ManifestError as e:
+        print(f"run_benchmarks.py: {e}", file=sys.stderr)
+        raise SystemExit(2)
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+// Input: a mode byte, then m and n as u32 LE. Output: the Ackermann function
+// A(m, n) as a u64 LE:
+//
+//   A(0, n) = n + 1
+//   A(m, 0) = A(m - 1, 1)
+//   A(m, n) = A(m - 1, A(m, n - 1))
+//
+//   0  recursive: the definition as written
+//   1  memo: the same recursion, with every A(m, n) for m > 0 kept in a
+//      HashMap and looked up before recursing
+//   2  iterative: an explicit Vec stack of pending m values, n carried along
+//
+// The recursion is as deep as the result, so every mode runs on a thread
+// with a 64 MiB stack (the same for all three, so they compare fairly), and
+// inputs are limited to what fits with room to spare (memo's frames are
+// about 64 bytes): m at most 3 and A(m, n) at most 2^19, which for m = 3
+// (A = 2^(n+3) - 3) is n at most 16. Anything else, an unknown or missing
+// mode byte, or a short input exits with status 2.
+//
+// A(2, 3) = 9, A(3, 3) = 61, A(3, 8) = 2045; A(1, 524286) = 2^19 is the
+// deepest input accepted.
+const MODE_RECURSIVE: u8 = 0;
+const MODE_MEMO: u8 = 1;
+const MODE_ITERATIVE: u8 = 2;
+const MAX_M: u32 = 3;
+const MAX_RESULT: u64 = 1 << 19;
+const STACK_SIZE: usize = 64 << 20;
+
+/// A(m, n) in closed form, for checking the input before computing it.
+fn closed_form(m: u32, n: u64) -> u64 {
+    match m {
+        0 => n + 1,
+        1 => n + 2,
+        2 => 2 * n + 3,
+        _ => (1u64 << (n + 3).min(63)) - 3,
+    }
+}
+
+fn ack_recursive(m: u32, n: u64) -> u64 {
+    match (m, n) {
+        (0, n) => n + 1,
+        (m, 0) => ack_recursive(m - 1, 1),
+        (m, n) => ack_recursive(m - 1, ack_recursive(m, n - 1)),
+    }
+}
+
+fn ack_memo(m: u32, n: u64, memo: &mut HashMap<(u32, u64), u64>) -> u64 {
+    if m == 0 {
+        return n + 1;
+    }
+    if let Some(&v) = memo.get(&(m, n)) {
+        return v;
+    }
+    let v = if n == 0 {
+        ack_memo(m - 1, 1, memo)
+    } else {
+        let inner = ack_memo(m, n - 1, memo);
+        ack_memo(m - 1, inner, memo)
+    };
+    memo.insert((m, n), v);
+    v
+}
+
+fn ack_iterative(m: u32, mut n: u64) -> u64 {
+    let mut stack = vec![m];
+    while let Some(m) = stack.pop() {
+        if m == 0 {
+            n += 1;
+        } else if n == 0 {
+            stack.push(m - 1);
+            n = 1;
+        } else {
+            stack.push(m - 1);
+            stack.push(m);
+            n -= 1;
+        }
+    }
+    n
+}
+
+fn run(input: &[u8]) -> Result<u64, String> {
+    let (&mode, rest) = input.split_first().ok_or("missing mode byte")?;
+    if ![MODE_RECURSIVE, MODE_MEMO, MODE_ITERATIVE].contains(&mode) {
+        return Err(format!("unknown mode {mode}"));
+    }
+    if rest.len() < 8 {
+        return Err(format!("truncated header: need 8 bytes after the mode, got {}", rest.len()));
+    }
+    let m = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+    let n = u32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]]) as u64;
+    if m > MAX_M {
+        return Err(format!("m = {m} is over {MAX_M}"));
+    }
+    if closed_form(m, n) > MAX_RESULT {
+        return Err(format!("A({m}, {n}) is over 2^19, too deep to recurse"));
+    }
+
+    let worker = std::thread::Builder::new().stack_size(STACK_SIZE).spawn(move || match mode {
+        MODE_RECURSIVE => ack_recursive(m, n),
+        MODE_MEMO => ack_memo(m, n, &mut HashMap::new()),
+        _ => ack_iterative(m, n),
+    });
+    Ok(worker.unwrap().join().unwrap())
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(v) => std::io::stdout().write_all(&v.to_le_bytes()).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
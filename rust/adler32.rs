@@ -0,0 +1,43 @@
+use std::io::{Read, Write};
+
+// zlib Adler-32: a = 1 + sum of bytes, b = sum of the successive values of
+// a, both mod 65521, packed as b << 16 | a. Output: the checksum as u32 LE
+// (1 for empty input, 0x11E60398 for "Wikipedia").
+//
+// The sums are reduced once per NMAX bytes, the most that can be added
+// before b could overflow a u32. `--naive` reduces after every byte
+// instead, to cross-check.
+const MOD: u32 = 65521;
+const NMAX: usize = 5552;
+
+fn adler32(input: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for block in input.chunks(NMAX) {
+        for &x in block {
+            a += x as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    b << 16 | a
+}
+
+fn adler32_naive(input: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &x in input {
+        a = (a + x as u32) % MOD;
+        b = (b + a) % MOD;
+    }
+    b << 16 | a
+}
+
+fn main() {
+    let naive = std::env::args().skip(1).any(|a| a == "--naive");
+
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let sum = if naive { adler32_naive(&input) } else { adler32(&input) };
+    std::io::stdout().write_all(&sum.to_le_bytes()).unwrap();
+}
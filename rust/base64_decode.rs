@@ -0,0 +1,69 @@
+use std::io::{Read, Write};
+
+const INVALID: u8 = 0xFF;
+
+const fn decode_table() -> [u8; 256] {
+    let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+const DECODE: [u8; 256] = decode_table();
+
+// Strict RFC 4648 decoding: the input length must be a multiple of 4, '='
+// may only pad the final quantum, the unused bits before padding must be
+// zero, and any other byte (including whitespace) is rejected. Rejected
+// input exits with status 2 and writes nothing to stdout.
+fn decode(input: &[u8]) -> Result<Vec<u8>, String> {
+    if !input.len().is_multiple_of(4) {
+        return Err(format!("length {} is not a multiple of 4", input.len()));
+    }
+
+    let mut output = Vec::with_capacity(input.len() / 4 * 3);
+    let quanta = input.len() / 4;
+    for (q, c) in input.chunks_exact(4).enumerate() {
+        let pad = match c {
+            [_, _, b'=', b'='] => 2,
+            [_, _, _, b'='] => 1,
+            _ => 0,
+        };
+        if pad > 0 && q + 1 != quanta {
+            return Err(format!("padding before end of input at byte {}", q * 4));
+        }
+
+        let mut n = 0u32;
+        for (i, &b) in c[..4 - pad].iter().enumerate() {
+            let v = DECODE[b as usize];
+            if v == INVALID {
+                return Err(format!("invalid character 0x{b:02x} at byte {}", q * 4 + i));
+            }
+            n |= (v as u32) << (18 - 6 * i);
+        }
+
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        if bytes[3 - pad..].iter().any(|&b| b != 0) {
+            return Err(format!("non-zero padding bits in final quantum at byte {}", q * 4));
+        }
+        output.extend_from_slice(&bytes[..3 - pad]);
+    }
+
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match decode(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
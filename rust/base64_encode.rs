@@ -0,0 +1,38 @@
+use std::io::{Read, Write};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Standard RFC 4648 base64 with '=' padding and no line breaks.
+    let mut output = Vec::with_capacity(input.len().div_ceil(3) * 4);
+    let chunks = input.chunks_exact(3);
+    let tail = chunks.remainder();
+    for c in chunks {
+        let n = (c[0] as u32) << 16 | (c[1] as u32) << 8 | c[2] as u32;
+        output.push(ALPHABET[(n >> 18) as usize & 63]);
+        output.push(ALPHABET[(n >> 12) as usize & 63]);
+        output.push(ALPHABET[(n >> 6) as usize & 63]);
+        output.push(ALPHABET[n as usize & 63]);
+    }
+    match *tail {
+        [a] => {
+            let n = (a as u32) << 16;
+            output.push(ALPHABET[(n >> 18) as usize & 63]);
+            output.push(ALPHABET[(n >> 12) as usize & 63]);
+            output.extend_from_slice(b"==");
+        }
+        [a, b] => {
+            let n = (a as u32) << 16 | (b as u32) << 8;
+            output.push(ALPHABET[(n >> 18) as usize & 63]);
+            output.push(ALPHABET[(n >> 12) as usize & 63]);
+            output.push(ALPHABET[(n >> 6) as usize & 63]);
+            output.push(b'=');
+        }
+        _ => {}
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,98 @@
+use std::io::{Read, Write};
+
+// Input: u32 LE node count N, u32 LE edge count M, M (u, v) pairs of u32 LE
+// node ids, then a (source, target) pair. Edges are undirected; self-loops
+// and duplicate edges are allowed and change no distance. Output: the
+// number of edges on a shortest source-target path as u32 LE, 0 when source
+// == target and u32::MAX when the target is unreachable. A node id >= N or
+// an input that is not exactly 16 + 8M bytes exits with status 2.
+//
+// The edges are packed into CSR form (offsets per node into one neighbour
+// array), then a queue-driven BFS runs from the source until it dequeues the
+// target.
+//
+// With N = 5 and edges (0,1) (1,2) (1,2) (2,2) (0,3), source 2: target 3
+// gives 3, target 2 gives 0, target 4 (isolated) gives u32::MAX.
+struct Graph {
+    n: usize,
+    offsets: Vec<u32>,
+    neighbours: Vec<u32>,
+}
+
+fn parse(input: &[u8]) -> Result<(Graph, u32, u32), String> {
+    let word = |i: usize| {
+        let w = &input[4 * i..4 * i + 4];
+        u32::from_le_bytes([w[0], w[1], w[2], w[3]])
+    };
+    if input.len() < 8 {
+        return Err(format!("truncated header: need 8 bytes, got {}", input.len()));
+    }
+    let (n, m) = (word(0) as usize, word(1) as usize);
+    if input.len() as u64 != 16 + 8 * m as u64 {
+        return Err(format!("{m} edges need {} bytes, got {}", 16 + 8 * m as u64, input.len()));
+    }
+    let check = |v: u32| match v as usize {
+        i if i < n => Ok(v),
+        _ => Err(format!("node {v} out of range for {n} nodes")),
+    };
+
+    let mut offsets = vec![0u32; n + 1];
+    for e in 0..m {
+        let (u, v) = (check(word(2 + 2 * e))?, check(word(3 + 2 * e))?);
+        offsets[u as usize + 1] += 1;
+        offsets[v as usize + 1] += 1;
+    }
+    for i in 0..n {
+        offsets[i + 1] += offsets[i];
+    }
+    let mut fill = offsets.clone();
+    let mut neighbours = vec![0u32; 2 * m];
+    for e in 0..m {
+        let (u, v) = (word(2 + 2 * e), word(3 + 2 * e));
+        neighbours[fill[u as usize] as usize] = v;
+        fill[u as usize] += 1;
+        neighbours[fill[v as usize] as usize] = u;
+        fill[v as usize] += 1;
+    }
+
+    let (source, target) = (check(word(2 + 2 * m))?, check(word(3 + 2 * m))?);
+    Ok((Graph { n, offsets, neighbours }, source, target))
+}
+
+fn bfs(g: &Graph, source: u32, target: u32) -> u32 {
+    let mut dist = vec![u32::MAX; g.n];
+    let mut queue = Vec::with_capacity(g.n);
+    dist[source as usize] = 0;
+    queue.push(source);
+    let mut head = 0;
+    while head < queue.len() {
+        let u = queue[head] as usize;
+        head += 1;
+        if u == target as usize {
+            break;
+        }
+        let next = dist[u] + 1;
+        for &v in &g.neighbours[g.offsets[u] as usize..g.offsets[u + 1] as usize] {
+            if dist[v as usize] == u32::MAX {
+                dist[v as usize] = next;
+                queue.push(v);
+            }
+        }
+    }
+    dist[target as usize]
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match parse(&input) {
+        Ok((graph, source, target)) => {
+            std::io::stdout().write_all(&bfs(&graph, source, target).to_le_bytes()).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::io::{Read, Write};
+
+mod bignum;
+
+// Input: n as a u32 LE. Output: n! as a u32 LE limb count, then that many
+// u64 LE limbs, least significant first, with no zero limb at the top (0!
+// and 1! are one limb, 1).
+//
+// The product is built one factor at a time: schoolbook multiplication of
+// the limbs by the u64 scalar k for k = 2..=n, each limb's product and carry
+// held in a u128 (rust/bignum, shared with pi_digits). No crate, so this
+// measures the same hand-rolled loop an X07 bignum would run;
+// bigint_factorial_crate does it with num-bigint.
+//
+// 20! is one limb, 0x21c3677c82b40000 (2432902008176640000). 100! is 9
+// limbs: 0x1b30964ec395dc24069528d54bbda40d16e966ef9a70eb21b5b2943a321cdf1039
+// 1745570cca9420c6ecb3b72ed2ee8b02ea2735c61a000000000000000000000000.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.len() < 4 {
+        eprintln!("malformed input: truncated header: need 4 bytes, got {}", input.len());
+        std::process::exit(2);
+    }
+    let n = u32::from_le_bytes([input[0], input[1], input[2], input[3]]);
+
+    let mut limbs = vec![1u64];
+    for k in 2..=n as u64 {
+        bignum::mul_scalar(&mut limbs, k);
+    }
+
+    let mut output = Vec::with_capacity(4 + 8 * limbs.len());
+    output.extend_from_slice(&(limbs.len() as u32).to_le_bytes());
+    for limb in &limbs {
+        output.extend_from_slice(&limb.to_le_bytes());
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,104 @@
+// Unsigned bignums as Vec<u64> limbs, least significant first, with no zero
+// limb at the top, so zero is the empty Vec. Shared by bigint_factorial and
+// pi_digits, which each use only some of it. Everything here is a single
+// O(limbs) pass: no bignum-by-bignum multiplication or general division.
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+
+fn trim(a: &mut Vec<u64>) {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+}
+
+/// a *= k, schoolbook, each limb's product and carry held in a u128.
+pub fn mul_scalar(a: &mut Vec<u64>, k: u64) {
+    if k == 0 {
+        a.clear();
+        return;
+    }
+    let mut carry = 0u64;
+    for limb in a.iter_mut() {
+        let wide = *limb as u128 * k as u128 + carry as u128;
+        *limb = wide as u64;
+        carry = (wide >> 64) as u64;
+    }
+    if carry != 0 {
+        a.push(carry);
+    }
+}
+
+/// a += b * k.
+pub fn add_mul(a: &mut Vec<u64>, b: &[u64], k: u64) {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut carry = 0u128;
+    for (i, limb) in a.iter_mut().enumerate() {
+        if i >= b.len() && carry == 0 {
+            break;
+        }
+        let product = b.get(i).map_or(0, |&l| l as u128 * k as u128);
+        let wide = *limb as u128 + product + carry;
+        *limb = wide as u64;
+        carry = wide >> 64;
+    }
+    if carry != 0 {
+        a.push(carry as u64);
+    }
+    trim(a);
+}
+
+/// a -= b * k. Panics if b * k is greater than a.
+pub fn sub_mul(a: &mut Vec<u64>, b: &[u64], k: u64) {
+    // borrow is what is still owed to the next limb up, at most k + 1 limbs'
+    // worth of 2^64, so it fits a u128.
+    let mut borrow = 0u128;
+    for (i, limb) in a.iter_mut().enumerate() {
+        if i >= b.len() && borrow == 0 {
+            break;
+        }
+        let owed = b.get(i).map_or(0, |&l| l as u128 * k as u128) + borrow;
+        let (low, high) = (owed as u64, (owed >> 64) as u64);
+        let (diff, under) = limb.overflowing_sub(low);
+        *limb = diff;
+        borrow = high as u128 + under as u128;
+    }
+    // b's top limb is non-zero, so a shorter a is smaller than b * k too.
+    assert!(borrow == 0 && (k == 0 || a.len() >= b.len()), "sub_mul: result below zero");
+    trim(a);
+}
+
+pub fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// The top 128 bits of a >> shift, for a >> shift below 2^128.
+fn bits_from(a: &[u64], shift: usize) -> u128 {
+    let (word, bit) = (shift / 64, shift % 64);
+    let limb = |i: usize| a.get(i).copied().unwrap_or(0) as u128;
+    let wide = limb(word) | limb(word + 1) << 64;
+    let spill = if bit == 0 { 0 } else { limb(word + 2) << (128 - bit) };
+    (wide >> bit) | spill
+}
+
+/// floor(a / b), leaving the remainder in a. The quotient must be below
+/// 2^62 and b non-zero.
+///
+/// The quotient is estimated from the top 64 bits of b and the bits of a
+/// above the same point, which is within one of the true one, then fixed up
+/// by subtracting b at most twice.
+pub fn div_rem_small(a: &mut Vec<u64>, b: &[u64]) -> u64 {
+    assert!(!b.is_empty(), "div_rem_small: division by zero");
+    let b_bits = b.len() * 64 - b[b.len() - 1].leading_zeros() as usize;
+    let shift = b_bits.saturating_sub(64);
+    let estimate = bits_from(a, shift) / bits_from(b, shift);
+    let mut q = (estimate as u64).saturating_sub(1);
+    sub_mul(a, b, q);
+    while cmp(a, b) != Ordering::Less {
+        sub_mul(a, b, 1);
+        q += 1;
+    }
+    q
+}
@@ -0,0 +1,46 @@
+use std::io::{Read, Write};
+
+// Output: for each pair of adjacent bytes present in stdin, overlapping, a
+// record of the pair's two bytes in input order then its count as a u64 LE,
+// in ascending pair order. Fewer than two input bytes give no output.
+//
+// "abab" holds "ab" twice and "ba" once: records 61 62 with count 2, then
+// 62 61 with count 1.
+//
+// Unlike byte_freq's 256 counters, the 65,536 here take 512 KiB: on random
+// input each count misses L1 and often L2, while a low-entropy input keeps
+// its few hot counters cached.
+const CHUNK: usize = 256 * 1024;
+const PAIRS: usize = 1 << 16;
+
+fn main() {
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = vec![0u8; CHUNK];
+    let mut counts = vec![0u64; PAIRS].into_boxed_slice();
+
+    // The last byte of the previous chunk, which pairs with the next one.
+    let mut prev: Option<u8> = None;
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        let chunk = &buf[..n];
+        if let Some(p) = prev {
+            counts[(p as usize) << 8 | chunk[0] as usize] += 1;
+        }
+        for w in chunk.windows(2) {
+            counts[(w[0] as usize) << 8 | w[1] as usize] += 1;
+        }
+        prev = Some(chunk[n - 1]);
+    }
+
+    let mut output = Vec::new();
+    for (pair, &count) in counts.iter().enumerate() {
+        if count > 0 {
+            output.extend_from_slice(&[(pair >> 8) as u8, pair as u8]);
+            output.extend_from_slice(&count.to_le_bytes());
+        }
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
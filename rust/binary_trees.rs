@@ -0,0 +1,64 @@
+use std::io::{Read, Write};
+
+// The Benchmarks Game binary-trees workload with Box-allocated nodes.
+//
+// Input: the depth n as u32 LE. With min_depth = 4 and
+// max_depth = max(min_depth + 2, n):
+//   1. build and check a "stretch" tree of depth max_depth + 1;
+//   2. build a long-lived tree of depth max_depth and keep it;
+//   3. for d = min_depth, min_depth + 2, ..., max_depth: build and check
+//      2^(max_depth - d + min_depth) trees of depth d, summing their checks;
+//   4. check the long-lived tree.
+// A tree of depth 0 is a single node; check() is the node count. The output
+// is every check value in that order (stretch, one sum per d, long-lived),
+// each as i64 LE. A short input exits with status 2.
+//
+// Known answers: n = 6 gives 255, 1984, 2032, 127; n = 10 gives 4095,
+// 31744, 32512, 32704, 32752, 2047.
+const EXIT_MALFORMED: i32 = 2;
+const MIN_DEPTH: u32 = 4;
+
+struct Node {
+    children: Option<(Box<Node>, Box<Node>)>,
+}
+
+fn bottom_up_tree(depth: u32) -> Box<Node> {
+    let children = if depth > 0 {
+        Some((bottom_up_tree(depth - 1), bottom_up_tree(depth - 1)))
+    } else {
+        None
+    };
+    Box::new(Node { children })
+}
+
+fn check(node: &Node) -> i64 {
+    match &node.children {
+        Some((left, right)) => 1 + check(left) + check(right),
+        None => 1,
+    }
+}
+
+fn main() {
+    let mut buf = [0u8; 4];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    let n = u32::from_le_bytes(buf);
+    let max_depth = n.max(MIN_DEPTH + 2);
+
+    let mut checks = vec![check(&bottom_up_tree(max_depth + 1))];
+
+    let long_lived = bottom_up_tree(max_depth);
+
+    for depth in (MIN_DEPTH..=max_depth).step_by(2) {
+        let iterations = 1u64 << (max_depth - depth + MIN_DEPTH);
+        let sum: i64 = (0..iterations).map(|_| check(&bottom_up_tree(depth))).sum();
+        checks.push(sum);
+    }
+
+    checks.push(check(&long_lived));
+
+    let output: Vec<u8> = checks.iter().flat_map(|c| c.to_le_bytes()).collect();
+    std::io::stdout().write_all(&output).unwrap();
+}
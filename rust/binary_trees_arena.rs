@@ -0,0 +1,87 @@
+use std::io::{Read, Write};
+
+// binary_trees with every tree stored in a Vec arena: nodes refer to their
+// children by index, and the arena for the short-lived trees is cleared and
+// reused between iterations instead of freeing and reallocating each node.
+// Same input and output as binary_trees.
+const EXIT_MALFORMED: i32 = 2;
+const MIN_DEPTH: u32 = 4;
+
+// Index of the left child; the right child is always left + 1. 0 marks a
+// leaf (the root is at index 0, so no child ever is).
+struct Node {
+    left: u32,
+}
+
+struct Arena {
+    nodes: Vec<Node>,
+}
+
+impl Arena {
+    fn with_depth(depth: u32) -> Self {
+        Arena { nodes: Vec::with_capacity((1usize << (depth + 1)) - 1) }
+    }
+
+    /// Builds a tree of `depth` (replacing any previous one) and returns
+    /// its root index.
+    fn build(&mut self, depth: u32) -> u32 {
+        self.nodes.clear();
+        self.nodes.push(Node { left: 0 });
+        self.fill(0, depth);
+        0
+    }
+
+    fn fill(&mut self, at: u32, depth: u32) {
+        if depth == 0 {
+            return;
+        }
+        let left = self.nodes.len() as u32;
+        self.nodes.push(Node { left: 0 });
+        self.nodes.push(Node { left: 0 });
+        self.nodes[at as usize].left = left;
+        self.fill(left, depth - 1);
+        self.fill(left + 1, depth - 1);
+    }
+
+    fn check(&self, at: u32) -> i64 {
+        match self.nodes[at as usize].left {
+            0 => 1,
+            left => 1 + self.check(left) + self.check(left + 1),
+        }
+    }
+}
+
+fn main() {
+    let mut buf = [0u8; 4];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    let n = u32::from_le_bytes(buf);
+    let max_depth = n.max(MIN_DEPTH + 2);
+
+    let mut stretch = Arena::with_depth(max_depth + 1);
+    let root = stretch.build(max_depth + 1);
+    let mut checks = vec![stretch.check(root)];
+    drop(stretch);
+
+    let mut long_lived = Arena::with_depth(max_depth);
+    let long_lived_root = long_lived.build(max_depth);
+
+    let mut scratch = Arena::with_depth(max_depth);
+    for depth in (MIN_DEPTH..=max_depth).step_by(2) {
+        let iterations = 1u64 << (max_depth - depth + MIN_DEPTH);
+        let sum: i64 = (0..iterations)
+            .map(|_| {
+                let root = scratch.build(depth);
+                scratch.check(root)
+            })
+            .sum();
+        checks.push(sum);
+    }
+
+    checks.push(long_lived.check(long_lived_root));
+
+    let output: Vec<u8> = checks.iter().flat_map(|c| c.to_le_bytes()).collect();
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,88 @@
+use std::io::{Read, Write};
+
+// Input: a u32 LE count N, then newline-separated lines: the first N are
+// keys to insert, the rest are membership queries (a final newline is
+// optional; lines are bytes and may be empty). Output: the number of
+// queries the filter reports present, as u64 LE. Fewer than N lines exits
+// with status 2.
+//
+// Every parameter is fixed so that false positives are reproducible:
+//
+// - m = 8N bits rounded up to a multiple of 64, at least 64, stored as u64
+//   words; bit j is bit j % 64 of word j / 64.
+// - h1 = 64-bit FNV-1a of the line (offset basis 0xcbf29ce484222325, prime
+//   0x100000001b3: per byte, xor then multiply, wrapping).
+// - h2 = the same with offset basis 0x6c62272e07bb0142 (the high half of
+//   the 128-bit FNV offset basis).
+// - k = 6 bits per line: (h1 + i * h2) % m for i in 0..6, the sum and
+//   product wrapping in u64.
+//
+// Keys "apple", "banana", "cherry" (m = 64) with queries "apple", "grape",
+// "w85", "kiwi", "cherry" give 3: "w85" is a false positive. Every inserted
+// key is always reported present.
+const K: u64 = 6;
+const FNV_PRIME: u64 = 0x100000001b3;
+const BASIS_1: u64 = 0xcbf29ce484222325;
+const BASIS_2: u64 = 0x6c62272e07bb0142;
+
+fn fnv1a(data: &[u8], basis: u64) -> u64 {
+    data.iter().fold(basis, |h, &b| (h ^ b as u64).wrapping_mul(FNV_PRIME))
+}
+
+struct Bloom {
+    words: Vec<u64>,
+    m: u64,
+}
+
+impl Bloom {
+    fn new(n: u64) -> Self {
+        let words = (8 * n).div_ceil(64).max(1);
+        Bloom { words: vec![0; words as usize], m: 64 * words }
+    }
+
+    fn insert(&mut self, line: &[u8]) {
+        let (h1, h2) = (fnv1a(line, BASIS_1), fnv1a(line, BASIS_2));
+        for i in 0..K {
+            let j = h1.wrapping_add(i.wrapping_mul(h2)) % self.m;
+            self.words[(j / 64) as usize] |= 1 << (j % 64);
+        }
+    }
+
+    fn contains(&self, line: &[u8]) -> bool {
+        let (h1, h2) = (fnv1a(line, BASIS_1), fnv1a(line, BASIS_2));
+        (0..K).all(|i| {
+            let j = h1.wrapping_add(i.wrapping_mul(h2)) % self.m;
+            self.words[(j / 64) as usize] >> (j % 64) & 1 == 1
+        })
+    }
+}
+
+fn run(input: &[u8]) -> Result<u64, String> {
+    if input.len() < 4 {
+        return Err(format!("truncated count: need 4 bytes, got {}", input.len()));
+    }
+    let n = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as u64;
+    let mut lines = input[4..]
+        .split_inclusive(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\n").unwrap_or(line));
+
+    let mut filter = Bloom::new(n);
+    for i in 0..n {
+        let key = lines.next().ok_or_else(|| format!("{n} keys declared, {i} present"))?;
+        filter.insert(key);
+    }
+    Ok(lines.filter(|q| filter.contains(q)).count() as u64)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(present) => std::io::stdout().write_all(&present.to_le_bytes()).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
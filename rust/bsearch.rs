@@ -0,0 +1,82 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, a u32 LE count K, K u32 LE queries, then a sorted
+// packed u32 LE haystack filling the rest. Output: K u32 LE values, each
+// query's insertion index: the number of haystack values below it, as
+// `partition_point(|&x| x < q)`. A query equal to a run of duplicates gets
+// the run's first index. An unknown mode, fewer than K queries, or a
+// haystack that is not a whole number of u32s exits with status 2; an
+// unsorted haystack gives unspecified (but in-range) indexes.
+//
+//   0  std: slice::partition_point
+//   1  branchless: halve the range with an arithmetic select per step, so
+//      the only data-dependent work is the load
+//
+// Haystack [10, 20, 20, 20, 30]: 5 -> 0, 10 -> 0, 15 -> 1, 20 -> 1, 25 -> 4,
+// 30 -> 4, 35 -> 5. An empty haystack gives 0 for every query.
+const MODE_STD: u8 = 0;
+const MODE_BRANCHLESS: u8 = 1;
+
+fn search_std(haystack: &[u32], q: u32) -> usize {
+    haystack.partition_point(|&x| x < q)
+}
+
+fn search_branchless(haystack: &[u32], q: u32) -> usize {
+    if haystack.is_empty() {
+        return 0;
+    }
+    // The answer is in base..=base + len.
+    let mut base = 0;
+    let mut len = haystack.len();
+    while len > 1 {
+        let half = len / 2;
+        base += (haystack[base + half - 1] < q) as usize * half;
+        len -= half;
+    }
+    base + (haystack[base] < q) as usize
+}
+
+fn u32s(bytes: &[u8]) -> Vec<u32> {
+    bytes.chunks_exact(4).map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]])).collect()
+}
+
+fn run(input: &[u8]) -> Result<Vec<u8>, String> {
+    let (&mode, rest) = input.split_first().ok_or("missing mode byte")?;
+    let search = match mode {
+        MODE_STD => search_std,
+        MODE_BRANCHLESS => search_branchless,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+    if rest.len() < 4 {
+        return Err(format!("truncated count: need 4 bytes, got {}", rest.len()));
+    }
+    let k = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+    let rest = &rest[4..];
+    if rest.len() / 4 < k {
+        return Err(format!("{k} queries declared, {} bytes present", rest.len()));
+    }
+    let (queries, haystack) = rest.split_at(4 * k);
+    if haystack.len() % 4 != 0 {
+        return Err(format!("haystack is {} bytes, not a multiple of 4", haystack.len()));
+    }
+    let haystack = u32s(haystack);
+
+    let mut output = Vec::with_capacity(4 * k);
+    for q in u32s(queries) {
+        output.extend_from_slice(&(search(&haystack, q) as u32).to_le_bytes());
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::ops::Bound;
+
+mod records;
+use records::Op;
+
+// hashmap_bench's record stream (rust/records) over a BTreeMap<u64, u64>,
+// plus op 2, range sum: the values of every key in [key, key + 1000) are
+// added to a second sum. The start is inclusive and the end exclusive; a
+// range that would pass u64::MAX ends there instead. Output: the final
+// length, the lookup sum, and the range sum, three u64 LE.
+//
+// After (10, insert) (10, insert) (1009, insert) (1010, insert):
+//   range 10    sums 10 and 1009   -> 3
+//   range 11    sums 1009 and 1010 -> 2
+//   range 1010  sums 1010 only     -> 1
+//   range 1011  sums nothing       -> 0
+// (u64::MAX, insert) then range u64::MAX - 5 gives 1.
+const RANGE: u64 = 1000;
+
+fn run(input: &[u8]) -> Result<Vec<u64>, String> {
+    let mut map: BTreeMap<u64, u64> = BTreeMap::new();
+    let (mut lookup_sum, mut range_sum) = (0u64, 0u64);
+    for record in records::parse(input)? {
+        match record? {
+            (key, Op::Insert) => *map.entry(key).or_insert(0) += 1,
+            (key, Op::Lookup) => {
+                if let Some(&v) = map.get(&key) {
+                    lookup_sum = lookup_sum.wrapping_add(v);
+                }
+            }
+            (key, Op::RangeSum) => {
+                let end = key.checked_add(RANGE).map_or(Bound::Unbounded, Bound::Excluded);
+                for &v in map.range((Bound::Included(key), end)).map(|(_, v)| v) {
+                    range_sum = range_sum.wrapping_add(v);
+                }
+            }
+        }
+    }
+    Ok(vec![map.len() as u64, lookup_sum, range_sum])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    records::finish(run(&input));
+}
@@ -0,0 +1,35 @@
+use std::io::{Read, Write};
+
+mod suffix_sort;
+
+// Burrows-Wheeler transform. Output: the primary index as u64 LE, then the
+// transformed bytes (as many as the input).
+//
+// The input is treated as ending in a virtual sentinel that sorts before
+// every byte value, so any byte (including 0x00) can appear and nothing is
+// rejected. The transform of s$ has one more symbol than s: the sentinel is
+// left out of the output, and the primary index is the position it would
+// have held. "banana" gives index 4 and "annbaa" (the textbook "annb$aa"
+// without the '$'); empty input gives index 0 and no bytes.
+//
+// The suffix array of s$ comes from rust/suffix_sort. Indexes are u32, so
+// the input must be under 4 GiB.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    assert!(input.len() < u32::MAX as usize, "input must be under 4 GiB");
+
+    let sa = suffix_sort::suffix_array(&input);
+    let mut primary = 0;
+    let mut output = Vec::with_capacity(8 + input.len());
+    output.extend_from_slice(&[0; 8]);
+    for (row, &p) in sa.iter().enumerate() {
+        match p {
+            0 => primary = row as u64,
+            _ => output.push(input[p as usize - 1]),
+        }
+    }
+    output[..8].copy_from_slice(&primary.to_le_bytes());
+
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,74 @@
+use std::io::{Read, Write};
+
+// Inverts bwt: input is the primary index as u64 LE, then the transformed
+// bytes; output is the original bytes. An index greater than the byte
+// count, or a pair that no input transforms to, exits with status 2.
+//
+// The sentinel is put back at the primary index, then the last-to-first
+// mapping is walked from the row that starts with the sentinel, producing
+// the original back to front. The walk of a valid transform reaches the
+// sentinel's row again exactly after the last byte.
+fn inverse(input: &[u8]) -> Result<Vec<u8>, String> {
+    if input.len() < 8 {
+        return Err(format!("truncated header: need 8 bytes, got {}", input.len()));
+    }
+    let primary = u64::from_le_bytes([
+        input[0], input[1], input[2], input[3], input[4], input[5], input[6], input[7],
+    ]);
+    let last = &input[8..];
+    let n = last.len();
+    if primary > n as u64 {
+        return Err(format!("primary index {primary} past the end ({n} bytes)"));
+    }
+    let primary = primary as usize;
+    // Byte at row `row` of the last column, skipping the sentinel's row.
+    let at = |row: usize| last[if row < primary { row } else { row - 1 }];
+
+    // first[b]: the row where rows starting with b begin (row 0 is the
+    // sentinel's). rank[row]: occurrences of the row's byte above it.
+    let mut first = [0usize; 256];
+    for &b in last {
+        first[b as usize] += 1;
+    }
+    let mut sum = 1;
+    for f in &mut first {
+        let here = *f;
+        *f = sum;
+        sum += here;
+    }
+    let mut seen = [0usize; 256];
+    let mut rank = vec![0u32; n + 1];
+    for row in (0..=n).filter(|&row| row != primary) {
+        let b = at(row) as usize;
+        rank[row] = seen[b] as u32;
+        seen[b] += 1;
+    }
+
+    let mut output = vec![0u8; n];
+    let mut row = 0;
+    for i in (0..n).rev() {
+        if row == primary {
+            return Err("not a valid transform: the sentinel comes too early".to_string());
+        }
+        let b = at(row);
+        output[i] = b;
+        row = first[b as usize] + rank[row] as usize;
+    }
+    if row != primary {
+        return Err("not a valid transform: the walk does not end at the primary index".to_string());
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match inverse(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
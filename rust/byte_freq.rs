@@ -1,16 +1,40 @@
 use std::io::{Read, Write};
 
+const CHUNK: usize = 256 * 1024;
+
 fn main() {
-    let mut input = Vec::new();
-    std::io::stdin().read_to_end(&mut input).unwrap();
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = vec![0u8; CHUNK];
 
-    let mut freq = [0u32; 256];
+    // Four banks, one per position mod 4, so runs of equal bytes don't
+    // serialize on a single counter's store-to-load dependency.
+    let mut banks = [[0u64; 256]; 4];
 
-    for &b in &input {
-        freq[b as usize] += 1;
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        let mut quads = buf[..n].chunks_exact(4);
+        for q in &mut quads {
+            banks[0][q[0] as usize] += 1;
+            banks[1][q[1] as usize] += 1;
+            banks[2][q[2] as usize] += 1;
+            banks[3][q[3] as usize] += 1;
+        }
+        for &b in quads.remainder() {
+            banks[0][b as usize] += 1;
+        }
+    }
+
+    let mut freq = [0u64; 256];
+    for bank in &banks {
+        for (total, &count) in freq.iter_mut().zip(bank) {
+            *total += count;
+        }
     }
 
-    let mut output = Vec::with_capacity(256 * 5);
+    let mut output = Vec::with_capacity(256 * 9);
 
     for (j, &count) in freq.iter().enumerate() {
         if count > 0 {
@@ -0,0 +1,110 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then start and count as u64 LE. For every n in
+// [start, start + count), the total stopping time: the number of steps
+// n -> n / 2 (n even) or 3n + 1 (n odd) to reach 1. Output: their sum, the
+// largest, and the smallest n that takes it, three u64 LE; count 0 gives
+// three zeros. An unknown or missing mode byte, a short input, start 0 with
+// a non-zero count, or a range past u64::MAX exits with status 2.
+//
+//   0  direct: every n walked to 1
+//   1  memo: walks stop at the first value below 2^20 whose time is known,
+//      and each n below 2^20 records its own
+//
+// Values are u128, so 3n + 1 cannot overflow for any n reached from a u64
+// start in practice (the largest known peaks are near 2^116); one that did
+// would exit with status 2 too.
+//
+// n = 27 takes 111 steps (peaking at 9232); [1, 10) gives sum 61 (0, 1, 7,
+// 2, 5, 8, 16, 3, 19), maximum 19 at n = 9; [1, 1000000) gives maximum 524
+// at n = 837799.
+const MODE_DIRECT: u8 = 0;
+const MODE_MEMO: u8 = 1;
+const CACHE_SIZE: usize = 1 << 20;
+
+fn next(m: u128) -> Result<u128, String> {
+    if m.is_multiple_of(2) {
+        Ok(m / 2)
+    } else if m <= (u128::MAX - 1) / 3 {
+        Ok(3 * m + 1)
+    } else {
+        Err("3n + 1 overflowed u128".to_string())
+    }
+}
+
+fn steps_direct(n: u64) -> Result<u64, String> {
+    let (mut m, mut steps) = (n as u128, 0);
+    while m != 1 {
+        m = next(m)?;
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+/// cache[m] is m's time once known; 0 means not yet, except for m = 1.
+fn steps_memo(n: u64, cache: &mut [u32]) -> Result<u64, String> {
+    let (mut m, mut steps) = (n as u128, 0);
+    while m != 1 {
+        if m < CACHE_SIZE as u128 && cache[m as usize] != 0 {
+            steps += cache[m as usize] as u64;
+            break;
+        }
+        m = next(m)?;
+        steps += 1;
+    }
+    if (n as usize) < CACHE_SIZE {
+        cache[n as usize] = steps as u32;
+    }
+    Ok(steps)
+}
+
+fn run(input: &[u8]) -> Result<[u64; 3], String> {
+    let (&mode, rest) = input.split_first().ok_or("missing mode byte")?;
+    if mode != MODE_DIRECT && mode != MODE_MEMO {
+        return Err(format!("unknown mode {mode}"));
+    }
+    if rest.len() < 16 {
+        return Err(format!("truncated header: need 16 bytes after the mode, got {}", rest.len()));
+    }
+    let start = u64::from_le_bytes([
+        rest[0], rest[1], rest[2], rest[3], rest[4], rest[5], rest[6], rest[7],
+    ]);
+    let count = u64::from_le_bytes([
+        rest[8], rest[9], rest[10], rest[11], rest[12], rest[13], rest[14], rest[15],
+    ]);
+    if count == 0 {
+        return Ok([0, 0, 0]);
+    }
+    if start == 0 {
+        return Err("start 0 has no stopping time".to_string());
+    }
+    start.checked_add(count - 1).ok_or("range runs past u64::MAX")?;
+
+    let mut cache = if mode == MODE_MEMO { vec![0u32; CACHE_SIZE] } else { Vec::new() };
+    let (mut sum, mut max, mut arg_max) = (0u64, 0u64, start);
+    for n in (0..count).map(|i| start + i) {
+        let steps = if mode == MODE_MEMO { steps_memo(n, &mut cache)? } else { steps_direct(n)? };
+        sum += steps;
+        if steps > max {
+            max = steps;
+            arg_max = n;
+        }
+    }
+    Ok([sum, max, arg_max])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(fields) => {
+            let output: Vec<u8> = fields.iter().flat_map(|f| f.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
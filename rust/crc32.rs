@@ -0,0 +1,38 @@
+use std::io::{Read, Write};
+
+// zlib CRC-32 (reflected polynomial 0xEDB88320), one table lookup per byte.
+// See crc32_slice8.rs for the slice-by-8 variant of the same checksum.
+const POLY: u32 = 0xEDB88320;
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { POLY ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+}
+
+const TABLE: [u32; 256] = make_table();
+
+fn crc32(input: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in input {
+        crc = TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    std::io::stdout().write_all(&crc32(&input).to_le_bytes()).unwrap();
+}
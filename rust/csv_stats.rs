@@ -0,0 +1,101 @@
+use std::io::{Read, Write};
+
+// Input: RFC 4180 CSV. Output: the record count, the total field count, and
+// the total byte length of all fields after unquoting, as three u64 LE.
+//
+// The rules are the `csv` crate's defaults (see csv_stats_crate), so both
+// give identical output on any input:
+//
+// - Records end at '\n', '\r', or "\r\n". Empty lines are skipped, so a
+//   trailing newline does not add a record, but a line holding only `""`
+//   is a record with one empty field.
+// - A field starting with '"' is quoted: it runs to the next lone '"', and
+//   may contain ',', newlines, and `""` (one '"'). Bytes after the closing
+//   quote are appended to the field as unquoted text.
+// - A '"' inside an unquoted field is an ordinary byte.
+// - At EOF an open quoted field ends, and a trailing ',' adds an empty
+//   field.
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    StartRecord,
+    StartField,
+    Unquoted,
+    Quoted,
+    // A '"' inside a quoted field: either half of `""` or the closing quote.
+    QuoteInQuoted,
+    // Just ended a record on '\r'; a following '\n' belongs to it.
+    AfterCr,
+}
+
+#[derive(Default)]
+struct Stats {
+    records: u64,
+    fields: u64,
+    bytes: u64,
+}
+
+fn csv_stats(input: &[u8]) -> Stats {
+    let mut s = Stats::default();
+    let mut state = State::StartRecord;
+    for &b in input {
+        if state == State::AfterCr {
+            state = State::StartRecord;
+            if b == b'\n' {
+                continue;
+            }
+        }
+        if state == State::StartRecord {
+            if b == b'\n' || b == b'\r' {
+                continue;
+            }
+            s.records += 1;
+            state = State::StartField;
+        }
+
+        state = match (state, b) {
+            (State::Quoted, b'"') => State::QuoteInQuoted,
+            (State::Quoted, _) => {
+                s.bytes += 1;
+                State::Quoted
+            }
+            (State::QuoteInQuoted, b'"') => {
+                s.bytes += 1;
+                State::Quoted
+            }
+            (State::StartField, b'"') => State::Quoted,
+            (_, b',') => {
+                s.fields += 1;
+                State::StartField
+            }
+            (_, b'\n') => {
+                s.fields += 1;
+                State::StartRecord
+            }
+            (_, b'\r') => {
+                s.fields += 1;
+                State::AfterCr
+            }
+            _ => {
+                s.bytes += 1;
+                State::Unquoted
+            }
+        };
+    }
+
+    if !matches!(state, State::StartRecord | State::AfterCr) {
+        s.fields += 1;
+    }
+    s
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let s = csv_stats(&input);
+    let mut output = Vec::with_capacity(24);
+    output.extend_from_slice(&s.records.to_le_bytes());
+    output.extend_from_slice(&s.fields.to_le_bytes());
+    output.extend_from_slice(&s.bytes.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,88 @@
+use std::io::{Read, Write};
+
+// Input: newline-separated lines (a final newline is optional), each a UTC
+// timestamp of exactly the form YYYY-MM-DDTHH:MM:SSZ: 20 bytes, uppercase T
+// and Z. Output: the number of lines parsed, the wrapping sum of their Unix
+// times in seconds as i64 LE, and the number of lines rejected, three
+// 8-byte LE fields.
+//
+// Dates are proleptic Gregorian, years 0000 to 9999: February has 29 days
+// in years divisible by 4, except centuries not divisible by 400. The month
+// must be 1-12, the day within the month, the hour 0-23, the minute 0-59 and
+// the second 0-60; as chrono does, second 60 (a leap second) counts as 59.
+//
+// datetime_parse_crate reads any RFC 3339 timestamp with chrono (offsets,
+// fractional seconds, lowercase t and z, a space for T). On lines of this
+// form the two agree.
+//
+// 2000-02-29T00:00:00Z is 951782400; 0000-01-01T00:00:00Z is -62167219200
+// and 9999-12-31T23:59:59Z 253402300799. 1900-02-29T00:00:00Z,
+// 2001-13-01T00:00:00Z and 2001-01-32T00:00:00Z are rejected.
+fn is_leap(y: i64) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        2 if is_leap(y) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days from 1970-01-01 to y-m-d (Howard Hinnant's days_from_civil: years
+/// start in March, so the leap day is last).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn parse(line: &[u8]) -> Option<i64> {
+    if line.len() != 20 {
+        return None;
+    }
+    for (i, sep) in [(4, b'-'), (7, b'-'), (10, b'T'), (13, b':'), (16, b':'), (19, b'Z')] {
+        if line[i] != sep {
+            return None;
+        }
+    }
+    let num = |from: usize, to: usize| {
+        line[from..to].iter().try_fold(0i64, |v, &b| {
+            let d = b.wrapping_sub(b'0');
+            (d <= 9).then_some(v * 10 + d as i64)
+        })
+    };
+    let (y, mo, d) = (num(0, 4)?, num(5, 7)?, num(8, 10)?);
+    let (h, mi, s) = (num(11, 13)?, num(14, 16)?, num(17, 19)?);
+    if !(1..=12).contains(&mo) || d < 1 || d > days_in_month(y, mo) || h > 23 || mi > 59 || s > 60 {
+        return None;
+    }
+    Some(days_from_civil(y, mo, d) * 86400 + h * 3600 + mi * 60 + s.min(59))
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let (mut parsed, mut sum, mut rejected) = (0u64, 0i64, 0u64);
+    for line in input.split_inclusive(|&b| b == b'\n') {
+        match parse(line.strip_suffix(b"\n").unwrap_or(line)) {
+            Some(t) => {
+                parsed += 1;
+                sum = sum.wrapping_add(t);
+            }
+            None => rejected += 1,
+        }
+    }
+
+    let mut output = Vec::with_capacity(24);
+    output.extend_from_slice(&parsed.to_le_bytes());
+    output.extend_from_slice(&sum.to_le_bytes());
+    output.extend_from_slice(&rejected.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
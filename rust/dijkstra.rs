@@ -0,0 +1,106 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::{Read, Write};
+
+// bfs's input with a weight per edge: u32 LE node count N, u32 LE edge count
+// M, M (u, v, weight) triples of u32 LE, then a (source, target) pair. Edges
+// are undirected; zero weights, self-loops, and parallel edges with
+// different weights are allowed. Output: the shortest source-target distance
+// as u64 LE, 0 when source == target and u64::MAX when the target is
+// unreachable. A node id >= N or an input that is not exactly 16 + 12M
+// bytes exits with status 2.
+//
+// Decrease-key by lazy deletion: a node is pushed as Reverse((dist, node))
+// every time its tentative distance improves, and a popped entry whose
+// distance is larger than the node's current one is stale and skipped. The
+// search stops when the target is popped. Distances are exact integers, so
+// any correct implementation gives the same bytes whatever its heap order.
+//
+// With N = 4 and edges (0,1,5) (0,1,2) (1,2,0) (2,3,4) (0,3,7), source 0:
+// target 2 gives 2 (the cheaper parallel edge, then the zero-weight one),
+// target 3 gives 6; with N = 5, target 4 gives u64::MAX.
+struct Graph {
+    n: usize,
+    offsets: Vec<u32>,
+    // (neighbour, weight) for each node's edges, in offsets order.
+    adjacent: Vec<(u32, u32)>,
+}
+
+fn parse(input: &[u8]) -> Result<(Graph, u32, u32), String> {
+    let word = |i: usize| {
+        let w = &input[4 * i..4 * i + 4];
+        u32::from_le_bytes([w[0], w[1], w[2], w[3]])
+    };
+    if input.len() < 8 {
+        return Err(format!("truncated header: need 8 bytes, got {}", input.len()));
+    }
+    let (n, m) = (word(0) as usize, word(1) as usize);
+    if input.len() as u64 != 16 + 12 * m as u64 {
+        return Err(format!("{m} edges need {} bytes, got {}", 16 + 12 * m as u64, input.len()));
+    }
+    let check = |v: u32| match v as usize {
+        i if i < n => Ok(v),
+        _ => Err(format!("node {v} out of range for {n} nodes")),
+    };
+
+    let mut offsets = vec![0u32; n + 1];
+    for e in 0..m {
+        let (u, v) = (check(word(2 + 3 * e))?, check(word(3 + 3 * e))?);
+        offsets[u as usize + 1] += 1;
+        offsets[v as usize + 1] += 1;
+    }
+    for i in 0..n {
+        offsets[i + 1] += offsets[i];
+    }
+    let mut fill = offsets.clone();
+    let mut adjacent = vec![(0u32, 0u32); 2 * m];
+    for e in 0..m {
+        let (u, v, w) = (word(2 + 3 * e), word(3 + 3 * e), word(4 + 3 * e));
+        adjacent[fill[u as usize] as usize] = (v, w);
+        fill[u as usize] += 1;
+        adjacent[fill[v as usize] as usize] = (u, w);
+        fill[v as usize] += 1;
+    }
+
+    let (source, target) = (check(word(2 + 3 * m))?, check(word(3 + 3 * m))?);
+    Ok((Graph { n, offsets, adjacent }, source, target))
+}
+
+fn dijkstra(g: &Graph, source: u32, target: u32) -> u64 {
+    let mut dist = vec![u64::MAX; g.n];
+    let mut heap = BinaryHeap::new();
+    dist[source as usize] = 0;
+    heap.push(Reverse((0u64, source)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if u == target {
+            return d;
+        }
+        if d > dist[u as usize] {
+            continue;
+        }
+        let u = u as usize;
+        for &(v, w) in &g.adjacent[g.offsets[u] as usize..g.offsets[u + 1] as usize] {
+            let next = d + w as u64;
+            if next < dist[v as usize] {
+                dist[v as usize] = next;
+                heap.push(Reverse((next, v)));
+            }
+        }
+    }
+    u64::MAX
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match parse(&input) {
+        Ok((graph, source, target)) => {
+            std::io::stdout().write_all(&dijkstra(&graph, source, target).to_le_bytes()).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
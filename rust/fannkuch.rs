@@ -0,0 +1,80 @@
+use std::io::{Read, Write};
+
+// Input: n as u32 LE (7-12 is the useful range; n = 0 gives zeros). Output:
+// the checksum, then the maximum flip count, each as i64 LE. A short input
+// exits with status 2.
+//
+// Benchmarks Game fannkuch-redux: for every permutation of 0..n, count the
+// flips (reverse the first perm[0] + 1 elements) until 0 comes first. The
+// checksum adds the flips of even-numbered permutations and subtracts those
+// of odd ones, so permutations must be visited in the reference order
+// (rotate the first r + 1 elements, counter array per r). Published values:
+// n = 7 gives checksum 228 and 16 max flips; n = 10 gives 73196 and 38.
+const EXIT_MALFORMED: i32 = 2;
+
+fn read_n() -> usize {
+    let mut buf = [0u8; 4];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    u32::from_le_bytes(buf) as usize
+}
+
+fn fannkuch(n: usize) -> (i64, i64) {
+    if n == 0 {
+        return (0, 0);
+    }
+
+    let mut perm1: Vec<usize> = (0..n).collect();
+    let mut perm = vec![0usize; n];
+    let mut count = vec![0usize; n];
+    let mut checksum = 0i64;
+    let mut max_flips = 0i64;
+    let mut perm_count = 0u64;
+    let mut r = n;
+
+    loop {
+        while r != 1 {
+            count[r - 1] = r;
+            r -= 1;
+        }
+
+        perm.copy_from_slice(&perm1);
+        let mut flips = 0i64;
+        loop {
+            let k = perm[0];
+            if k == 0 {
+                break;
+            }
+            perm[..=k].reverse();
+            flips += 1;
+        }
+        max_flips = max_flips.max(flips);
+        checksum += if perm_count.is_multiple_of(2) { flips } else { -flips };
+
+        // Next permutation: rotate perm1[..=r] left by one, carrying into
+        // the next r once count[r] runs out.
+        loop {
+            if r == n {
+                return (checksum, max_flips);
+            }
+            perm1[..=r].rotate_left(1);
+            count[r] -= 1;
+            if count[r] > 0 {
+                break;
+            }
+            r += 1;
+        }
+        perm_count += 1;
+    }
+}
+
+fn main() {
+    let (checksum, max_flips) = fannkuch(read_n());
+
+    let mut output = [0u8; 16];
+    output[..8].copy_from_slice(&checksum.to_le_bytes());
+    output[8..].copy_from_slice(&max_flips.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
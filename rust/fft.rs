@@ -0,0 +1,112 @@
+use std::f64::consts::PI;
+use std::io::{Read, Write};
+
+// Unscaled forward DFT, X[j] = sum over n of x[n] e^(-2 pi i jn / N).
+// Input: log2 N as a u32 LE, then the N complex samples as interleaved f64
+// LE (re, im). Output: the N transformed samples in the same format, then a
+// checksum f64 LE: the sum over j of sqrt(re^2 + im^2), rounded to 6
+// decimal places (round(sum * 1e6) / 1e6). A wrong length exits with status
+// 2. `--inverse` computes x[n] = (1 / N) sum over j of X[j] e^(2 pi i jn / N)
+// instead, in the same format, so a transform can be checked by round trip.
+//
+// Implementations round differently (twiddle factors, operation order), so
+// outputs are compared within benchmarks.toml's diff_epsilon rather than
+// bit for bit.
+//
+// Iterative radix-2 Cooley-Tukey: the samples are permuted into bit-reversed
+// order in place, then log2 N butterfly passes run, the pass merging blocks
+// of 2h using every (N / 2h)-th twiddle. The N / 2 twiddles e^(-2 pi i j / N)
+// are computed once, each with its own cos and sin, since building them by
+// repeated multiplication lets the error grow with j.
+//
+// A delta (1 at n = 0) transforms to all ones, checksum N. With N = 8,
+// cos(2 pi n / 8) transforms to 4 at bins 1 and 7 and 0 elsewhere, to
+// rounding (checksum 8).
+fn fft(x: &mut [[f64; 2]], inverse: bool) {
+    let n = x.len();
+    if n < 2 {
+        return;
+    }
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let r = i.reverse_bits() >> (usize::BITS - bits);
+        if i < r {
+            x.swap(i, r);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let twiddles: Vec<[f64; 2]> = (0..n / 2)
+        .map(|j| {
+            let angle = sign * 2.0 * PI * j as f64 / n as f64;
+            [angle.cos(), angle.sin()]
+        })
+        .collect();
+
+    let mut h = 1;
+    while h < n {
+        let stride = n / (2 * h);
+        for block in x.chunks_exact_mut(2 * h) {
+            let (lo, hi) = block.split_at_mut(h);
+            for (j, (a, b)) in lo.iter_mut().zip(hi).enumerate() {
+                let [wr, wi] = twiddles[j * stride];
+                let t = [b[0] * wr - b[1] * wi, b[0] * wi + b[1] * wr];
+                *b = [a[0] - t[0], a[1] - t[1]];
+                *a = [a[0] + t[0], a[1] + t[1]];
+            }
+        }
+        h *= 2;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f64;
+        for v in x.iter_mut() {
+            *v = [v[0] * scale, v[1] * scale];
+        }
+    }
+}
+
+fn run(input: &[u8], inverse: bool) -> Result<Vec<[f64; 2]>, String> {
+    if input.len() < 4 {
+        return Err(format!("truncated header: need 4 bytes, got {}", input.len()));
+    }
+    let k = u32::from_le_bytes([input[0], input[1], input[2], input[3]]);
+    let body = &input[4..];
+    if k >= 58 {
+        return Err(format!("log2 size {k} is too large"));
+    }
+    if body.len() as u64 != 16 << k {
+        return Err(format!("2^{k} samples need {} bytes, got {}", 16u64 << k, body.len()));
+    }
+
+    let f = |b: &[u8]| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+    let mut x: Vec<[f64; 2]> = body.chunks_exact(16).map(|s| [f(&s[..8]), f(&s[8..])]).collect();
+    fft(&mut x, inverse);
+    Ok(x)
+}
+
+fn main() {
+    let inverse = std::env::args().skip(1).any(|a| a == "--inverse");
+
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input, inverse) {
+        Ok(x) => {
+            let mut output = Vec::with_capacity(16 * x.len() + 8);
+            let mut magnitudes = 0.0;
+            for [re, im] in x {
+                output.extend_from_slice(&re.to_le_bytes());
+                output.extend_from_slice(&im.to_le_bytes());
+                magnitudes += (re * re + im * im).sqrt();
+            }
+            let checksum = (magnitudes * 1e6).round() / 1e6;
+            output.extend_from_slice(&checksum.to_le_bytes());
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
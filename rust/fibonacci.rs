@@ -1,15 +1,27 @@
 use std::io::{Read, Write};
 
+// Reads n (u64 LE; a 4-byte u32 LE input is still accepted) and writes
+// F(n) mod 2^64 as u64 LE, iterating n times.
+fn read_n() -> u64 {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    let mut buf = [0u8; 8];
+    match input.len() {
+        0..=3 => panic!("input too short: need 4 or 8 bytes"),
+        4..=7 => buf[..4].copy_from_slice(&input[..4]),
+        _ => buf.copy_from_slice(&input[..8]),
+    }
+    u64::from_le_bytes(buf)
+}
+
 fn main() {
-    let mut buf = [0u8; 4];
-    std::io::stdin().read_exact(&mut buf).unwrap();
-    let n = u32::from_le_bytes(buf);
+    let n = read_n();
 
     let result = if n < 2 {
         n
     } else {
-        let mut a: u32 = 0;
-        let mut b: u32 = 1;
+        let mut a: u64 = 0;
+        let mut b: u64 = 1;
         for _ in 2..=n {
             let tmp = a.wrapping_add(b);
             a = b;
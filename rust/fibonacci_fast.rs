@@ -0,0 +1,39 @@
+use std::io::{Read, Write};
+
+// The fibonacci output (F(n) mod 2^64 as u64 LE) by fast doubling, in
+// O(log n) steps instead of n:
+//   F(2k)   = F(k) * (2 F(k+1) - F(k))
+//   F(2k+1) = F(k)^2 + F(k+1)^2
+// Everything wraps, which is exact in the ring of integers mod 2^64.
+fn read_n() -> u64 {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    let mut buf = [0u8; 8];
+    match input.len() {
+        0..=3 => panic!("input too short: need 4 or 8 bytes"),
+        4..=7 => buf[..4].copy_from_slice(&input[..4]),
+        _ => buf.copy_from_slice(&input[..8]),
+    }
+    u64::from_le_bytes(buf)
+}
+
+fn main() {
+    let n = read_n();
+
+    // (F(k), F(k+1)) for k = the bits of n above the current one.
+    let mut a: u64 = 0;
+    let mut b: u64 = 1;
+    for bit in (0..64 - n.leading_zeros()).rev() {
+        let c = a.wrapping_mul(b.wrapping_mul(2).wrapping_sub(a));
+        let d = a.wrapping_mul(a).wrapping_add(b.wrapping_mul(b));
+        if (n >> bit) & 1 == 0 {
+            a = c;
+            b = d;
+        } else {
+            a = d;
+            b = c.wrapping_add(d);
+        }
+    }
+
+    std::io::stdout().write_all(&a.to_le_bytes()).unwrap();
+}
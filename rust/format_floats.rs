@@ -0,0 +1,34 @@
+use std::io::{Read, Write};
+
+// Input: packed f64 LE values. Output: each value formatted with f64's
+// Display, followed by a newline. A trailing partial value exits with
+// status 2.
+//
+// Display writes the shortest digit string that parses back to the same
+// value, always in positional notation: no exponent, no trailing ".0", and
+// as many zeros as the magnitude needs (f64::MAX is 309 digits, the
+// smallest subnormal "0." then 323 zeros then "5"). Negative zero is "-0",
+// the infinities "inf" and "-inf", and every NaN "NaN", so NaN sign and
+// payload are the only things parse_floats cannot read back exactly.
+//
+// When the value lies exactly halfway between the two closest shortest
+// strings, the last digit rounds away from zero: 271258947471118.625 gives
+// "271258947471118.63" (Python's repr rounds to even, "...62").
+//
+// 0.1, 1.0, -0.0, 1e21, 1e-7 and 123456789012345678.0 give "0.1", "1",
+// "-0", "1000000000000000000000", "0.0000001" and "123456789012345680".
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    if !input.len().is_multiple_of(8) {
+        eprintln!("malformed input: {} trailing bytes after the last value", input.len() % 8);
+        std::process::exit(2);
+    }
+    let mut output = Vec::with_capacity(input.len() * 3);
+    for b in input.chunks_exact(8) {
+        let v = f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]);
+        writeln!(output, "{v}").unwrap();
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,80 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then packed (a, b) pairs of u64 LE. Output: the XOR of
+// gcd(a, b) over all pairs, then the number of pairs with gcd 1, two u64 LE.
+// gcd(0, x) = gcd(x, 0) = x, so (0, 0) gives 0 and is not coprime. An
+// unknown or missing mode byte, or a trailing partial pair, exits with
+// status 2.
+//
+//   0  euclid: a, b = b, a % b until b is 0, one 64-bit division per step
+//   1  binary: Stein's algorithm, shifts and subtractions only, with
+//      trailing_zeros to strip factors of two in one step
+//
+// Both modes give the same output. Pairs (12, 18), (0, 7), (7, 0), (0, 0),
+// (5, 5), (9, 28) have GCDs 6, 7, 7, 0, 5, 1: XOR 2, one coprime pair.
+const MODE_EUCLID: u8 = 0;
+const MODE_BINARY: u8 = 1;
+
+fn gcd_euclid(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn gcd_binary(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return a | b;
+    }
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    loop {
+        // a is odd here; b's factors of two are not common ones.
+        b >>= b.trailing_zeros();
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b -= a;
+        if b == 0 {
+            return a << shift;
+        }
+    }
+}
+
+fn run(input: &[u8]) -> Result<[u64; 2], String> {
+    let (&mode, pairs) = input.split_first().ok_or("missing mode byte")?;
+    let gcd = match mode {
+        MODE_EUCLID => gcd_euclid,
+        MODE_BINARY => gcd_binary,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+    if !pairs.len().is_multiple_of(16) {
+        return Err(format!("{} trailing bytes after the last pair", pairs.len() % 16));
+    }
+
+    let (mut folded, mut coprime) = (0u64, 0u64);
+    for p in pairs.chunks_exact(16) {
+        let a = u64::from_le_bytes([p[0], p[1], p[2], p[3], p[4], p[5], p[6], p[7]]);
+        let b = u64::from_le_bytes([p[8], p[9], p[10], p[11], p[12], p[13], p[14], p[15]]);
+        let g = gcd(a, b);
+        folded ^= g;
+        coprime += (g == 1) as u64;
+    }
+    Ok([folded, coprime])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(fields) => {
+            let output: Vec<u8> = fields.iter().flat_map(|f| f.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
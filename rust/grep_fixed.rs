@@ -0,0 +1,99 @@
+use std::io::{Read, Write};
+
+// Input: a needle length as u32 LE, then the needle, then the text (the rest
+// of stdin), both raw bytes. Output: the lines of the text that contain the
+// needle, in regex_grep's shape: lines end at '\n', and each matching line
+// is printed followed by '\n', whether or not the text ended with one. A
+// length past the end of the input exits with status 2.
+//
+// Given the same input, this prints what regex_grep does whenever the needle
+// has no regex metacharacters and the text is UTF-8 (regex_grep rejects
+// anything else). The empty needle matches every line, empty ones included;
+// a needle containing '\n' matches none.
+//
+// The search runs over the whole text rather than line by line: Horspool's
+// algorithm finds the next occurrence, the line around it is printed, and
+// the search resumes after that line, so long stretches of non-matching
+// lines cost one skip loop and not a call per line.
+fn split(input: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    if input.len() < 4 {
+        return Err(format!("truncated header: need 4 bytes, got {}", input.len()));
+    }
+    let len = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
+    let rest = &input[4..];
+    if len > rest.len() {
+        return Err(format!("needle ends at byte {}, past end of input ({})", 4 + len, input.len()));
+    }
+    Ok(rest.split_at(len))
+}
+
+/// Horspool search: on a mismatch, shift by how far the haystack byte under
+/// the needle's last position is from the needle's end.
+struct Finder<'a> {
+    needle: &'a [u8],
+    shift: [usize; 256],
+}
+
+impl<'a> Finder<'a> {
+    fn new(needle: &'a [u8]) -> Self {
+        let mut shift = [needle.len(); 256];
+        for (i, &b) in needle.iter().enumerate().take(needle.len().saturating_sub(1)) {
+            shift[b as usize] = needle.len() - 1 - i;
+        }
+        Finder { needle, shift }
+    }
+
+    /// The offset of the first occurrence in `haystack`, if any.
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let n = self.needle.len();
+        let last = *self.needle.last()?;
+        let mut at = 0;
+        while at + n <= haystack.len() {
+            let b = haystack[at + n - 1];
+            if b == last && haystack[at..at + n - 1] == self.needle[..n - 1] {
+                return Some(at);
+            }
+            at += self.shift[b as usize];
+        }
+        None
+    }
+}
+
+fn grep(needle: &[u8], text: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    if needle.is_empty() {
+        // Every line matches: the text itself, with its last line ended.
+        output.extend_from_slice(text);
+        if !text.is_empty() && !text.ends_with(b"\n") {
+            output.push(b'\n');
+        }
+    } else if !needle.contains(&b'\n') {
+        let finder = Finder::new(needle);
+        let mut from = 0;
+        while let Some(i) = finder.find(&text[from..]) {
+            let hit = from + i;
+            let start = text[..hit].iter().rposition(|&b| b == b'\n').map_or(0, |j| j + 1);
+            let end = text[hit..].iter().position(|&b| b == b'\n').map_or(text.len(), |j| hit + j);
+            output.extend_from_slice(&text[start..end]);
+            output.push(b'\n');
+            from = end + 1;
+            if from >= text.len() {
+                break;
+            }
+        }
+    }
+    output
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match split(&input) {
+        Ok((needle, text)) => std::io::stdout().write_all(&grep(needle, text)).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::io::Read;
+
+mod records;
+use records::Op;
+
+// Input: 12-byte records, a u64 LE key then a u32 LE op (rust/records):
+//
+//   0  insert-or-increment: the key's value goes up by one, from 0 if absent
+//   1  lookup: a present key's value is added to the sum; an absent key adds
+//      nothing and is not inserted
+//
+// Output: the map's final length and the sum, two u64 LE. Neither depends on
+// iteration order, so any hasher gives the same bytes. A trailing partial
+// record or any other op (including btree_bench's 2) exits with status 2.
+//
+// (5, insert) (5, insert) (7, lookup) (5, lookup) (7, insert) (7, lookup)
+// gives length 2 and sum 3: the lookups see absent, 2, and 1.
+fn run(input: &[u8]) -> Result<Vec<u64>, String> {
+    let mut map: HashMap<u64, u64> = HashMap::new();
+    let mut sum = 0u64;
+    for (i, record) in records::parse(input)?.enumerate() {
+        match record? {
+            (key, Op::Insert) => *map.entry(key).or_insert(0) += 1,
+            (key, Op::Lookup) => {
+                if let Some(&v) = map.get(&key) {
+                    sum = sum.wrapping_add(v);
+                }
+            }
+            (_, Op::RangeSum) => return Err(format!("range op in record {i} needs an ordered map")),
+        }
+    }
+    Ok(vec![map.len() as u64, sum])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    records::finish(run(&input));
+}
@@ -0,0 +1,128 @@
+use std::collections::BinaryHeap;
+use std::io::{Read, Write};
+
+// Input: a mode byte, then packed i64 LE values. A non-negative value is
+// pushed onto a max-heap; a negative value -n pops n times, stopping early
+// once the heap is empty (i64::MIN pops 2^63 times, i.e. everything).
+// Output: the number of pops performed as u64 LE and the wrapping sum of the
+// popped values as i64 LE. An unknown mode or a trailing partial value exits
+// with status 2.
+//
+//   0  std: BinaryHeap<i64>
+//   1  array: a hand-rolled binary heap in a Vec, sifting a hole instead of
+//      swapping at each level
+//
+// 5 3 8 -2 1 -5 pushes 5, 3, 8, pops 8 and 5, pushes 1, then pops 3 and 1
+// and stops on the empty heap: 4 pops, sum 17. A pop count on an empty heap
+// performs no pops; empty input gives 0 and 0.
+const MODE_STD: u8 = 0;
+const MODE_ARRAY: u8 = 1;
+
+trait MaxHeap {
+    fn push(&mut self, v: i64);
+    fn pop(&mut self) -> Option<i64>;
+}
+
+impl MaxHeap for BinaryHeap<i64> {
+    fn push(&mut self, v: i64) {
+        BinaryHeap::push(self, v)
+    }
+
+    fn pop(&mut self) -> Option<i64> {
+        BinaryHeap::pop(self)
+    }
+}
+
+/// Children of `i` are at 2i + 1 and 2i + 2; every parent is >= its children.
+struct ArrayHeap(Vec<i64>);
+
+impl MaxHeap for ArrayHeap {
+    fn push(&mut self, v: i64) {
+        let h = &mut self.0;
+        let mut i = h.len();
+        h.push(v);
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if h[parent] >= v {
+                break;
+            }
+            h[i] = h[parent];
+            i = parent;
+        }
+        h[i] = v;
+    }
+
+    fn pop(&mut self) -> Option<i64> {
+        let h = &mut self.0;
+        let last = h.pop()?;
+        if h.is_empty() {
+            return Some(last);
+        }
+        let top = h[0];
+        let mut i = 0;
+        loop {
+            let mut child = 2 * i + 1;
+            if child >= h.len() {
+                break;
+            }
+            if child + 1 < h.len() && h[child + 1] > h[child] {
+                child += 1;
+            }
+            if h[child] <= last {
+                break;
+            }
+            h[i] = h[child];
+            i = child;
+        }
+        h[i] = last;
+        Some(top)
+    }
+}
+
+fn run<H: MaxHeap>(mut heap: H, data: &[u8]) -> (u64, i64) {
+    let (mut pops, mut sum) = (0u64, 0i64);
+    for w in data.chunks_exact(8) {
+        let v = i64::from_le_bytes([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]]);
+        if v >= 0 {
+            heap.push(v);
+            continue;
+        }
+        for _ in 0..v.unsigned_abs() {
+            match heap.pop() {
+                Some(top) => {
+                    pops += 1;
+                    sum = sum.wrapping_add(top);
+                }
+                None => break,
+            }
+        }
+    }
+    (pops, sum)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let (mode, data) = match input.split_first() {
+        Some((&mode, data)) => (mode, data),
+        None => (MODE_STD, &input[..]),
+    };
+    if !data.len().is_multiple_of(8) {
+        eprintln!("malformed input: {} trailing bytes after the last value", data.len() % 8);
+        std::process::exit(2);
+    }
+    let (pops, sum) = match mode {
+        MODE_STD => run(BinaryHeap::new(), data),
+        MODE_ARRAY => run(ArrayHeap(Vec::new()), data),
+        _ => {
+            eprintln!("malformed input: unknown mode {mode}");
+            std::process::exit(2);
+        }
+    };
+
+    let mut output = Vec::with_capacity(16);
+    output.extend_from_slice(&pops.to_le_bytes());
+    output.extend_from_slice(&sum.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
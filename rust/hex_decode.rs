@@ -0,0 +1,78 @@
+use std::io::{Read, Write};
+
+const INVALID: u8 = 0xFF;
+const SPACE: u8 = 0xFE;
+
+const fn decode_table() -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 10 {
+        table[b'0' as usize + i] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 6 {
+        table[b'a' as usize + i] = 10 + i as u8;
+        table[b'A' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+    let spaces = *b" \t\n\x0C\r";
+    let mut i = 0;
+    while i < spaces.len() {
+        table[spaces[i] as usize] = SPACE;
+        i += 1;
+    }
+    table
+}
+
+const DECODE: [u8; 256] = decode_table();
+
+// Hex digits in either case, two per output byte, high nibble first. ASCII
+// whitespace (as in `u8::is_ascii_whitespace`) is ignored anywhere, even
+// between the two digits of a byte. Any other byte, or an odd number of
+// digits, exits with status 2 and writes nothing to stdout.
+fn decode(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(input.len() / 2);
+    let mut high = None;
+    let mut i = 0;
+    while i < input.len() {
+        // Fast path: an aligned pair of digits, no branch on either value.
+        if high.is_none() && i + 1 < input.len() {
+            let (h, l) = (DECODE[input[i] as usize], DECODE[input[i + 1] as usize]);
+            if (h | l) < 16 {
+                output.push(h << 4 | l);
+                i += 2;
+                continue;
+            }
+        }
+
+        let b = input[i];
+        match DECODE[b as usize] {
+            INVALID => return Err(format!("invalid character 0x{b:02x} at byte {i}")),
+            SPACE => {}
+            v => match high.take() {
+                Some(h) => output.push(h << 4 | v),
+                None => high = Some(v),
+            },
+        }
+        i += 1;
+    }
+
+    if high.is_some() {
+        return Err(format!("odd number of hex digits ({})", output.len() * 2 + 1));
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match decode(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
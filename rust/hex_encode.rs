@@ -0,0 +1,17 @@
+use std::io::{Read, Write};
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Two lowercase hex digits per byte, high nibble first, no separators.
+    let mut output = Vec::with_capacity(input.len() * 2);
+    for &b in &input {
+        output.push(DIGITS[(b >> 4) as usize]);
+        output.push(DIGITS[(b & 15) as usize]);
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
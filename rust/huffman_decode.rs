@@ -0,0 +1,149 @@
+use std::io::{Read, Write};
+
+// Decodes huffman_encode's output (the layout and code assignment are
+// described there). Exits with status 2, writing nothing, when the input is
+// shorter than the table and trailer, a code length exceeds 64 or the
+// lengths over-subscribe the code space, the payload runs out or holds a
+// code no symbol has, or bytes remain after the last code's byte.
+//
+// Codes of up to LUT_BITS bits are decoded with one table lookup; longer
+// ones fall back to walking the canonical code one bit at a time.
+const SYMBOLS: usize = 256;
+const MAX_LEN: usize = 64;
+const LUT_BITS: u32 = 11;
+
+struct Code {
+    // symbols sorted by (length, value); first[l] is the first length-l code
+    // and offset[l] its index in `sorted`.
+    sorted: Vec<u8>,
+    count: [u64; MAX_LEN + 1],
+    first: [u64; MAX_LEN + 1],
+    offset: [usize; MAX_LEN + 1],
+    max_len: usize,
+    // (length << 8 | symbol) for every LUT_BITS-bit prefix that starts with
+    // a short code; 0 where the code is longer or unassigned.
+    lut: Vec<u16>,
+}
+
+fn build(lengths: &[u8]) -> Result<Code, String> {
+    let mut count = [0u64; MAX_LEN + 1];
+    let mut kraft = 0u128;
+    for (s, &l) in lengths.iter().enumerate() {
+        let l = l as usize;
+        if l > MAX_LEN {
+            return Err(format!("code length {l} for byte {s} exceeds {MAX_LEN}"));
+        }
+        if l > 0 {
+            count[l] += 1;
+            kraft += 1u128 << (MAX_LEN - l);
+        }
+    }
+    if kraft > 1u128 << MAX_LEN {
+        return Err("code lengths over-subscribe the code space".to_string());
+    }
+
+    let mut sorted: Vec<u8> = (0..SYMBOLS).filter(|&s| lengths[s] > 0).map(|s| s as u8).collect();
+    sorted.sort_by_key(|&s| (lengths[s as usize], s));
+    let max_len = sorted.last().map_or(0, |&s| lengths[s as usize] as usize);
+
+    let mut first = [0u64; MAX_LEN + 1];
+    let mut offset = [0usize; MAX_LEN + 1];
+    let (mut code, mut index) = (0u64, 0usize);
+    for l in 1..=MAX_LEN {
+        first[l] = code;
+        offset[l] = index;
+        index += count[l] as usize;
+        // Kraft guarantees this only wraps past the last used length.
+        code = code.wrapping_add(count[l]).wrapping_shl(1);
+    }
+
+    let mut lut = vec![0u16; 1 << LUT_BITS];
+    for (i, &s) in sorted.iter().enumerate() {
+        let l = lengths[s as usize] as usize;
+        if l as u32 > LUT_BITS {
+            break;
+        }
+        let c = first[l] + (i - offset[l]) as u64;
+        let span = 1usize << (LUT_BITS - l as u32);
+        let start = (c as usize) << (LUT_BITS - l as u32);
+        lut[start..start + span].fill((l as u16) << 8 | s as u16);
+    }
+
+    Ok(Code { sorted, count, first, offset, max_len, lut })
+}
+
+/// The `k` (1..=57) bits at bit `pos`, MSB first, zero past the end.
+fn peek(data: &[u8], pos: usize, k: u32) -> u64 {
+    let byte = pos / 8;
+    let word = match data.get(byte..byte + 8) {
+        Some(w) => u64::from_be_bytes([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]]),
+        None => {
+            let mut w = [0u8; 8];
+            let tail = &data[byte.min(data.len())..];
+            w[..tail.len()].copy_from_slice(tail);
+            u64::from_be_bytes(w)
+        }
+    };
+    (word << (pos % 8)) >> (64 - k)
+}
+
+fn decode(input: &[u8]) -> Result<Vec<u8>, String> {
+    if input.len() < SYMBOLS + 8 {
+        return Err(format!("truncated: need {} bytes, got {}", SYMBOLS + 8, input.len()));
+    }
+    let (lengths, rest) = input.split_at(SYMBOLS);
+    let (payload, trailer) = rest.split_at(rest.len() - 8);
+    let n = u64::from_le_bytes([
+        trailer[0], trailer[1], trailer[2], trailer[3], trailer[4], trailer[5], trailer[6], trailer[7],
+    ]);
+    let code = build(lengths)?;
+
+    let total_bits = payload.len() * 8;
+    let mut output = Vec::with_capacity(n.min(total_bits as u64) as usize);
+    let mut pos = 0usize;
+    for _ in 0..n {
+        let entry = code.lut[peek(payload, pos, LUT_BITS) as usize];
+        let len = (entry >> 8) as usize;
+        if entry != 0 && pos + len <= total_bits {
+            output.push(entry as u8);
+            pos += len;
+            continue;
+        }
+
+        let mut c = 0u64;
+        let mut l = 0;
+        loop {
+            l += 1;
+            if l > code.max_len {
+                return Err(format!("invalid code at bit {pos}"));
+            }
+            if pos + l > total_bits {
+                return Err(format!("payload ends inside a code at bit {pos}"));
+            }
+            c = c << 1 | peek(payload, pos + l - 1, 1);
+            if c >= code.first[l] && c - code.first[l] < code.count[l] {
+                output.push(code.sorted[code.offset[l] + (c - code.first[l]) as usize]);
+                pos += l;
+                break;
+            }
+        }
+    }
+
+    if payload.len() != pos.div_ceil(8) {
+        return Err(format!("{} trailing payload bytes", payload.len() - pos.div_ceil(8)));
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match decode(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::{Read, Write};
+
+// Canonical Huffman coding over bytes. Output layout:
+//
+//   256 bytes   code length of each byte value, 0 if it does not occur
+//   payload     each input byte's code, MSB first, the last byte zero-padded
+//   8 bytes     the input length as u64 LE
+//
+// The code lengths are fully determined, so every implementation must write
+// identical bytes:
+//
+// - Tree: start with one leaf per byte value that occurs, weighted by its
+//   count. Repeatedly merge the two lowest nodes, where nodes are ordered by
+//   (weight, smallest byte value in the node's subtree); subtrees are
+//   disjoint, so this order never ties. A leaf's code length is its depth.
+// - One distinct byte gets code length 1 (there is no tree to build); empty
+//   input gives an all-zero table, no payload, and a length of 0.
+// - Codes: sort the symbols by (length, byte value) and number them
+//   consecutively, shifting left whenever the length grows, as in DEFLATE.
+//
+// Lengths are not limited: skewed inputs routinely produce codes longer than
+// 8 bits. A code longer than 64 bits would need tens of terabytes of input
+// (Fibonacci-distributed counts), so codes are kept in a u64.
+const SYMBOLS: usize = 256;
+
+fn code_lengths(counts: &[u64; SYMBOLS]) -> [u8; SYMBOLS] {
+    let mut lengths = [0u8; SYMBOLS];
+    let present: Vec<usize> = (0..SYMBOLS).filter(|&s| counts[s] > 0).collect();
+    match present.len() {
+        0 => return lengths,
+        1 => {
+            lengths[present[0]] = 1;
+            return lengths;
+        }
+        _ => {}
+    }
+
+    // Nodes 0..256 are leaves; merged nodes are appended with their children.
+    let mut children: Vec<(usize, usize)> = Vec::with_capacity(present.len());
+    let mut heap: BinaryHeap<Reverse<(u64, usize, usize)>> =
+        present.iter().map(|&s| Reverse((counts[s], s, s))).collect();
+    while heap.len() > 1 {
+        let Reverse((w1, min1, a)) = heap.pop().unwrap();
+        let Reverse((w2, min2, b)) = heap.pop().unwrap();
+        children.push((a, b));
+        heap.push(Reverse((w1 + w2, min1.min(min2), SYMBOLS + children.len() - 1)));
+    }
+
+    let root = heap.pop().unwrap().0 .2;
+    let mut stack = vec![(root, 0u8)];
+    while let Some((node, depth)) = stack.pop() {
+        if node < SYMBOLS {
+            lengths[node] = depth;
+        } else {
+            let (a, b) = children[node - SYMBOLS];
+            stack.push((a, depth + 1));
+            stack.push((b, depth + 1));
+        }
+    }
+    lengths
+}
+
+fn canonical_codes(lengths: &[u8; SYMBOLS]) -> [u64; SYMBOLS] {
+    let mut order: Vec<usize> = (0..SYMBOLS).filter(|&s| lengths[s] > 0).collect();
+    order.sort_by_key(|&s| (lengths[s], s));
+
+    let mut codes = [0u64; SYMBOLS];
+    let mut code = 0u64;
+    let mut prev_len = lengths[order.first().copied().unwrap_or(0)];
+    for s in order {
+        code <<= lengths[s] - prev_len;
+        prev_len = lengths[s];
+        codes[s] = code;
+        code += 1;
+    }
+    codes
+}
+
+struct BitWriter {
+    out: Vec<u8>,
+    // The low `n` bits (n < 8 between calls) are not yet written.
+    acc: u64,
+    n: u32,
+}
+
+impl BitWriter {
+    fn put(&mut self, code: u64, len: u32) {
+        if len > 56 {
+            self.put(code >> 32, len - 32);
+            self.put(code & 0xFFFF_FFFF, 32);
+            return;
+        }
+        self.acc = self.acc << len | code;
+        self.n += len;
+        while self.n >= 8 {
+            self.n -= 8;
+            self.out.push((self.acc >> self.n) as u8);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.n > 0 {
+            self.out.push((self.acc << (8 - self.n)) as u8);
+        }
+        self.out
+    }
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let mut counts = [0u64; SYMBOLS];
+    for &b in &input {
+        counts[b as usize] += 1;
+    }
+    let lengths = code_lengths(&counts);
+    let codes = canonical_codes(&lengths);
+
+    let mut writer = BitWriter { out: Vec::with_capacity(SYMBOLS + input.len() + 8), acc: 0, n: 0 };
+    writer.out.extend_from_slice(&lengths);
+    for &b in &input {
+        writer.put(codes[b as usize], lengths[b as usize] as u32);
+    }
+    let mut output = writer.finish();
+    output.extend_from_slice(&(input.len() as u64).to_le_bytes());
+
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,127 @@
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv6Addr};
+
+// Input: a mode byte, then newline-separated lines (a final newline is
+// optional), each an IPv4 or IPv6 address in std's syntax. Output: four u64
+// LE fields: the number of valid IPv4 lines, valid IPv6 lines, invalid
+// lines, and the XOR over all valid addresses of their bytes folded into a
+// u64 (IPv4: the address as a big-endian u32; IPv6: the high and low
+// big-endian halves XORed). Both modes give the same output; an unknown or
+// missing mode byte exits with status 2.
+//
+//   0  std: IpAddr::from_str
+//   1  fast_v4: a hand-written IPv4 parser for lines without ':', which
+//      cannot be IPv6; Ipv6Addr::from_str for the rest
+//
+// Accept/reject rules (std's, which fast_v4 reproduces for IPv4):
+//
+// - IPv4 is exactly four '.'-separated decimal octets of 1-3 digits, each
+//   at most 255, with no leading zero unless the octet is "0": "0.0.0.0"
+//   and "1.2.3.0" are valid, "01.2.3.4", "1.2.3.04", "256.1.1.1", "1.2.3",
+//   "1.2.3.4.5", "1..3.4" and "4294967295" are not.
+// - IPv6 is up to eight ':'-separated groups of 1-4 hex digits in either
+//   case, with at most one "::" standing for one or more zero groups:
+//   "::", "1::", "1:2:3:4:5:6:7::" and "::0:0:0:0:0:0:0" are valid,
+//   "1:2:3:4:5:6:7:8:9", "1:2:3:4:5:6:7::8", "1::2::3", "abcd0::1", ":1::2"
+//   and "1::2:" are not.
+// - The last 32 bits of IPv6 may be written as IPv4, with the same rules:
+//   "::ffff:1.2.3.4", "::1.2.3.4" and "1:2:3:4:5:6:1.2.3.4" are valid,
+//   "::ffff:01.2.3.4" and "1:2:3:4:5:6:7:1.2.3.4" are not.
+// - No whitespace, brackets ("[::1]") or zone IDs ("fe80::1%eth0").
+//
+// Lines "1.2.3.4", "::ffff:1.2.3.4", "01.2.3.4", "fe80::1%eth0" give 1, 1, 2
+// and 0x0000ffff00000000.
+const MODE_STD: u8 = 0;
+const MODE_FAST_V4: u8 = 1;
+
+enum Parsed {
+    V4(u32),
+    V6(u128),
+    Invalid,
+}
+
+fn parse_std(line: &[u8]) -> Parsed {
+    match std::str::from_utf8(line).ok().and_then(|s| s.parse::<IpAddr>().ok()) {
+        Some(IpAddr::V4(a)) => Parsed::V4(u32::from(a)),
+        Some(IpAddr::V6(a)) => Parsed::V6(u128::from(a)),
+        None => Parsed::Invalid,
+    }
+}
+
+fn parse_v4(line: &[u8]) -> Option<u32> {
+    let mut addr = 0u32;
+    let mut octets = line.split(|&b| b == b'.');
+    for _ in 0..4 {
+        let octet = octets.next()?;
+        if octet.is_empty() || octet.len() > 3 || (octet.len() > 1 && octet[0] == b'0') {
+            return None;
+        }
+        let mut v = 0u32;
+        for &b in octet {
+            let d = b.wrapping_sub(b'0');
+            if d > 9 {
+                return None;
+            }
+            v = v * 10 + d as u32;
+        }
+        if v > 255 {
+            return None;
+        }
+        addr = addr << 8 | v;
+    }
+    match octets.next() {
+        Some(_) => None,
+        None => Some(addr),
+    }
+}
+
+fn parse_fast_v4(line: &[u8]) -> Parsed {
+    if !line.contains(&b':') {
+        return parse_v4(line).map_or(Parsed::Invalid, Parsed::V4);
+    }
+    match std::str::from_utf8(line).ok().and_then(|s| s.parse::<Ipv6Addr>().ok()) {
+        Some(a) => Parsed::V6(u128::from(a)),
+        None => Parsed::Invalid,
+    }
+}
+
+fn run(input: &[u8]) -> Result<[u64; 4], String> {
+    let (&mode, text) = input.split_first().ok_or("missing mode byte")?;
+    let parse = match mode {
+        MODE_STD => parse_std,
+        MODE_FAST_V4 => parse_fast_v4,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+
+    let (mut v4, mut v6, mut invalid, mut folded) = (0u64, 0u64, 0u64, 0u64);
+    for line in text.split_inclusive(|&b| b == b'\n') {
+        match parse(line.strip_suffix(b"\n").unwrap_or(line)) {
+            Parsed::V4(a) => {
+                v4 += 1;
+                folded ^= a as u64;
+            }
+            Parsed::V6(a) => {
+                v6 += 1;
+                folded ^= (a >> 64) as u64 ^ a as u64;
+            }
+            Parsed::Invalid => invalid += 1,
+        }
+    }
+    Ok([v4, v6, invalid, folded])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(fields) => {
+            let output: Vec<u8> = fields.iter().flat_map(|f| f.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::io::{Read, Write};
+
+// Lloyd's k-means. Input: u32 LE point count N, dimensions D, clusters K,
+// and iterations, then N * D f64 LE coordinates, point by point. Output:
+// the K * D final centroid coordinates as f64 LE. D must be at least 1, K
+// in 1..=N (or 0 with N = 0), and every coordinate finite; otherwise, or on
+// a wrong length, exits with status 2.
+//
+// Everything that affects rounding is fixed, so that an implementation doing
+// the same f64 operations in the same order gives the same bits:
+//
+// - The initial centroids are the first K points.
+// - Each iteration assigns every point to the centroid with the smallest
+//   squared distance, the sum over dimensions in order of (p - c)^2; ties go
+//   to the lowest cluster index.
+// - Each centroid then becomes the mean of its points: per dimension, the
+//   sum in point order divided by the count. A cluster with no points keeps
+//   its centroid.
+// - Exactly the given number of iterations run; there is no convergence
+//   test.
+//
+// 1-D points 0, 2, 1, 10 with K = 2: one iteration gives 0.5 and 6 (point 1
+// is equidistant from 0 and 2 and goes to cluster 0); two give 1 and 10.
+fn run(input: &[u8]) -> Result<Vec<f64>, String> {
+    if input.len() < 16 {
+        return Err(format!("truncated header: need 16 bytes, got {}", input.len()));
+    }
+    let word = |i: usize| {
+        u32::from_le_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]) as usize
+    };
+    let (n, d, k, iterations) = (word(0), word(4), word(8), word(12));
+    let body = &input[16..];
+    let need = 8 * n as u64 * d as u64;
+    if body.len() as u64 != need {
+        return Err(format!("{n} points of {d} dimensions need {need} bytes, got {}", body.len()));
+    }
+    if d == 0 {
+        return Err("points need at least one dimension".to_string());
+    }
+    if k > n || (k == 0 && n > 0) {
+        return Err(format!("{k} clusters for {n} points"));
+    }
+    let points: Vec<f64> = body
+        .chunks_exact(8)
+        .map(|b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .collect();
+    if let Some(i) = points.iter().position(|x| !x.is_finite()) {
+        return Err(format!("coordinate {i} is not finite"));
+    }
+
+    let mut centroids = points[..k * d].to_vec();
+    let mut sums = vec![0f64; k * d];
+    let mut counts = vec![0u64; k];
+    for _ in 0..iterations {
+        sums.fill(0.0);
+        counts.fill(0);
+        for p in points.chunks_exact(d) {
+            let mut best = (f64::INFINITY, 0);
+            for (c, centroid) in centroids.chunks_exact(d).enumerate() {
+                let dist = p.iter().zip(centroid).fold(0.0, |sum, (a, b)| sum + (a - b) * (a - b));
+                if dist < best.0 {
+                    best = (dist, c);
+                }
+            }
+            let c = best.1;
+            counts[c] += 1;
+            for (s, x) in sums[c * d..(c + 1) * d].iter_mut().zip(p) {
+                *s += x;
+            }
+        }
+        let clusters = centroids.chunks_exact_mut(d).zip(sums.chunks_exact(d));
+        for ((centroid, sum), &count) in clusters.zip(&counts) {
+            if count > 0 {
+                for (x, s) in centroid.iter_mut().zip(sum) {
+                    *x = s / count as f64;
+                }
+            }
+        }
+    }
+    Ok(centroids)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(centroids) => {
+            let output: Vec<u8> = centroids.iter().flat_map(|x| x.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
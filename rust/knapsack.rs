@@ -0,0 +1,83 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then a capacity C and an item count N as u32 LE, then
+// N (weight, value) pairs of u32 LE. Output: the largest total value of a
+// set of items, each taken at most once, whose weights sum to at most C, as
+// a u64 LE. An unknown or missing mode byte, C over 2^27, or an input that
+// is not exactly 9 + 8N bytes exits with status 2.
+//
+// The 0/1 knapsack DP on one rolling array: best[c] is the best value
+// within weight c using the items so far, and each item updates it for c
+// from C down to the item's weight, so best[c - weight] is still the value
+// without this item. Items heavier than C change nothing.
+//
+//   0  branchy: store the candidate only if it is larger
+//   1  branchless: select between the old value and the candidate with a
+//      mask, storing every time
+//
+// Both modes give the same output. C = 10 with items (5, 10), (4, 40),
+// (6, 30), (3, 50) gives 90 (the second and fourth); C = 0, no items, or
+// only items heavier than C give 0.
+const MODE_BRANCHY: u8 = 0;
+const MODE_BRANCHLESS: u8 = 1;
+const MAX_CAPACITY: u32 = 1 << 27;
+
+fn add_branchy(best: &mut [u64], weight: usize, value: u64) {
+    for c in (weight..best.len()).rev() {
+        let candidate = best[c - weight] + value;
+        if candidate > best[c] {
+            best[c] = candidate;
+        }
+    }
+}
+
+fn add_branchless(best: &mut [u64], weight: usize, value: u64) {
+    for c in (weight..best.len()).rev() {
+        let (old, candidate) = (best[c], best[c - weight] + value);
+        let take = ((candidate > old) as u64).wrapping_neg();
+        best[c] = old ^ ((old ^ candidate) & take);
+    }
+}
+
+fn run(input: &[u8]) -> Result<u64, String> {
+    let (&mode, rest) = input.split_first().ok_or("missing mode byte")?;
+    let add: fn(&mut [u64], usize, u64) = match mode {
+        MODE_BRANCHY => add_branchy,
+        MODE_BRANCHLESS => add_branchless,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+    if rest.len() < 8 {
+        return Err(format!("truncated header: need 8 bytes after the mode, got {}", rest.len()));
+    }
+    let word = |i: usize| u32::from_le_bytes([rest[i], rest[i + 1], rest[i + 2], rest[i + 3]]);
+    let (capacity, n) = (word(0), word(4) as u64);
+    if capacity > MAX_CAPACITY {
+        return Err(format!("capacity {capacity} is over {MAX_CAPACITY}"));
+    }
+    if rest.len() as u64 != 8 + 8 * n {
+        return Err(format!("{n} items need {} bytes after the mode, got {}", 8 + 8 * n, rest.len()));
+    }
+
+    let mut best = vec![0u64; capacity as usize + 1];
+    for item in rest[8..].chunks_exact(8) {
+        let weight = u32::from_le_bytes([item[0], item[1], item[2], item[3]]);
+        let value = u32::from_le_bytes([item[4], item[5], item[6], item[7]]);
+        if weight <= capacity {
+            add(&mut best, weight as usize, value as u64);
+        }
+    }
+    Ok(best[capacity as usize])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(v) => std::io::stdout().write_all(&v.to_le_bytes()).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
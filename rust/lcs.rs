@@ -0,0 +1,104 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then string A's length as u32 LE, then A, then string
+// B (the rest of stdin), as raw bytes, levenshtein's framing after the mode
+// byte. Output: the length of the longest common subsequence as u64 LE. An
+// unknown or missing mode byte, or a length that runs past the end of the
+// input, exits with status 2.
+//
+//   0  bits: the bit-parallel algorithm below
+//   1  naive: the O(|A||B|) DP, to cross-check mode 0 on small inputs
+//
+// Bit-parallel LCS (Allison-Dix, in Hyyro's form): bit j of V is cleared once
+// the LCS of the prefix of A seen so far and B[..=j] steps up at j, and each
+// byte of A updates V with one multi-word add:
+//
+//     V = (V + (V & M[x])) | (V & !M[x])
+//
+// where M[x] marks the positions of x in B. The LCS is the number of
+// cleared bits. Both modes give the same output. Check: "ABCBDAB" /
+// "BDCABA" is 4.
+const MODE_BITS: u8 = 0;
+const MODE_NAIVE: u8 = 1;
+
+// Splits the input after the mode byte; offsets in errors count from the
+// start of the input, mode byte included.
+fn split(input: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    if input.len() < 5 {
+        return Err(format!("truncated header: need 5 bytes, got {}", input.len()));
+    }
+    let len = u32::from_le_bytes([input[1], input[2], input[3], input[4]]) as usize;
+    let rest = &input[5..];
+    if len > rest.len() {
+        return Err(format!("string A ends at byte {}, past end of input ({})", 5 + len, input.len()));
+    }
+    Ok(rest.split_at(len))
+}
+
+fn lcs_bits(a: &[u8], b: &[u8]) -> u64 {
+    // The bit vector runs over the shorter string.
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.is_empty() {
+        return 0;
+    }
+
+    let words = b.len().div_ceil(64);
+    let mut masks = vec![0u64; 256 * words];
+    for (j, &y) in b.iter().enumerate() {
+        masks[y as usize * words + j / 64] |= 1 << (j % 64);
+    }
+
+    let mut v = vec![!0u64; words];
+    for &x in a {
+        let m = &masks[x as usize * words..][..words];
+        let mut carry = false;
+        for (v, &m) in v.iter_mut().zip(m) {
+            let u = *v & m;
+            let (sum, c1) = v.overflowing_add(u);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            carry = c1 || c2;
+            *v = sum | (*v & !m);
+        }
+    }
+
+    // Bits past the end of B stay set: M is zero there, so `V & !M` keeps
+    // them whatever the add carries in.
+    v.iter().map(|w| w.count_zeros() as u64).sum()
+}
+
+fn lcs_naive(a: &[u8], b: &[u8]) -> u64 {
+    // prev[j] is the LCS of a[..i] and b[..j].
+    let mut prev = vec![0u64; b.len() + 1];
+    let mut cur = vec![0u64; b.len() + 1];
+    for &x in a {
+        for (j, &y) in b.iter().enumerate() {
+            cur[j + 1] = if x == y { prev[j] + 1 } else { prev[j + 1].max(cur[j]) };
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+fn run(input: &[u8]) -> Result<u64, String> {
+    let &mode = input.first().ok_or("missing mode byte")?;
+    let lcs = match mode {
+        MODE_BITS => lcs_bits,
+        MODE_NAIVE => lcs_naive,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+    let (a, b) = split(input)?;
+    Ok(lcs(a, b))
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(len) => std::io::stdout().write_all(&len.to_le_bytes()).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
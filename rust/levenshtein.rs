@@ -0,0 +1,57 @@
+use std::io::{Read, Write};
+
+// Input: string A's length as u32 LE, then A, then string B (the rest of
+// stdin). Both are raw bytes; no UTF-8 is required. Output: the edit
+// distance (unit-cost insert, delete, substitute) as u64 LE. A length that
+// runs past the end of the input exits with status 2.
+//
+// Two-row DP over the shorter string, so memory is O(min(|A|, |B|)).
+// Checks: "kitten" / "sitting" is 3, identical strings are 0, strings over
+// disjoint alphabets are max(|A|, |B|), and an empty side is the other's
+// length.
+fn split(input: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    if input.len() < 4 {
+        return Err(format!("truncated header: need 4 bytes, got {}", input.len()));
+    }
+    let len = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
+    let rest = &input[4..];
+    if len > rest.len() {
+        return Err(format!("string A ends at byte {}, past end of input ({})", 4 + len, input.len()));
+    }
+    Ok(rest.split_at(len))
+}
+
+fn levenshtein(a: &[u8], b: &[u8]) -> u64 {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if short.is_empty() {
+        return long.len() as u64;
+    }
+
+    // prev[j] is the distance between long[..i] and short[..j].
+    let mut prev: Vec<u64> = (0..=short.len() as u64).collect();
+    let mut cur = vec![0u64; short.len() + 1];
+    for (i, &x) in long.iter().enumerate() {
+        cur[0] = i as u64 + 1;
+        for (j, &y) in short.iter().enumerate() {
+            let substitute = prev[j] + (x != y) as u64;
+            let delete = prev[j + 1] + 1;
+            let insert = cur[j] + 1;
+            cur[j + 1] = substitute.min(delete).min(insert);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[short.len()]
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match split(&input) {
+        Ok((a, b)) => std::io::stdout().write_all(&levenshtein(a, b).to_le_bytes()).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
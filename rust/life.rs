@@ -0,0 +1,79 @@
+use std::io::{Read, Write};
+
+// Conway's Game of Life (B3/S23). Input: u32 LE width, height, and
+// generation count, then width * height cell bytes, row-major, each 0 or 1.
+// Output: the grid after that many generations, in the same cell format. A
+// wrong length or a cell byte other than 0 or 1 exits with status 2.
+//
+// The grid is a torus: row -1 is row height - 1 and column -1 is column
+// width - 1. Each of the eight neighbour offsets is taken modulo the size
+// on its own, so on a grid narrower or shorter than 3 the same cell can be
+// counted more than once (width 1: a cell is its own left and right
+// neighbour). life_bitpacked follows the same rule.
+//
+// A glider moves one cell down and right every 4 generations; on a 32x32
+// torus it is back where it started after 128. A blinker has period 2.
+//
+// Two flat Vec<u8> buffers swap each generation. Interior cells sum their
+// neighbours over three row windows, which the compiler vectorizes; the
+// first and last columns wrap with modular indexes.
+fn step(grid: &[u8], next: &mut [u8], w: usize, h: usize) {
+    for y in 0..h {
+        let up = &grid[(y + h - 1) % h * w..][..w];
+        let mid = &grid[y * w..][..w];
+        let down = &grid[(y + 1) % h * w..][..w];
+        let out = &mut next[y * w..][..w];
+
+        let rule = |sum: u8, alive: u8| (sum == 3) as u8 | (alive & (sum == 2) as u8);
+        if w >= 3 {
+            let windows = up.windows(3).zip(mid.windows(3)).zip(down.windows(3));
+            for (o, ((u, m), d)) in out[1..w - 1].iter_mut().zip(windows) {
+                let sum = u[0] + u[1] + u[2] + m[0] + m[2] + d[0] + d[1] + d[2];
+                *o = rule(sum, m[1]);
+            }
+        }
+        for x in [0, w - 1] {
+            let (l, r) = ((x + w - 1) % w, (x + 1) % w);
+            let sum = up[l] + up[x] + up[r] + mid[l] + mid[r] + down[l] + down[x] + down[r];
+            out[x] = rule(sum, mid[x]);
+        }
+    }
+}
+
+fn run(input: &[u8]) -> Result<Vec<u8>, String> {
+    if input.len() < 12 {
+        return Err(format!("truncated header: need 12 bytes, got {}", input.len()));
+    }
+    let word = |i: usize| u32::from_le_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]);
+    let (w, h, generations) = (word(0) as usize, word(4) as usize, word(8));
+    let cells = &input[12..];
+    if cells.len() as u64 != w as u64 * h as u64 {
+        return Err(format!("{w}x{h} needs {} cell bytes, got {}", w as u64 * h as u64, cells.len()));
+    }
+    if let Some(i) = cells.iter().position(|&c| c > 1) {
+        return Err(format!("cell {i} is {}, not 0 or 1", cells[i]));
+    }
+
+    let mut grid = cells.to_vec();
+    if !grid.is_empty() {
+        let mut next = vec![0u8; grid.len()];
+        for _ in 0..generations {
+            step(&grid, &mut next, w, h);
+            std::mem::swap(&mut grid, &mut next);
+        }
+    }
+    Ok(grid)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(grid) => std::io::stdout().write_all(&grid).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
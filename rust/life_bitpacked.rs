@@ -0,0 +1,121 @@
+use std::io::{Read, Write};
+
+// life's input and output (the rules, including the toroidal wrap, are
+// described there), computed on rows packed 64 cells per u64: bit x % 64 of
+// word x / 64, bits past the width kept zero.
+//
+// Each generation first builds the left and right neighbour planes of every
+// row: the row shifted by one bit across words, with the wrapped-around end
+// bit put back. The eight neighbour planes around a row are then summed
+// bit-sliced with adders, 64 cells per word operation.
+fn load(cells: &[u8], w: usize, h: usize) -> Vec<u64> {
+    let words = w.div_ceil(64);
+    let mut grid = vec![0u64; words * h];
+    for (y, row) in cells.chunks_exact(w).enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            grid[y * words + x / 64] |= (c as u64) << (x % 64);
+        }
+    }
+    grid
+}
+
+fn store(grid: &[u64], w: usize, h: usize) -> Vec<u8> {
+    let words = w.div_ceil(64);
+    let mut cells = Vec::with_capacity(w * h);
+    for row in grid.chunks_exact(words) {
+        cells.extend((0..w).map(|x| (row[x / 64] >> (x % 64) & 1) as u8));
+    }
+    cells
+}
+
+/// `row` with every cell replaced by its left (x - 1) and right (x + 1)
+/// neighbour, wrapping at the width.
+fn neighbours(row: &[u64], w: usize, left: &mut [u64], right: &mut [u64]) {
+    let n = row.len();
+    let last = (w - 1) % 64;
+    for i in 0..n {
+        let below = if i > 0 { row[i - 1] >> 63 } else { 0 };
+        let above = if i + 1 < n { row[i + 1] << 63 } else { 0 };
+        left[i] = row[i] << 1 | below;
+        right[i] = row[i] >> 1 | above;
+    }
+    // The bit shifted past the width comes back at the other end.
+    left[n - 1] &= u64::MAX >> (63 - last);
+    left[0] |= row[n - 1] >> last & 1;
+    right[n - 1] |= (row[0] & 1) << last;
+}
+
+fn row(plane: &[u64], r: usize, words: usize) -> &[u64] {
+    &plane[r * words..][..words]
+}
+
+fn step(grid: &[u64], next: &mut [u64], planes: &mut [Vec<u64>; 2], w: usize, h: usize) {
+    let words = w.div_ceil(64);
+    let [left, right] = planes;
+    let rows = grid.chunks_exact(words).zip(left.chunks_exact_mut(words));
+    for ((cells, l), r) in rows.zip(right.chunks_exact_mut(words)) {
+        neighbours(cells, w, l, r);
+    }
+
+    for y in 0..h {
+        let (u, d) = ((y + h - 1) % h, (y + 1) % h);
+        let (ul, uc, ur) = (row(left, u, words), row(grid, u, words), row(right, u, words));
+        let (ml, mc, mr) = (row(left, y, words), row(grid, y, words), row(right, y, words));
+        let (dl, dc, dr) = (row(left, d, words), row(grid, d, words), row(right, d, words));
+        let out = &mut next[y * words..][..words];
+        for i in 0..words {
+            // Ones and twos of each row's neighbour count (full adders for
+            // the rows above and below, a half adder for the cell's own).
+            let (u1, u2) = (ul[i] ^ uc[i] ^ ur[i], (ul[i] & uc[i]) | (ur[i] & (ul[i] ^ uc[i])));
+            let (d1, d2) = (dl[i] ^ dc[i] ^ dr[i], (dl[i] & dc[i]) | (dr[i] & (dl[i] ^ dc[i])));
+            let (m1, m2) = (ml[i] ^ mr[i], ml[i] & mr[i]);
+            // The count is ones + 2 * (u2 + d2 + m2 + carry); it is 2 or 3
+            // exactly when one of those four twos is set.
+            let ones = u1 ^ d1 ^ m1;
+            let carry = (u1 & d1) | (m1 & (u1 ^ d1));
+            let (p, q) = (u2 ^ d2, m2 ^ carry);
+            let one_two = (p ^ q) & !((u2 & d2) | (m2 & carry));
+            out[i] = one_two & (ones | mc[i]);
+        }
+    }
+}
+
+fn run(input: &[u8]) -> Result<Vec<u8>, String> {
+    if input.len() < 12 {
+        return Err(format!("truncated header: need 12 bytes, got {}", input.len()));
+    }
+    let word = |i: usize| u32::from_le_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]);
+    let (w, h, generations) = (word(0) as usize, word(4) as usize, word(8));
+    let cells = &input[12..];
+    if cells.len() as u64 != w as u64 * h as u64 {
+        return Err(format!("{w}x{h} needs {} cell bytes, got {}", w as u64 * h as u64, cells.len()));
+    }
+    if let Some(i) = cells.iter().position(|&c| c > 1) {
+        return Err(format!("cell {i} is {}, not 0 or 1", cells[i]));
+    }
+    if cells.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut grid = load(cells, w, h);
+    let mut next = vec![0u64; grid.len()];
+    let mut planes = [vec![0u64; grid.len()], vec![0u64; grid.len()]];
+    for _ in 0..generations {
+        step(&grid, &mut next, &mut planes, w, h);
+        std::mem::swap(&mut grid, &mut next);
+    }
+    Ok(store(&grid, w, h))
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(grid) => std::io::stdout().write_all(&grid).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::io::{Read, Write};
+
+// Input: the image size N as u32 LE. Output: an N x N bitmap, one bit per
+// pixel, most significant bit first, each row padded to a whole byte (the
+// Benchmarks Game mandelbrot output without the PBM header). A short input
+// exits with status 2.
+//
+// Pixel (x, y) is c = (2x/N - 1.5) + (2y/N - 1)i. Starting from z = 0, z is
+// updated to z^2 + c up to 50 times, stopping early once |z|^2 > 4; the bit
+// is 1 if |z|^2 <= 4 after the loop. The exact operation order matters for
+// boundary pixels and is the one in `pixel`.
+//
+// Known answer: N = 16 gives the rows (hex, 2 bytes each) 0008 0010 0010
+// 007c 00fe 01fe 19ff 1dfe fffe 1dfe 19ff 01fe 00fe 007c 0010 0010.
+const EXIT_MALFORMED: i32 = 2;
+const ITERATIONS: u32 = 50;
+
+fn pixel(cr: f64, ci: f64) -> bool {
+    let (mut zr, mut zi, mut tr, mut ti) = (0.0f64, 0.0f64, 0.0f64, 0.0f64);
+    let mut i = 0;
+    while i < ITERATIONS && tr + ti <= 4.0 {
+        zi = 2.0 * zr * zi + ci;
+        zr = tr - ti + cr;
+        tr = zr * zr;
+        ti = zi * zi;
+        i += 1;
+    }
+    tr + ti <= 4.0
+}
+
+fn row(n: usize, y: usize, out: &mut [u8]) {
+    let ci = 2.0 * y as f64 / n as f64 - 1.0;
+    for (byte_index, byte) in out.iter_mut().enumerate() {
+        let mut bits = 0u8;
+        for bit in 0..8 {
+            let x = byte_index * 8 + bit;
+            if x < n && pixel(2.0 * x as f64 / n as f64 - 1.5, ci) {
+                bits |= 0x80 >> bit;
+            }
+        }
+        *byte = bits;
+    }
+}
+
+fn main() {
+    let mut buf = [0u8; 4];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    let n = u32::from_le_bytes(buf) as usize;
+
+    let row_bytes = n.div_ceil(8);
+    let mut output = vec![0u8; row_bytes * n];
+    if row_bytes > 0 {
+        for (y, out) in output.chunks_mut(row_bytes).enumerate() {
+            row(n, y, out);
+        }
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,78 @@
+use std::io::{Read, Write};
+
+// Input: n and seed as two u32 LE. Both n x n matrices are generated from
+// the seed, so the input stays 8 bytes; output: the bits of the sum of all
+// elements of A * B (f64::to_bits), as a u64 LE. A short input exits with
+// status 2.
+//
+// Matrix generation, which other implementations must reproduce bit for bit:
+//
+//   state = seed as u64 ^ 0x9E37_79B9_7F4A_7C15   (never zero for a u32 seed)
+//   next():                                       (xorshift64*)
+//       state ^= state >> 12
+//       state ^= state << 25
+//       state ^= state >> 27
+//       return state * 0x2545_F491_4F6C_DD1D       (wrapping)
+//   element = (next() >> 11) as f64 * 2^-53        (uniform in [0, 1))
+//
+// All n*n elements of A are drawn first, then B, each in row-major order.
+// The product is accumulated in i-k-j order (C[i][j] += A[i][k] * B[k][j]
+// for k ascending, with no fused multiply-add) and C is summed in row-major
+// order starting from 0.0.
+//
+// Check values (seed 0): n = 1 draws A = 0.052790873358508184 and
+// B = 0.33112028100185353 and outputs 0x3F91E64F9675FA98; n = 2 outputs
+// 0x3FFDB0479E99736A.
+const EXIT_MALFORMED: i32 = 2;
+
+struct XorShift64Star(u64);
+
+impl XorShift64Star {
+    fn new(seed: u32) -> Self {
+        XorShift64Star(seed as u64 ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+fn read_header() -> (usize, u32) {
+    let mut buf = [0u8; 8];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    let n = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    let seed = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+    (n, seed)
+}
+
+fn main() {
+    let (n, seed) = read_header();
+    let mut rng = XorShift64Star::new(seed);
+    let a: Vec<f64> = (0..n * n).map(|_| rng.next_f64()).collect();
+    let b: Vec<f64> = (0..n * n).map(|_| rng.next_f64()).collect();
+
+    let mut c = vec![0.0f64; n * n];
+    for i in 0..n {
+        for k in 0..n {
+            let aik = a[i * n + k];
+            for j in 0..n {
+                c[i * n + j] += aik * b[k * n + j];
+            }
+        }
+    }
+
+    let sum: f64 = c.iter().fold(0.0, |acc, &x| acc + x);
+    std::io::stdout().write_all(&sum.to_bits().to_le_bytes()).unwrap();
+}
@@ -0,0 +1,174 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::{Read, Write};
+
+// Input: width W, height H and seed as three u32 LE. A W×H maze is carved
+// from the seed, then solved from the top-left cell to the bottom-right one.
+// Output: the path length in moves as a u64 LE, then the moves, one byte
+// each: 0 north (up), 1 east, 2 south, 3 west. W or H of 0, more than 2^24
+// cells, or a short input exits with status 2.
+//
+// Generation, which other implementations must reproduce exactly:
+//
+//   the generator is rust/matmul.rs's xorshift64*, seeded the same way
+//   cell (x, y) is index y * W + x; every wall starts closed
+//   visit (0, 0) and push it on a stack
+//   while the stack is not empty:
+//       c = top of the stack
+//       u = c's unvisited neighbours, in the order N, E, S, W
+//       if u is empty, pop c
+//       else take u[next() % len(u)], open the wall between it and c,
+//           visit it and push it
+//
+// This is recursive backtracking with the recursion on a Vec, so the maze
+// is perfect: exactly one path joins any two cells. A* (Manhattan distance
+// to the exit, which never overestimates) finds that path; since it is the
+// only one, the output does not depend on heap order or tie-breaking.
+//
+// A 1×1 maze gives length 0 and no moves. Seed 0 carves 2×2 as 0 E 1 S 3 W
+// 2 (the bottom-left cell a dead end off the bottom-right), so the path is
+// E, S: 02 00 00 00 00 00 00 00 01 02.
+const MAX_CELLS: u64 = 1 << 24;
+const NORTH: u8 = 0;
+const EAST: u8 = 1;
+const SOUTH: u8 = 2;
+const WEST: u8 = 3;
+
+struct XorShift64Star(u64);
+
+impl XorShift64Star {
+    fn new(seed: u32) -> Self {
+        XorShift64Star(seed as u64 ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+struct Maze {
+    w: usize,
+    h: usize,
+    // Bit d of open[c] is set when the wall on c's side d is open.
+    open: Vec<u8>,
+}
+
+impl Maze {
+    /// The cell one step from c in direction d, if it is inside the maze.
+    fn step(&self, c: usize, d: u8) -> Option<usize> {
+        let (x, y) = (c % self.w, c / self.w);
+        match d {
+            NORTH if y > 0 => Some(c - self.w),
+            EAST if x + 1 < self.w => Some(c + 1),
+            SOUTH if y + 1 < self.h => Some(c + self.w),
+            WEST if x > 0 => Some(c - 1),
+            _ => None,
+        }
+    }
+
+    fn generate(w: usize, h: usize, seed: u32) -> Maze {
+        let mut maze = Maze { w, h, open: vec![0; w * h] };
+        let mut rng = XorShift64Star::new(seed);
+        let mut visited = vec![false; w * h];
+        visited[0] = true;
+        let mut stack = vec![0];
+        while let Some(&c) = stack.last() {
+            let mut unvisited = [(0u8, 0usize); 4];
+            let mut count = 0;
+            for d in [NORTH, EAST, SOUTH, WEST] {
+                if let Some(n) = maze.step(c, d).filter(|&n| !visited[n]) {
+                    unvisited[count] = (d, n);
+                    count += 1;
+                }
+            }
+            if count == 0 {
+                stack.pop();
+                continue;
+            }
+            let (d, n) = unvisited[(rng.next() % count as u64) as usize];
+            maze.open[c] |= 1 << d;
+            maze.open[n] |= 1 << (d ^ 2);
+            visited[n] = true;
+            stack.push(n);
+        }
+        maze
+    }
+
+    /// The moves from the top-left cell to the bottom-right one, by A*.
+    fn solve(&self) -> Vec<u8> {
+        let goal = self.w * self.h - 1;
+        let heuristic = |c: usize| ((self.w - 1 - c % self.w) + (self.h - 1 - c / self.w)) as u64;
+        let mut dist = vec![u64::MAX; self.w * self.h];
+        // The move that reached each cell on its best known path.
+        let mut came_by = vec![0u8; self.w * self.h];
+        let mut heap = BinaryHeap::new();
+        dist[0] = 0;
+        heap.push(Reverse((heuristic(0), 0u64, 0usize)));
+        while let Some(Reverse((_, g, c))) = heap.pop() {
+            if c == goal {
+                break;
+            }
+            if g > dist[c] {
+                continue;
+            }
+            for d in [NORTH, EAST, SOUTH, WEST] {
+                if self.open[c] & (1 << d) == 0 {
+                    continue;
+                }
+                let n = self.step(c, d).unwrap();
+                if g + 1 < dist[n] {
+                    dist[n] = g + 1;
+                    came_by[n] = d;
+                    heap.push(Reverse((g + 1 + heuristic(n), g + 1, n)));
+                }
+            }
+        }
+
+        let mut moves = Vec::with_capacity(dist[goal] as usize);
+        let mut c = goal;
+        while c != 0 {
+            moves.push(came_by[c]);
+            c = self.step(c, came_by[c] ^ 2).unwrap();
+        }
+        moves.reverse();
+        moves
+    }
+}
+
+fn run(input: &[u8]) -> Result<Vec<u8>, String> {
+    if input.len() < 12 {
+        return Err(format!("truncated header: need 12 bytes, got {}", input.len()));
+    }
+    let word = |i: usize| u32::from_le_bytes([input[i], input[i + 1], input[i + 2], input[i + 3]]);
+    let (w, h, seed) = (word(0), word(4), word(8));
+    if w == 0 || h == 0 {
+        return Err(format!("{w}x{h} maze has no cells"));
+    }
+    if w as u64 * h as u64 > MAX_CELLS {
+        return Err(format!("{w}x{h} maze is over {MAX_CELLS} cells"));
+    }
+
+    let moves = Maze::generate(w as usize, h as usize, seed).solve();
+    let mut output = Vec::with_capacity(8 + moves.len());
+    output.extend_from_slice(&(moves.len() as u64).to_le_bytes());
+    output.extend_from_slice(&moves);
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use std::io::Read;
+
+// Startup baseline: process spawn, loader, and stdin plumbing, nothing else.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+}
@@ -0,0 +1,100 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then a board size N as u32 LE, at most 16. Output: the
+// number of ways to place N non-attacking queens on an N×N board, as a u64
+// LE. An unknown or missing mode byte, a short input or N over 16 exits with
+// status 2.
+//
+//   0  recursive: one call per placed queen
+//   1  iterative: the same search with an explicit stack of rows
+//
+// Both fill one row at a time, with three masks of the squares the queens
+// so far attack in the current row: their columns, and the diagonals going
+// down-left and down-right, which shift one square per row. Each free
+// square, lowest column first, is tried in turn.
+//
+// N = 0 gives 1 (the empty board), 2 and 3 give 0, 8 gives 92, 12 14200,
+// 15 2279184, 16 14772512 (OEIS A000170).
+const MODE_RECURSIVE: u8 = 0;
+const MODE_ITERATIVE: u8 = 1;
+const MAX_N: u32 = 16;
+
+fn count_recursive(full: u32, cols: u32, left: u32, right: u32) -> u64 {
+    if cols == full {
+        return 1;
+    }
+    let mut free = full & !(cols | left | right);
+    let mut count = 0;
+    while free != 0 {
+        let bit = free & free.wrapping_neg();
+        free ^= bit;
+        count += count_recursive(full, cols | bit, (left | bit) << 1, (right | bit) >> 1);
+    }
+    count
+}
+
+fn recursive(n: u32) -> u64 {
+    count_recursive((1 << n) - 1, 0, 0, 0)
+}
+
+fn iterative(n: u32) -> u64 {
+    if n == 0 {
+        return 1;
+    }
+    let full = (1u32 << n) - 1;
+    // stack[row] holds the masks on entering that row and the squares in it
+    // still to try.
+    let mut stack = [(0u32, 0u32, 0u32, 0u32); MAX_N as usize];
+    stack[0] = (0, 0, 0, full);
+    let mut row = 0;
+    let mut count = 0;
+    loop {
+        let (cols, left, right, free) = stack[row];
+        if free == 0 {
+            if row == 0 {
+                return count;
+            }
+            row -= 1;
+            continue;
+        }
+        let bit = free & free.wrapping_neg();
+        stack[row].3 = free ^ bit;
+        let (cols, left, right) = (cols | bit, (left | bit) << 1, (right | bit) >> 1);
+        if cols == full {
+            count += 1;
+        } else {
+            row += 1;
+            stack[row] = (cols, left, right, full & !(cols | left | right));
+        }
+    }
+}
+
+fn run(input: &[u8]) -> Result<u64, String> {
+    let (&mode, rest) = input.split_first().ok_or("missing mode byte")?;
+    let count = match mode {
+        MODE_RECURSIVE => recursive,
+        MODE_ITERATIVE => iterative,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+    if rest.len() < 4 {
+        return Err(format!("truncated header: need 4 bytes after the mode, got {}", rest.len()));
+    }
+    let n = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+    if n > MAX_N {
+        return Err(format!("board size {n} is over {MAX_N}"));
+    }
+    Ok(count(n))
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(count) => std::io::stdout().write_all(&count.to_le_bytes()).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::io::{Read, Write};
+
+// Input: newline-separated lines (a final newline is optional), each parsed
+// with str::parse::<f64>: an optional sign, then decimal digits with an
+// optional point and exponent ("1.5", "-.25", "5.", "1E+3"), or "inf",
+// "infinity" or "nan" in any case; no whitespace, underscores or hex.
+// Output, as four 8-byte LE fields:
+//
+// - the number of lines that parsed to a finite value
+// - the bit pattern of their sum, added in line order starting from +0.0
+// - the number of lines skipped because they parsed to an infinity or NaN,
+//   including values that overflow ("1e400" is inf)
+// - the number of lines that did not parse
+//
+// Underflow is not an error: "5e-324" is the smallest subnormal and
+// "2.4703282292062327e-324", just under half of it, parses to 0.
+//
+// Lines "1.5", "-0.25", "1e3", "5e-324", "inf", "NaN", "1e400", "abc", ""
+// give 4 finite, sum 1001.25 (bits 0x408f4a0000000000; the subnormal is
+// lost in rounding), 3 skipped and 2 invalid.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let (mut finite, mut sum, mut skipped, mut invalid) = (0u64, 0.0f64, 0u64, 0u64);
+    for line in input.split_inclusive(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        match std::str::from_utf8(line).ok().and_then(|s| s.parse::<f64>().ok()) {
+            Some(v) if v.is_finite() => {
+                finite += 1;
+                sum += v;
+            }
+            Some(_) => skipped += 1,
+            None => invalid += 1,
+        }
+    }
+
+    let mut output = Vec::with_capacity(32);
+    for field in [finite, sum.to_bits(), skipped, invalid] {
+        output.extend_from_slice(&field.to_le_bytes());
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,95 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then newline-separated lines (a final newline is
+// optional; a line is every byte between newlines, so "12\r" does not
+// parse). Each line is parsed as a decimal i64: an optional '+' or '-', then
+// one or more ASCII digits, leading zeros allowed, and the value in range
+// (an overflow is a failure, never wrapped). Output: the number of lines
+// parsed, their wrapping sum as i64 LE, and the number of lines that failed,
+// as three 8-byte LE fields. An unknown mode or missing mode byte exits with
+// status 2.
+//
+//   0  std: str::from_utf8 then str::parse::<i64>
+//   1  bytes: a hand-rolled parser over the bytes, accepting exactly what
+//      std does
+//
+// Lines "+5", "-007", "", "9223372036854775808", "-9223372036854775808",
+// "12" (no final newline) give 4 parsed, sum -9223372036854775798 (i64::MIN
+// + 10), and 2 failed: the empty line and the overflow.
+const MODE_STD: u8 = 0;
+const MODE_BYTES: u8 = 1;
+
+fn parse_std(line: &[u8]) -> Option<i64> {
+    std::str::from_utf8(line).ok()?.parse().ok()
+}
+
+fn parse_bytes(line: &[u8]) -> Option<i64> {
+    let (negative, digits) = match line.split_first() {
+        Some((b'-', rest)) => (true, rest),
+        Some((b'+', rest)) => (false, rest),
+        _ => (false, line),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    // Accumulate the magnitude in u64, which holds 2^63 for i64::MIN.
+    let mut v = 0u64;
+    for &b in digits {
+        let d = b.wrapping_sub(b'0');
+        if d > 9 {
+            return None;
+        }
+        v = v.checked_mul(10)?.checked_add(d as u64)?;
+    }
+    if negative {
+        if v > 1 << 63 {
+            None
+        } else {
+            Some(0u64.wrapping_sub(v) as i64)
+        }
+    } else if v > i64::MAX as u64 {
+        None
+    } else {
+        Some(v as i64)
+    }
+}
+
+fn run(input: &[u8]) -> Result<(u64, i64, u64), String> {
+    let (&mode, text) = input.split_first().ok_or("missing mode byte")?;
+    let parse = match mode {
+        MODE_STD => parse_std,
+        MODE_BYTES => parse_bytes,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+
+    let (mut parsed, mut sum, mut failed) = (0u64, 0i64, 0u64);
+    for line in text.split_inclusive(|&b| b == b'\n') {
+        match parse(line.strip_suffix(b"\n").unwrap_or(line)) {
+            Some(v) => {
+                parsed += 1;
+                sum = sum.wrapping_add(v);
+            }
+            None => failed += 1,
+        }
+    }
+    Ok((parsed, sum, failed))
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok((parsed, sum, failed)) => {
+            let mut output = Vec::with_capacity(24);
+            output.extend_from_slice(&parsed.to_le_bytes());
+            output.extend_from_slice(&sum.to_le_bytes());
+            output.extend_from_slice(&failed.to_le_bytes());
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
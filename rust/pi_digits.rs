@@ -0,0 +1,66 @@
+use std::cmp::Ordering;
+use std::io::{Read, Write};
+
+mod bignum;
+
+// Input: a digit count n as a u32 LE. Output: the first n decimal digits of
+// π as ASCII, "3" first, with no decimal point or newline; n = 0 gives no
+// output. A short input exits with status 2.
+//
+// This is the Benchmarks Game pidigits workload: Gibbons' unbounded spigot,
+// which folds in one term of the series per step and emits a digit once
+// floor((3 num + acc) / den) and floor((4 num + acc) / den) agree. acc goes
+// negative after some digits, so e = acc + 3 num, which never does, is kept
+// instead; the first floor is then e / den, and the second agrees with it
+// when the remainder plus num is below den. Every operation is a bignum
+// times or plus a small number, or a division whose quotient is a digit, so
+// n digits take O(n^2) limb operations (rust/bignum, shared with
+// bigint_factorial).
+//
+// The first 30 digits are 314159265358979323846264338327; digits 1001 to
+// 1010, after the first 1000, are 9380952572.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.len() < 4 {
+        eprintln!("malformed input: truncated header: need 4 bytes, got {}", input.len());
+        std::process::exit(2);
+    }
+    let n = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
+
+    let (mut e, mut den, mut num) = (vec![3u64], vec![1u64], vec![1u64]);
+    let mut scratch = Vec::new();
+    let mut digits = Vec::with_capacity(n);
+    let mut k = 0u64;
+    while digits.len() < n {
+        // Fold in term k: acc = (acc + 2 num)(2k + 1), den *= 2k + 1 and
+        // num *= k, so e = e (2k + 1) + num (k - 1).
+        k += 1;
+        bignum::mul_scalar(&mut e, 2 * k + 1);
+        bignum::add_mul(&mut e, &num, k - 1);
+        bignum::mul_scalar(&mut den, 2 * k + 1);
+        bignum::mul_scalar(&mut num, k);
+
+        // Skip while num > acc, that is e < 4 num.
+        scratch.clear();
+        bignum::add_mul(&mut scratch, &num, 4);
+        if bignum::cmp(&e, &scratch) == Ordering::Less {
+            continue;
+        }
+        scratch.clear();
+        scratch.extend_from_slice(&e);
+        let d = bignum::div_rem_small(&mut scratch, &den);
+        bignum::add_mul(&mut scratch, &num, 1);
+        if bignum::cmp(&scratch, &den) != Ordering::Less {
+            continue;
+        }
+        digits.push(b'0' + d as u8);
+        // Take the digit off and shift: e = 10 (e mod den), num *= 10.
+        bignum::sub_mul(&mut scratch, &num, 1);
+        std::mem::swap(&mut e, &mut scratch);
+        bignum::mul_scalar(&mut e, 10);
+        bignum::mul_scalar(&mut num, 10);
+    }
+
+    std::io::stdout().write_all(&digits).unwrap();
+}
@@ -0,0 +1,117 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then the data. Output: the number of set bits in the
+// data as u64 LE (0 for empty input). An unknown mode exits with status 2.
+//
+//   0  table: one 256-entry lookup per byte
+//   1  count_ones: u64::count_ones per 8-byte word (POPCNT where the target
+//      has it)
+//   2  harley_seal: carry-save adders fold 16 words at a time into
+//      ones/twos/fours/eights counters, so only one SWAR popcount is done
+//      per 16 words
+//
+// All modes give the same count; a trailing partial word is zero-padded.
+const MODE_TABLE: u8 = 0;
+const MODE_COUNT_ONES: u8 = 1;
+const MODE_HARLEY_SEAL: u8 = 2;
+
+const fn bit_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 1;
+    while i < 256 {
+        table[i] = (i & 1) as u8 + table[i / 2];
+        i += 1;
+    }
+    table
+}
+
+const BITS: [u8; 256] = bit_table();
+
+fn popcount_table(data: &[u8]) -> u64 {
+    data.iter().map(|&b| BITS[b as usize] as u64).sum()
+}
+
+fn words(data: &[u8]) -> (impl Iterator<Item = u64> + '_, u64) {
+    let chunks = data.chunks_exact(8);
+    let mut tail = [0u8; 8];
+    tail[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+    let words = chunks.map(|w| u64::from_le_bytes([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]]));
+    (words, u64::from_le_bytes(tail))
+}
+
+fn popcount_count_ones(data: &[u8]) -> u64 {
+    let (words, tail) = words(data);
+    words.map(|w| w.count_ones() as u64).sum::<u64>() + tail.count_ones() as u64
+}
+
+fn swar(mut x: u64) -> u64 {
+    x -= (x >> 1) & 0x5555_5555_5555_5555;
+    x = (x & 0x3333_3333_3333_3333) + ((x >> 2) & 0x3333_3333_3333_3333);
+    x = (x + (x >> 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x.wrapping_mul(0x0101_0101_0101_0101) >> 56
+}
+
+/// Carry-save adder: adds three bit vectors, returning (carries, sums).
+#[inline(always)]
+fn csa(a: u64, b: u64, c: u64) -> (u64, u64) {
+    let u = a ^ b;
+    ((a & b) | (u & c), u ^ c)
+}
+
+fn popcount_harley_seal(data: &[u8]) -> u64 {
+    let (mut words, tail) = words(data);
+    let mut block = [0u64; 16];
+    let (mut ones, mut twos, mut fours, mut eights) = (0u64, 0u64, 0u64, 0u64);
+    let mut total = 0u64;
+    let rest: u64 = loop {
+        let mut n = 0;
+        for (slot, w) in block.iter_mut().zip(&mut words) {
+            *slot = w;
+            n += 1;
+        }
+        if n < 16 {
+            break block[..n].iter().map(|&w| swar(w)).sum();
+        }
+
+        let d = &block;
+        let (twos_a, o) = csa(ones, d[0], d[1]);
+        let (twos_b, o) = csa(o, d[2], d[3]);
+        let (fours_a, t) = csa(twos, twos_a, twos_b);
+        let (twos_a, o) = csa(o, d[4], d[5]);
+        let (twos_b, o) = csa(o, d[6], d[7]);
+        let (fours_b, t) = csa(t, twos_a, twos_b);
+        let (eights_a, f) = csa(fours, fours_a, fours_b);
+        let (twos_a, o) = csa(o, d[8], d[9]);
+        let (twos_b, o) = csa(o, d[10], d[11]);
+        let (fours_a, t) = csa(t, twos_a, twos_b);
+        let (twos_a, o) = csa(o, d[12], d[13]);
+        let (twos_b, o) = csa(o, d[14], d[15]);
+        let (fours_b, t) = csa(t, twos_a, twos_b);
+        let (eights_b, f) = csa(f, fours_a, fours_b);
+        let (sixteens, e) = csa(eights, eights_a, eights_b);
+        total += swar(sixteens);
+        (ones, twos, fours, eights) = (o, t, f, e);
+    };
+
+    16 * total + 8 * swar(eights) + 4 * swar(fours) + 2 * swar(twos) + swar(ones)
+        + rest
+        + swar(tail)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let count = match input.split_first() {
+        None => 0,
+        Some((&MODE_TABLE, data)) => popcount_table(data),
+        Some((&MODE_COUNT_ONES, data)) => popcount_count_ones(data),
+        Some((&MODE_HARLEY_SEAL, data)) => popcount_harley_seal(data),
+        Some((&mode, _)) => {
+            eprintln!("malformed input: unknown mode {mode}");
+            std::process::exit(2);
+        }
+    };
+
+    std::io::stdout().write_all(&count.to_le_bytes()).unwrap();
+}
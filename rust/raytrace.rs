@@ -0,0 +1,234 @@
+use std::io::{Read, Write};
+use std::ops::{Add, Sub};
+
+// Input: the image size N as u32 LE. Output: the N x N image of a fixed
+// scene, row-major from the top left, 3 bytes (R, G, B) per pixel.
+//
+// The renderer uses only +, -, *, /, sqrt and floor on f64, so another
+// implementation doing the same operations in the same order gives the same
+// bytes; the order is the one in the code below (a dot product sums x, y, z
+// left to right, and normalizing divides each component by the length).
+// benchmarks.toml's diff_byte_tolerance covers implementations that contract
+// or reorder some of them.
+//
+// Camera: a pinhole at (0, 1, -3). Pixel (x, y) gets the ray of direction
+// normalize(2(x + 0.5)/N - 1, 1 - 2(y + 0.5)/N, 1.5).
+//
+// Scene, each object with a colour and a reflectivity r:
+//
+// - the plane y = 0, a checkerboard of unit squares: (0.8, 0.8, 0.8) where
+//   floor(x) + floor(z) is even, else (0.3, 0.3, 0.3); r = 0.25
+// - a sphere at (0, 1, 3), radius 1, colour (0.9, 0.2, 0.2), r = 0.3
+// - a sphere at (-2.2, 0.7, 4), radius 0.7, colour (0.2, 0.9, 0.3), r = 0.2
+// - a sphere at (1.8, 0.6, 1.8), radius 0.6, colour (0.9, 0.9, 0.9), r = 0.6
+// - a white point light at (-3, 5, -1); no attenuation
+//
+// A ray (o, d) hits the plane at t = -o.y / d.y (when d.y != 0) and a
+// sphere (c, radius) at t = -b - sqrt(b^2 - q), or -b + sqrt(b^2 - q) if the
+// first is not past EPS, where b = (o - c).d and q = (o - c).(o - c) -
+// radius^2. Hits need t > EPS = 1e-9; the nearest wins, and on an exact tie
+// the first in the list above. A miss is the sky: (1 - s)(1, 1, 1) +
+// s(0.5, 0.7, 1.0) with s = 0.5(d.y + 1).
+//
+// At a hit P with normal n (the plane's is (0, 1, 0); a sphere's is
+// (P - c) / radius) and colour C, the light direction is l = normalize(light
+// - P). The point is lit unless a ray from P + 1e-4 n towards l hits an
+// object closer than the light. Then, with k = n.l:
+//
+// - diffuse = k if lit and k > 0, else 0
+// - specular = (max(0, (2k n - l).(-d)))^32 * 0.5 if lit and k > 0, else 0,
+//   the power by five squarings
+// - local = C * (0.1 + diffuse) + (specular, specular, specular)
+// - colour = (1 - r) local + r trace(P + 1e-4 n, d - 2(d.n) n), for rays at
+//   depth 0 to 2 (the camera ray is depth 0); at depth 3, colour = local
+//
+// Each channel is clamped to [0, 1] and stored as floor(c * 255 + 0.5).
+// The red sphere dominates the middle of the image, reflecting the
+// checkerboard, with the shadows falling back and to the right.
+const EPS: f64 = 1e-9;
+const BIAS: f64 = 1e-4;
+const MAX_DEPTH: u32 = 3;
+const AMBIENT: f64 = 0.1;
+const LIGHT: Vec3 = Vec3(-3.0, 5.0, -1.0);
+const CAMERA: Vec3 = Vec3(0.0, 1.0, -3.0);
+
+#[derive(Clone, Copy)]
+struct Vec3(f64, f64, f64);
+
+impl Vec3 {
+    fn dot(self, o: Vec3) -> f64 {
+        self.0 * o.0 + self.1 * o.1 + self.2 * o.2
+    }
+
+    fn normalize(self) -> Vec3 {
+        let len = self.dot(self).sqrt();
+        Vec3(self.0 / len, self.1 / len, self.2 / len)
+    }
+
+    fn scale(self, s: f64) -> Vec3 {
+        Vec3(self.0 * s, self.1 * s, self.2 * s)
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+    fn add(self, o: Vec3) -> Vec3 {
+        Vec3(self.0 + o.0, self.1 + o.1, self.2 + o.2)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+    fn sub(self, o: Vec3) -> Vec3 {
+        Vec3(self.0 - o.0, self.1 - o.1, self.2 - o.2)
+    }
+}
+
+enum Shape {
+    Plane,
+    Sphere { centre: Vec3, radius: f64 },
+}
+
+struct Object {
+    shape: Shape,
+    colour: Vec3,
+    reflectivity: f64,
+}
+
+const SCENE: [Object; 4] = [
+    Object { shape: Shape::Plane, colour: Vec3(0.8, 0.8, 0.8), reflectivity: 0.25 },
+    Object {
+        shape: Shape::Sphere { centre: Vec3(0.0, 1.0, 3.0), radius: 1.0 },
+        colour: Vec3(0.9, 0.2, 0.2),
+        reflectivity: 0.3,
+    },
+    Object {
+        shape: Shape::Sphere { centre: Vec3(-2.2, 0.7, 4.0), radius: 0.7 },
+        colour: Vec3(0.2, 0.9, 0.3),
+        reflectivity: 0.2,
+    },
+    Object {
+        shape: Shape::Sphere { centre: Vec3(1.8, 0.6, 1.8), radius: 0.6 },
+        colour: Vec3(0.9, 0.9, 0.9),
+        reflectivity: 0.6,
+    },
+];
+
+fn intersect(shape: &Shape, o: Vec3, d: Vec3) -> Option<f64> {
+    let t = match *shape {
+        Shape::Plane => {
+            if d.1 == 0.0 {
+                return None;
+            }
+            -o.1 / d.1
+        }
+        Shape::Sphere { centre, radius } => {
+            let oc = o - centre;
+            let b = oc.dot(d);
+            let q = oc.dot(oc) - radius * radius;
+            let disc = b * b - q;
+            if disc < 0.0 {
+                return None;
+            }
+            let s = disc.sqrt();
+            if -b - s > EPS {
+                -b - s
+            } else {
+                -b + s
+            }
+        }
+    };
+    if t > EPS {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+fn nearest(o: Vec3, d: Vec3) -> Option<(f64, &'static Object)> {
+    let mut best: Option<(f64, &Object)> = None;
+    for object in &SCENE {
+        if let Some(t) = intersect(&object.shape, o, d) {
+            if best.is_none_or(|(bt, _)| t < bt) {
+                best = Some((t, object));
+            }
+        }
+    }
+    best
+}
+
+fn trace(o: Vec3, d: Vec3, depth: u32) -> Vec3 {
+    let (t, object) = match nearest(o, d) {
+        Some(hit) => hit,
+        None => {
+            let s = 0.5 * (d.1 + 1.0);
+            return Vec3(1.0, 1.0, 1.0).scale(1.0 - s) + Vec3(0.5, 0.7, 1.0).scale(s);
+        }
+    };
+    let p = o + d.scale(t);
+    let (n, colour) = match object.shape {
+        Shape::Plane => {
+            let even = (p.0.floor() + p.2.floor()) as i64 % 2 == 0;
+            (Vec3(0.0, 1.0, 0.0), if even { object.colour } else { Vec3(0.3, 0.3, 0.3) })
+        }
+        Shape::Sphere { centre, radius } => {
+            let v = p - centre;
+            (Vec3(v.0 / radius, v.1 / radius, v.2 / radius), object.colour)
+        }
+    };
+    let above = p + n.scale(BIAS);
+
+    let to_light = LIGHT - p;
+    let l = to_light.normalize();
+    let k = n.dot(l);
+    let mut diffuse = 0.0;
+    let mut specular = 0.0;
+    if k > 0.0 {
+        let light_dist = to_light.dot(to_light).sqrt();
+        let lit = nearest(above, l).is_none_or(|(st, _)| st >= light_dist);
+        if lit {
+            diffuse = k;
+            let r = n.scale(2.0 * k) - l;
+            let mut s = r.dot(Vec3(-d.0, -d.1, -d.2)).max(0.0);
+            for _ in 0..5 {
+                s *= s;
+            }
+            specular = s * 0.5;
+        }
+    }
+    let local = colour.scale(AMBIENT + diffuse) + Vec3(specular, specular, specular);
+    if depth == MAX_DEPTH {
+        return local;
+    }
+    let reflected = d - n.scale(2.0 * d.dot(n));
+    let r = object.reflectivity;
+    local.scale(1.0 - r) + trace(above, reflected, depth + 1).scale(r)
+}
+
+fn channel(c: f64) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0 + 0.5).floor() as u8
+}
+
+fn render(n: usize) -> Vec<u8> {
+    let mut image = Vec::with_capacity(n * n * 3);
+    for y in 0..n {
+        for x in 0..n {
+            let u = 2.0 * (x as f64 + 0.5) / n as f64 - 1.0;
+            let v = 1.0 - 2.0 * (y as f64 + 0.5) / n as f64;
+            let c = trace(CAMERA, Vec3(u, v, 1.5).normalize(), 0);
+            image.extend_from_slice(&[channel(c.0), channel(c.1), channel(c.2)]);
+        }
+    }
+    image
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.len() < 4 {
+        eprintln!("malformed input: need 4 bytes, got {}", input.len());
+        std::process::exit(2);
+    }
+    let n = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
+    std::io::stdout().write_all(&render(n)).unwrap();
+}
@@ -0,0 +1,47 @@
+// The 12-byte record stream read by the map benchmarks (hashmap_bench,
+// hashmap_fx, btree_bench): a u64 LE key, then a u32 LE op. Each benchmark
+// decides which ops it accepts; only the framing is checked here.
+
+pub enum Op {
+    /// 0: the key's value goes up by one, from 0 if absent.
+    Insert,
+    /// 1: a present key's value is added to the lookup sum.
+    Lookup,
+    /// 2: the values of the keys in [key, key + 1000) are added to the range
+    /// sum; btree_bench only.
+    RangeSum,
+}
+
+/// The records of `input` as (key, op), or an error for a trailing partial
+/// record. Each item is an error instead if its op is not 0, 1 or 2.
+pub fn parse(input: &[u8]) -> Result<impl Iterator<Item = Result<(u64, Op), String>> + '_, String> {
+    if !input.len().is_multiple_of(12) {
+        return Err(format!("{} bytes is not a whole number of 12-byte records", input.len()));
+    }
+    Ok(input.chunks_exact(12).enumerate().map(|(i, r)| {
+        let key = u64::from_le_bytes([r[0], r[1], r[2], r[3], r[4], r[5], r[6], r[7]]);
+        let op = match u32::from_le_bytes([r[8], r[9], r[10], r[11]]) {
+            0 => Op::Insert,
+            1 => Op::Lookup,
+            2 => Op::RangeSum,
+            op => return Err(format!("unknown op {op} in record {i}")),
+        };
+        Ok((key, op))
+    }))
+}
+
+/// Writes the benchmark's result, or exits with status 2 for malformed input.
+pub fn finish(result: Result<Vec<u64>, String>) {
+    use std::io::Write;
+
+    match result {
+        Ok(values) => {
+            let output: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
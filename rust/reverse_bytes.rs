@@ -0,0 +1,12 @@
+use std::io::{Read, Write};
+
+// Output: stdin with its bytes in reverse order, reversed in place in the
+// one buffer it was read into. Any bytes are fine; empty input gives empty
+// output. On ASCII the output is reverse_utf8's; on other UTF-8 it is not,
+// since each multi-byte character's bytes come out backwards too.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    input.reverse();
+    std::io::stdout().write_all(&input).unwrap();
+}
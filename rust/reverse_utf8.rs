@@ -0,0 +1,36 @@
+use std::io::{Read, Write};
+
+// Output: stdin, which must be UTF-8, with its Unicode scalar values in
+// reverse order, each still encoded front to back. Invalid UTF-8 exits with
+// status 2; empty input gives empty output.
+//
+// After validating, the input is walked backwards from the end: each step
+// goes back over continuation bytes (0b10xxxxxx) to the lead byte of the
+// previous character and copies that character's bytes out. No Vec<char>
+// is built.
+//
+// Only scalar values are reversed, not grapheme clusters: a combining mark
+// ends up before the base it followed, so "e\u{301}x" ("éx" with U+0301
+// COMBINING ACUTE ACCENT) gives "x\u{301}e". A 4-byte character such as
+// U+1F600 stays whole: "a😀b" gives "b😀a". On ASCII the output is
+// reverse_bytes'.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if let Err(e) = std::str::from_utf8(&input) {
+        eprintln!("malformed input: {e}");
+        std::process::exit(2);
+    }
+
+    let mut output = Vec::with_capacity(input.len());
+    let mut end = input.len();
+    while end > 0 {
+        let mut start = end - 1;
+        while input[start] & 0xC0 == 0x80 {
+            start -= 1;
+        }
+        output.extend_from_slice(&input[start..end]);
+        end = start;
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,45 @@
+use std::io::{Read, Write};
+
+// Decodes the PackBits-style stream written by rle2_encode (the packet
+// format is described there). Control byte 128 is accepted as a no-op. A
+// packet cut off by the end of input exits with status 2 and writes
+// nothing.
+fn decode(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(input.len() * 2);
+    let mut i = 0;
+    while i < input.len() {
+        let n = input[i] as usize;
+        i += 1;
+        match n {
+            0..=127 => {
+                let Some(literal) = input.get(i..i + n + 1) else {
+                    return Err(format!("truncated literal packet at byte {}", i - 1));
+                };
+                output.extend_from_slice(literal);
+                i += n + 1;
+            }
+            128 => {}
+            _ => {
+                let Some(&b) = input.get(i) else {
+                    return Err(format!("truncated repeat packet at byte {}", i - 1));
+                };
+                output.resize(output.len() + 257 - n, b);
+                i += 1;
+            }
+        }
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match decode(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
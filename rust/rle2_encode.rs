@@ -0,0 +1,62 @@
+use std::io::{Read, Write};
+
+// PackBits-style RLE. The stream is a sequence of packets, each a control
+// byte n followed by its data:
+//
+//   n in 0..=127    n + 1 literal bytes follow
+//   n = 128         no-op, no data (never emitted here)
+//   n in 129..=255  one byte follows, repeated 257 - n times (2..=128)
+//
+// The encoder is greedy and fully determined, so any implementation of it
+// must produce these exact bytes. At each position, let r be the length of
+// the run of equal bytes starting there:
+//
+// 1. If r >= 3, emit a repeat packet of min(r, 128) bytes.
+// 2. Otherwise emit a literal packet that extends up to, not including, the
+//    next position where a run of 3 or more starts, capped at 128 bytes.
+//
+// So runs of 2 are always part of a literal, a run longer than 128 is split
+// into repeat packets of 128 with the remainder handled from rule 1, and the
+// output is never longer than n + ceil(n / 128) bytes. Apple's TN1023
+// example encodes as documented there:
+//
+//   AA AA AA 80 00 2A AA AA AA AA 80 00 2A 22 AA AA AA AA AA AA AA AA AA AA
+//   -> FE AA 02 80 00 2A FD AA 03 80 00 2A 22 F7 AA
+const MAX_PACKET: usize = 128;
+const MIN_REPEAT: usize = 3;
+
+/// Length of the run of `data[0]` at the start of `data`, up to `max`.
+fn run_len(data: &[u8], max: usize) -> usize {
+    let x = data[0];
+    data.iter().take(max).take_while(|&&b| b == x).count()
+}
+
+fn encode(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len() + input.len().div_ceil(MAX_PACKET));
+    let mut i = 0;
+    while i < input.len() {
+        let r = run_len(&input[i..], MAX_PACKET);
+        if r >= MIN_REPEAT {
+            output.push((257 - r) as u8);
+            output.push(input[i]);
+            i += r;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < input.len() && i - start < MAX_PACKET && run_len(&input[i..], MIN_REPEAT) < MIN_REPEAT {
+            i += 1;
+        }
+        output.push((i - start - 1) as u8);
+        output.extend_from_slice(&input[start..i]);
+    }
+    output
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    std::io::stdout().write_all(&encode(&input)).unwrap();
+}
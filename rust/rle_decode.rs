@@ -0,0 +1,20 @@
+use std::io::{Read, Write};
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Input is the rle_encode stream: (count, byte) pairs. A trailing odd
+    // byte is a truncated pair and is ignored.
+    let pairs = input.chunks_exact(2);
+    let total: usize = pairs.clone().map(|p| p[0] as usize).sum();
+
+    let mut output = Vec::with_capacity(total);
+    for p in pairs {
+        let cnt = p[0] as usize;
+        let b = p[1];
+        output.resize(output.len() + cnt, b);
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
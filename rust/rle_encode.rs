@@ -1,36 +1,64 @@
 use std::io::{Read, Write};
 
+const CHUNK: usize = 256 * 1024;
+
 fn main() {
-    let mut input = Vec::new();
-    std::io::stdin().read_to_end(&mut input).unwrap();
+    let mut stdin = std::io::stdin().lock();
+    let mut stdout = std::io::stdout().lock();
+    let mut buf = vec![0u8; CHUNK];
+    // Grown on demand and reused across chunks; compressible input never
+    // needs the 2x worst case.
+    let mut output = Vec::new();
 
-    if input.is_empty() {
-        return;
-    }
+    // The run in progress (byte, length 1..=255), carried across chunks so a
+    // run that spans a chunk boundary is emitted as one pair. None until the
+    // first byte.
+    let mut run: Option<(u8, usize)> = None;
 
-    let mut output = Vec::with_capacity(input.len() * 2);
-    let mut cur = input[0];
-    let mut cnt: u8 = 1;
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
 
-    for &x in &input[1..] {
-        if x == cur {
-            if cnt < 255 {
-                cnt += 1;
-            } else {
-                output.push(cnt);
-                output.push(cur);
-                cnt = 1;
+        output.clear();
+        let mut rest = &buf[..n];
+        while let Some(&x) = rest.first() {
+            let mut len = run_len(rest, x);
+            rest = &rest[len..];
+            match run {
+                Some((cur, cnt)) if cur == x => len += cnt,
+                Some((cur, cnt)) => output.extend_from_slice(&[cnt as u8, cur]),
+                None => {}
+            }
+            // Full 255-byte pairs; the remainder stays open in case the run
+            // continues into the next chunk.
+            while len > 255 {
+                output.extend_from_slice(&[255, x]);
+                len -= 255;
             }
-        } else {
-            output.push(cnt);
-            output.push(cur);
-            cur = x;
-            cnt = 1;
+            run = Some((x, len));
         }
+        stdout.write_all(&output).unwrap();
     }
 
-    output.push(cnt);
-    output.push(cur);
+    if let Some((cur, cnt)) = run {
+        stdout.write_all(&[cnt as u8, cur]).unwrap();
+    }
+}
 
-    std::io::stdout().write_all(&output).unwrap();
+/// Length of the run of `x` that starts `data`, compared eight bytes at a
+/// time.
+fn run_len(data: &[u8], x: u8) -> usize {
+    let pattern = u64::from_ne_bytes([x; 8]);
+    let mut i = 0;
+    while i + 8 <= data.len() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&data[i..i + 8]);
+        if u64::from_ne_bytes(word) != pattern {
+            break;
+        }
+        i += 8;
+    }
+    i + data[i..].iter().position(|&b| b != x).unwrap_or(data.len() - i)
 }
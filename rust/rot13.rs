@@ -0,0 +1,85 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then (mode 1 only) a 256-byte table, then the data.
+// Output: the data with each byte substituted:
+//
+//   0  rot13: ASCII letters rotated 13 places within their case, tested by
+//      range like to_upper's branchy mode; every other byte unchanged
+//   1  table: byte b becomes table[b], what tr(1) does; one load per byte
+//      and nothing to branch on, whatever the data
+//
+// The data is streamed a chunk at a time through one buffer, translated in
+// place, so memory stays flat at any size. Empty data gives empty output;
+// an unknown or missing mode byte, or mode 1 with fewer than 256 table
+// bytes, exits with status 2.
+//
+// rot13 is its own inverse: "Hello, World!" gives "Uryyb, Jbeyq!" and back.
+// An identity table (table[b] = b) copies the data unchanged.
+const MODE_ROT13: u8 = 0;
+const MODE_TABLE: u8 = 1;
+const CHUNK: usize = 256 * 1024;
+
+fn rot13(data: &mut [u8]) {
+    for b in data {
+        match *b {
+            b'a'..=b'm' | b'A'..=b'M' => *b += 13,
+            b'n'..=b'z' | b'N'..=b'Z' => *b -= 13,
+            _ => {}
+        }
+    }
+}
+
+/// Fills buf from r, short only at EOF; returns the bytes read.
+fn read_full(r: &mut impl Read, buf: &mut [u8]) -> usize {
+    let mut got = 0;
+    while got < buf.len() {
+        let n = r.read(&mut buf[got..]).unwrap();
+        if n == 0 {
+            break;
+        }
+        got += n;
+    }
+    got
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("malformed input: {msg}");
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut stdin = std::io::stdin().lock();
+    let mut mode = [0u8];
+    if read_full(&mut stdin, &mut mode) == 0 {
+        fail("missing mode byte");
+    }
+    let mut table = [0u8; 256];
+    match mode[0] {
+        MODE_ROT13 => {}
+        MODE_TABLE => {
+            let got = read_full(&mut stdin, &mut table);
+            if got < table.len() {
+                fail(&format!("truncated table: need 256 bytes after the mode, got {got}"));
+            }
+        }
+        mode => fail(&format!("unknown mode {mode}")),
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    let mut buf = vec![0u8; CHUNK];
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        let chunk = &mut buf[..n];
+        if mode[0] == MODE_ROT13 {
+            rot13(chunk);
+        } else {
+            for b in chunk.iter_mut() {
+                *b = table[*b as usize];
+            }
+        }
+        stdout.write_all(chunk).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::{Read, Write};
+
+// Input: a limit N as u64 LE. Output: the number of primes <= N, then the
+// largest prime <= N, each as u64 LE (both 0 when N < 2). A short input, or
+// N over MAX_LIMIT, exits with status 2.
+//
+// Bit-packed sieve of Eratosthenes over the odd numbers only: bit i of the
+// sieve stands for 2i + 1, so N = 10^9 needs about 60 MiB.
+const EXIT_MALFORMED: i32 = 2;
+// 2^40 already needs a 64 GiB sieve; the bound keeps p * p and the bit
+// indices well inside a u64.
+const MAX_LIMIT: u64 = 1 << 40;
+
+fn read_limit() -> u64 {
+    let mut buf = [0u8; 8];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    let limit = u64::from_le_bytes(buf);
+    if limit > MAX_LIMIT {
+        eprintln!("malformed input: limit {limit} is over {MAX_LIMIT}");
+        std::process::exit(EXIT_MALFORMED);
+    }
+    limit
+}
+
+fn sieve(limit: u64) -> (u64, u64) {
+    if limit < 2 {
+        return (0, 0);
+    }
+    if limit < 3 {
+        return (1, 2);
+    }
+
+    // Odd numbers 1, 3, ..., up to limit; a set bit means composite (or 1).
+    let odds = limit.div_ceil(2) as usize;
+    let mut composite = vec![0u64; odds.div_ceil(64)];
+    composite[0] |= 1;
+
+    let mut i = 1;
+    loop {
+        let p = 2 * i + 1;
+        if p > limit as usize / p {
+            break;
+        }
+        if composite[i / 64] & (1 << (i % 64)) == 0 {
+            // p*p is the first multiple not already crossed off; step 2p
+            // skips the even multiples.
+            let mut j = p * p / 2;
+            while j < odds {
+                composite[j / 64] |= 1 << (j % 64);
+                j += p;
+            }
+        }
+        i += 1;
+    }
+
+    // Bits past the last odd number are not part of the sieve.
+    let tail = odds % 64;
+    if tail != 0 {
+        *composite.last_mut().unwrap() |= !0u64 << tail;
+    }
+
+    let count = 1 + composite.iter().map(|w| w.count_zeros() as u64).sum::<u64>();
+    let (word, bits) = composite.iter().enumerate().rev().find(|(_, &w)| w != !0).unwrap();
+    let top = 63 - (!bits).leading_zeros() as usize;
+    let largest = 2 * (word * 64 + top) as u64 + 1;
+    (count, largest)
+}
+
+fn main() {
+    let (count, largest) = sieve(read_limit());
+
+    let mut output = [0u8; 16];
+    output[..8].copy_from_slice(&count.to_le_bytes());
+    output[8..].copy_from_slice(&largest.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,60 @@
+use std::io::{Read, Write};
+
+// Input: offset and length as u64 LE, then the payload. Output:
+// payload[offset..offset + length], cut short at the end of the payload:
+// an offset at or past the end gives no output, and a length running past
+// it gives the bytes up to the end. Neither is an error; only a header
+// shorter than 16 bytes exits with status 2.
+//
+// The payload is never held in memory: the first offset bytes are read and
+// thrown away a chunk at a time, then length bytes are copied through the
+// same fixed buffer, so memory stays at one chunk for any input size.
+//
+// Offset 2, length 3 of "abcdef" gives "cde"; offset 4, length 10 gives
+// "ef"; offset 6 or more, or length 0, gives nothing.
+const CHUNK: usize = 256 * 1024;
+
+fn main() {
+    let mut stdin = std::io::stdin().lock();
+    let mut header = [0u8; 16];
+    let mut got = 0;
+    while got < header.len() {
+        let n = stdin.read(&mut header[got..]).unwrap();
+        if n == 0 {
+            eprintln!("malformed input: truncated header: need 16 bytes, got {got}");
+            std::process::exit(2);
+        }
+        got += n;
+    }
+    let offset = u64::from_le_bytes([
+        header[0], header[1], header[2], header[3], header[4], header[5], header[6], header[7],
+    ]);
+    let length = u64::from_le_bytes([
+        header[8], header[9], header[10], header[11], header[12], header[13], header[14],
+        header[15],
+    ]);
+
+    let mut stdout = std::io::stdout().lock();
+    let mut buf = vec![0u8; CHUNK];
+
+    let mut to_skip = offset;
+    while to_skip > 0 {
+        let want = to_skip.min(CHUNK as u64) as usize;
+        let n = stdin.read(&mut buf[..want]).unwrap();
+        if n == 0 {
+            return;
+        }
+        to_skip -= n as u64;
+    }
+
+    let mut to_copy = length;
+    while to_copy > 0 {
+        let want = to_copy.min(CHUNK as u64) as usize;
+        let n = stdin.read(&mut buf[..want]).unwrap();
+        if n == 0 {
+            break;
+        }
+        stdout.write_all(&buf[..n]).unwrap();
+        to_copy -= n as u64;
+    }
+}
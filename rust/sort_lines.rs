@@ -0,0 +1,32 @@
+use std::io::{Read, Write};
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    if input.is_empty() {
+        return;
+    }
+
+    // Lines are split on '\n' and compared as raw bytes, so invalid UTF-8 is
+    // fine. A final '\n' terminates the last line rather than starting an
+    // empty one, and is kept on output.
+    let trailing_newline = input.last() == Some(&b'\n');
+    let body = if trailing_newline { &input[..input.len() - 1] } else { &input[..] };
+
+    let mut lines: Vec<&[u8]> = body.split(|&b| b == b'\n').collect();
+    lines.sort_unstable();
+
+    let mut output = Vec::with_capacity(input.len());
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            output.push(b'\n');
+        }
+        output.extend_from_slice(line);
+    }
+    if trailing_newline {
+        output.push(b'\n');
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,21 @@
+use std::io::{Read, Write};
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Input is packed u32 LE words; a trailing partial word is ignored.
+    let mut values: Vec<u32> = input
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .collect();
+
+    values.sort_unstable();
+
+    let mut output = Vec::with_capacity(values.len() * 4);
+    for v in &values {
+        output.extend_from_slice(&v.to_le_bytes());
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
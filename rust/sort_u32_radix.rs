@@ -0,0 +1,49 @@
+use std::io::{Read, Write};
+
+// LSD radix sort, one pass per byte. Same input and output as sort_u32.
+fn radix_sort(values: &mut Vec<u32>) {
+    let mut scratch = vec![0u32; values.len()];
+    for shift in (0..32).step_by(8) {
+        let mut counts = [0usize; 256];
+        for &v in values.iter() {
+            counts[((v >> shift) & 0xFF) as usize] += 1;
+        }
+        // Every value has the same byte here, so this pass is a no-op.
+        if counts.contains(&values.len()) {
+            continue;
+        }
+
+        let mut offsets = [0usize; 256];
+        let mut total = 0;
+        for (off, &c) in offsets.iter_mut().zip(&counts) {
+            *off = total;
+            total += c;
+        }
+        for &v in values.iter() {
+            let bucket = ((v >> shift) & 0xFF) as usize;
+            scratch[offsets[bucket]] = v;
+            offsets[bucket] += 1;
+        }
+        std::mem::swap(values, &mut scratch);
+    }
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Input is packed u32 LE words; a trailing partial word is ignored.
+    let mut values: Vec<u32> = input
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .collect();
+
+    radix_sort(&mut values);
+
+    let mut output = Vec::with_capacity(values.len() * 4);
+    for v in &values {
+        output.extend_from_slice(&v.to_le_bytes());
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
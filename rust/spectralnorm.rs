@@ -0,0 +1,63 @@
+use std::io::{Read, Write};
+
+// The Benchmarks Game spectral-norm workload. Input: n as u32 LE. Output:
+// the approximation as f64 LE bytes (to_bits). A short input exits with
+// status 2.
+//
+// A is the infinite matrix with, for 0-based i and j,
+//     A(i, j) = 1 / ((i + j) * (i + j + 1) / 2 + i + 1)
+// (the integer part computed exactly, then converted to f64). Starting from
+// u = [1.0; n], 10 iterations each compute v = At(A(u)) and then
+// u = At(A(v)), where A(x)[i] = sum_j A(i, j) x[j] and At(x)[i] =
+// sum_j A(j, i) x[j], summed in ascending j starting from 0.0. The result is
+// sqrt(sum_i u[i] v[i] / sum_i v[i] v[i]); for n = 100 it is 1.274219991 to
+// nine places. n = 0 gives 0.0 rather than 0/0.
+const EXIT_MALFORMED: i32 = 2;
+const ITERATIONS: usize = 10;
+
+fn a(i: usize, j: usize) -> f64 {
+    1.0 / (((i + j) * (i + j + 1) / 2 + i + 1) as f64)
+}
+
+/// out = A x (or At x when `transpose`).
+fn mul(x: &[f64], out: &mut [f64], transpose: bool) {
+    for (i, o) in out.iter_mut().enumerate() {
+        let mut sum = 0.0;
+        for (j, &xj) in x.iter().enumerate() {
+            sum += if transpose { a(j, i) } else { a(i, j) } * xj;
+        }
+        *o = sum;
+    }
+}
+
+fn mul_ata(x: &[f64], tmp: &mut [f64], out: &mut [f64]) {
+    mul(x, tmp, false);
+    mul(tmp, out, true);
+}
+
+fn main() {
+    let mut buf = [0u8; 4];
+    if let Err(e) = std::io::stdin().read_exact(&mut buf) {
+        eprintln!("malformed input: truncated header: need {} bytes: {e}", buf.len());
+        std::process::exit(EXIT_MALFORMED);
+    }
+    let n = u32::from_le_bytes(buf) as usize;
+
+    let mut u = vec![1.0f64; n];
+    let mut v = vec![0.0f64; n];
+    let mut tmp = vec![0.0f64; n];
+    for _ in 0..ITERATIONS {
+        mul_ata(&u, &mut tmp, &mut v);
+        mul_ata(&v, &mut tmp, &mut u);
+    }
+
+    let mut vbv = 0.0;
+    let mut vv = 0.0;
+    for i in 0..n {
+        vbv += u[i] * v[i];
+        vv += v[i] * v[i];
+    }
+    let result = if n == 0 { 0.0 } else { (vbv / vv).sqrt() };
+
+    std::io::stdout().write_all(&result.to_bits().to_le_bytes()).unwrap();
+}
@@ -0,0 +1,26 @@
+use std::io::{Read, Write};
+
+mod suffix_sort;
+
+// Output: the suffix array of stdin, raw bytes compared as unsigned: the
+// start of every suffix, in ascending order of the suffixes, as packed u32
+// LE. A suffix sorts before every longer suffix it is a prefix of. Input of
+// 4 GiB or more exits with status 2; empty input gives no output.
+//
+// "banana" gives 5, 3, 1, 0, 4, 2 ("a", "ana", "anana", "banana", "na",
+// "nana").
+//
+// bwt sorts the same way (rust/suffix_sort), adding a sentinel that sorts
+// first; here it is dropped from the front of the array.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.len() >= u32::MAX as usize {
+        eprintln!("malformed input: {} bytes, must be under 4 GiB", input.len());
+        std::process::exit(2);
+    }
+
+    let sa = suffix_sort::suffix_array(&input);
+    let output: Vec<u8> = sa[1..].iter().flat_map(|i| i.to_le_bytes()).collect();
+    std::io::stdout().write_all(&output).unwrap();
+}
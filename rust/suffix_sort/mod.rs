@@ -0,0 +1,66 @@
+// Suffix sorting shared by bwt and suffix_array: prefix doubling with two
+// counting-sort passes per round, O(n log n) even on highly repetitive
+// input, stopping as soon as every rank is distinct.
+
+/// The suffix array of `s` followed by a virtual sentinel that sorts before
+/// every byte value: `s.len() + 1` indexes, the first always `s.len()` (the
+/// sentinel alone), then the suffixes of `s` in order, a proper prefix before
+/// any longer suffix it starts. `s` must be shorter than `u32::MAX`.
+pub fn suffix_array(s: &[u8]) -> Vec<u32> {
+    // Position n is the sentinel, rank 0; byte b has rank b + 1.
+    let n = s.len() + 1;
+    let mut rank: Vec<u32> = s.iter().map(|&b| b as u32 + 1).chain([0]).collect();
+    let mut sa: Vec<u32> = (0..n as u32).collect();
+    sa.sort_unstable_by_key(|&i| rank[i as usize]);
+
+    let mut tmp = vec![0u32; n];
+    let mut count = vec![0u32; n.max(257)];
+    let mut k = 1;
+    loop {
+        let key2 = |i: usize, rank: &[u32]| if i + k < n { rank[i + k] } else { 0 };
+
+        // Order by the second key: suffixes with nothing k ahead first, then
+        // the rest in the order of the suffix k ahead of them.
+        let mut j = 0;
+        for i in n - k.min(n)..n {
+            tmp[j] = i as u32;
+            j += 1;
+        }
+        for &p in &sa {
+            if p as usize >= k {
+                tmp[j] = p - k as u32;
+                j += 1;
+            }
+        }
+
+        // Stable counting sort by the first key.
+        let max_rank = *rank.iter().max().unwrap() as usize;
+        count[..=max_rank].fill(0);
+        for &r in &rank {
+            count[r as usize] += 1;
+        }
+        let mut sum = 0;
+        for c in &mut count[..=max_rank] {
+            let here = *c;
+            *c = sum;
+            sum += here;
+        }
+        for &p in &tmp {
+            let r = &mut count[rank[p as usize] as usize];
+            sa[*r as usize] = p;
+            *r += 1;
+        }
+
+        tmp[sa[0] as usize] = 0;
+        for j in 1..n {
+            let (a, b) = (sa[j - 1] as usize, sa[j] as usize);
+            let same = rank[a] == rank[b] && key2(a, &rank) == key2(b, &rank);
+            tmp[b] = tmp[a] + !same as u32;
+        }
+        std::mem::swap(&mut rank, &mut tmp);
+        if rank[sa[n - 1] as usize] as usize == n - 1 {
+            return sa;
+        }
+        k *= 2;
+    }
+}
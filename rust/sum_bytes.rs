@@ -1,10 +1,35 @@
 use std::io::{Read, Write};
 
+// Bytes per iteration of the hot loop, one u64 accumulator lane each.
+const LANES: usize = 16;
+const CHUNK: usize = 256 * 1024;
+
 fn main() {
-    let mut input = Vec::new();
-    std::io::stdin().read_to_end(&mut input).unwrap();
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = vec![0u8; CHUNK];
+
+    // The sum is a u64 that wraps modulo 2^64. That takes more than 2^56
+    // bytes of input, so in practice it is exact.
+    let mut lanes = [0u64; LANES];
+    let mut acc = 0u64;
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        let chunks = buf[..n].chunks_exact(LANES);
+        let tail = chunks.remainder();
+        for chunk in chunks {
+            for (lane, &b) in lanes.iter_mut().zip(chunk) {
+                *lane = lane.wrapping_add(b as u64);
+            }
+        }
+        for &b in tail {
+            acc = acc.wrapping_add(b as u64);
+        }
+    }
 
-    let acc: u32 = input.iter().map(|&b| b as u32).sum();
+    let acc = lanes.iter().fold(acc, |a, &l| a.wrapping_add(l));
 
     std::io::stdout().write_all(&acc.to_le_bytes()).unwrap();
 }
@@ -0,0 +1,23 @@
+use std::io::{Read, Write};
+
+// sum_bytes with the single-accumulator loop its 16 lanes replaced, kept to
+// measure what the lanes buy. Same output, wrapping modulo 2^64.
+const CHUNK: usize = 256 * 1024;
+
+fn main() {
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = vec![0u8; CHUNK];
+
+    let mut acc = 0u64;
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        for &b in &buf[..n] {
+            acc = acc.wrapping_add(b as u64);
+        }
+    }
+
+    std::io::stdout().write_all(&acc.to_le_bytes()).unwrap();
+}
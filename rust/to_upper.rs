@@ -0,0 +1,63 @@
+use std::io::{Read, Write};
+
+// Input: a mode byte, then the data. Output: the data with ASCII 'a'..='z'
+// uppercased and every other byte unchanged. Empty input gives empty
+// output; an unknown mode exits with status 2.
+//
+//   0  branchy: test each byte and subtract 0x20 only from lowercase ones
+//   1  branchless: clear bit 5 under a mask computed from the byte's range,
+//      over fixed-size chunks so the loop vectorizes
+//
+// Both modes give identical output; the difference is only in timing, and
+// is largest on text, where the branch is taken about as often as not.
+const MODE_BRANCHY: u8 = 0;
+const MODE_BRANCHLESS: u8 = 1;
+
+const CHUNK: usize = 64;
+
+fn upper_branchy(data: &mut [u8]) {
+    for b in data {
+        if *b >= b'a' && *b <= b'z' {
+            *b -= 0x20;
+        }
+    }
+}
+
+#[inline(always)]
+fn upper_byte(b: u8) -> u8 {
+    // 0x20 when b is in 'a'..='z', else 0.
+    let lower = (b.wrapping_sub(b'a') < 26) as u8;
+    b & !(lower << 5)
+}
+
+fn upper_branchless(data: &mut [u8]) {
+    let mut chunks = data.chunks_exact_mut(CHUNK);
+    for chunk in &mut chunks {
+        for b in chunk {
+            *b = upper_byte(*b);
+        }
+    }
+    for b in chunks.into_remainder() {
+        *b = upper_byte(*b);
+    }
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.is_empty() {
+        return;
+    }
+
+    let (mode, data) = input.split_first_mut().unwrap();
+    match *mode {
+        MODE_BRANCHY => upper_branchy(data),
+        MODE_BRANCHLESS => upper_branchless(data),
+        mode => {
+            eprintln!("malformed input: unknown mode {mode}");
+            std::process::exit(2);
+        }
+    }
+
+    std::io::stdout().write_all(data).unwrap();
+}
@@ -0,0 +1,58 @@
+use std::io::{BufWriter, Read, Write};
+
+// Output: every maximal run of non-whitespace bytes in stdin, in order, as a
+// u32 LE length followed by the token's bytes, then the number of tokens as
+// a u32 LE footer. Whitespace is ASCII's (space, \t, \n, \x0c, \r, as
+// u8::is_ascii_whitespace); any other byte, non-ASCII included, is part of a
+// token. Leading, trailing and repeated whitespace produce no empty tokens,
+// and a token running to EOF is emitted like any other. Input of 4 GiB or
+// more exits with status 2.
+//
+// word_count only counts; this copies every token out, the same length-
+// prefixed framing the crate benchmarks read (rust_cargo/proto). On
+// word_count's inputs, which have no form feeds, the footer is word_count's
+// count.
+//
+// "  ab\tc \n\nd" gives 02 00 00 00 "ab" 01 00 00 00 "c" 01 00 00 00 "d"
+// 03 00 00 00; empty or all-whitespace input gives just the zero footer.
+const OUT_BUF: usize = 256 * 1024;
+
+const fn make_table() -> [bool; 256] {
+    let mut table = [false; 256];
+    table[b' ' as usize] = true;
+    table[b'\t' as usize] = true;
+    table[b'\n' as usize] = true;
+    table[0x0c] = true;
+    table[b'\r' as usize] = true;
+    table
+}
+
+const IS_SPACE: [bool; 256] = make_table();
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.len() >= u32::MAX as usize {
+        eprintln!("malformed input: {} bytes, must be under 4 GiB", input.len());
+        std::process::exit(2);
+    }
+
+    let mut out = BufWriter::with_capacity(OUT_BUF, std::io::stdout().lock());
+    let mut count: u32 = 0;
+    let mut i = 0;
+    while i < input.len() {
+        if IS_SPACE[input[i] as usize] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < input.len() && !IS_SPACE[input[i] as usize] {
+            i += 1;
+        }
+        out.write_all(&((i - start) as u32).to_le_bytes()).unwrap();
+        out.write_all(&input[start..i]).unwrap();
+        count += 1;
+    }
+    out.write_all(&count.to_le_bytes()).unwrap();
+    out.flush().unwrap();
+}
@@ -0,0 +1,94 @@
+use std::io::{Read, Write};
+
+// Input: newline-separated words, an empty line, then newline-separated
+// query prefixes (a final newline is optional; with no empty line there
+// are no queries). Output: per query, the number of inserted words starting
+// with it, as u64 LE. Words are bytes; duplicates count once per
+// occurrence, and an empty query matches every word.
+//
+// Words "car", "cart", "car", "dog" then queries "car", "ca", "cart", "d",
+// "cat", "" give 3, 3, 1, 1, 0, 4.
+//
+// Nodes live in one Vec and link by index: each has its first child and
+// next sibling (children are found by a linear scan), so a node's size does
+// not depend on the alphabet. `count` counts the words passing through it.
+const NONE: u32 = u32::MAX;
+
+struct Node {
+    byte: u8,
+    first_child: u32,
+    next_sibling: u32,
+    count: u64,
+}
+
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Trie { nodes: vec![Node { byte: 0, first_child: NONE, next_sibling: NONE, count: 0 }] }
+    }
+
+    fn child(&self, node: u32, byte: u8) -> Option<u32> {
+        let mut c = self.nodes[node as usize].first_child;
+        while c != NONE {
+            let n = &self.nodes[c as usize];
+            if n.byte == byte {
+                return Some(c);
+            }
+            c = n.next_sibling;
+        }
+        None
+    }
+
+    fn insert(&mut self, word: &[u8]) {
+        let mut node = 0;
+        self.nodes[0].count += 1;
+        for &b in word {
+            node = match self.child(node, b) {
+                Some(c) => c,
+                None => {
+                    let c = self.nodes.len() as u32;
+                    let parent = &mut self.nodes[node as usize];
+                    let next_sibling = std::mem::replace(&mut parent.first_child, c);
+                    self.nodes.push(Node { byte: b, first_child: NONE, next_sibling, count: 0 });
+                    c
+                }
+            };
+            self.nodes[node as usize].count += 1;
+        }
+    }
+
+    fn prefix_count(&self, prefix: &[u8]) -> u64 {
+        let mut node = 0;
+        for &b in prefix {
+            match self.child(node, b) {
+                Some(c) => node = c,
+                None => return 0,
+            }
+        }
+        self.nodes[node as usize].count
+    }
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    let text = input.strip_suffix(b"\n").unwrap_or(&input);
+
+    let mut lines = text.split(|&b| b == b'\n');
+    let mut trie = Trie::new();
+    for word in lines.by_ref() {
+        if word.is_empty() {
+            break;
+        }
+        trie.insert(word);
+    }
+
+    let mut output = Vec::new();
+    for query in lines {
+        output.extend_from_slice(&trie.prefix_count(query).to_le_bytes());
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
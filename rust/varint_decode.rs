@@ -0,0 +1,69 @@
+use std::io::{Read, Write};
+
+// Input: concatenated unsigned LEB128 varints, as written by varint_encode.
+// Output: the values as packed u64 LE. A varint longer than 10 bytes, a
+// 10th byte above 1 (more than 64 bits), or input ending while the
+// continuation bit is set exits with status 2 and writes nothing.
+//
+// One- and two-byte varints, the common case for small values, are decoded
+// without entering the general loop.
+const MAX_LEN: usize = 10;
+
+fn decode(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(input.len() * 8);
+    let mut i = 0;
+    while i < input.len() {
+        let b0 = input[i];
+        if b0 < 0x80 {
+            output.extend_from_slice(&(b0 as u64).to_le_bytes());
+            i += 1;
+            continue;
+        }
+        if let Some(&b1) = input.get(i + 1) {
+            if b1 < 0x80 {
+                let v = (b0 & 0x7F) as u64 | (b1 as u64) << 7;
+                output.extend_from_slice(&v.to_le_bytes());
+                i += 2;
+                continue;
+            }
+        }
+
+        let start = i;
+        let mut v = 0u64;
+        let mut shift = 0;
+        loop {
+            let Some(&b) = input.get(i) else {
+                return Err(format!("truncated varint at byte {start}"));
+            };
+            if i - start == MAX_LEN - 1 {
+                if b >= 0x80 {
+                    return Err(format!("varint at byte {start} is longer than {MAX_LEN} bytes"));
+                }
+                if b > 1 {
+                    return Err(format!("varint at byte {start} overflows 64 bits"));
+                }
+            }
+            v |= ((b & 0x7F) as u64) << shift;
+            i += 1;
+            if b < 0x80 {
+                break;
+            }
+            shift += 7;
+        }
+        output.extend_from_slice(&v.to_le_bytes());
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match decode(&input) {
+        Ok(output) => std::io::stdout().write_all(&output).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use std::io::{Read, Write};
+
+// Input: packed u64 LE values (a trailing partial word is ignored). Output:
+// each value as an unsigned LEB128 varint (protobuf style): 7 bits per
+// byte, low group first, with the high bit set on every byte but the last.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let mut output = Vec::with_capacity(input.len() / 8 * 10);
+    for w in input.chunks_exact(8) {
+        let mut v = u64::from_le_bytes([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]]);
+        while v >= 0x80 {
+            output.push(v as u8 | 0x80);
+            v >>= 7;
+        }
+        output.push(v as u8);
+    }
+
+    std::io::stdout().write_all(&output).unwrap();
+}
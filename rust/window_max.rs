@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+
+// Input: a window size W as u32 LE, then packed i64 LE values. Output: the
+// maximum of each run of W consecutive values, in order, as packed i64 LE:
+// n - W + 1 of them for n values. A W past the end of the array gives the
+// one maximum over everything, and no values give no output. W = 0 or a
+// trailing partial value exits with status 2.
+//
+// The deque holds the indices of the window's candidates, values strictly
+// decreasing from front to back: a new value first removes every candidate
+// it is at least as large as (they can never be a maximum again), and the
+// front leaves once it slides out of the window. Each index enters and
+// leaves once, so the whole pass is O(n) for any W.
+//
+// W = 2 over 1, 3, 2, 5, 4 gives 3, 3, 5, 5; W = 9 over the same gives 5.
+fn parse(input: &[u8]) -> Result<(usize, Vec<i64>), String> {
+    if input.len() < 4 {
+        return Err(format!("truncated header: need 4 bytes, got {}", input.len()));
+    }
+    let w = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
+    if w == 0 {
+        return Err("window size 0".to_string());
+    }
+    let body = &input[4..];
+    if !body.len().is_multiple_of(8) {
+        return Err(format!("{} trailing bytes after the last value", body.len() % 8));
+    }
+    let values = body
+        .chunks_exact(8)
+        .map(|b| i64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .collect();
+    Ok((w, values))
+}
+
+fn window_max(values: &[i64], w: usize) -> Vec<i64> {
+    // Past the end of the array, the one window is the whole of it.
+    let w = w.min(values.len());
+    let mut maxima = Vec::with_capacity(values.len() + 1 - w.max(1));
+    let mut deque: VecDeque<usize> = VecDeque::with_capacity(w);
+    for (i, &v) in values.iter().enumerate() {
+        while deque.back().is_some_and(|&j| values[j] <= v) {
+            deque.pop_back();
+        }
+        deque.push_back(i);
+        if deque[0] + w <= i {
+            deque.pop_front();
+        }
+        if i + 1 >= w {
+            maxima.push(values[deque[0]]);
+        }
+    }
+    maxima
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match parse(&input) {
+        Ok((w, values)) => {
+            let maxima = window_max(&values, w);
+            let output: Vec<u8> = maxima.iter().flat_map(|v| v.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
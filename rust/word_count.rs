@@ -1,19 +1,28 @@
 use std::io::{Read, Write};
 
+const CHUNK: usize = 256 * 1024;
+
 fn main() {
-    let mut input = Vec::new();
-    std::io::stdin().read_to_end(&mut input).unwrap();
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = vec![0u8; CHUNK];
 
     let mut cnt: u32 = 0;
+    // Carried across chunks so a word split by a chunk boundary counts once.
     let mut in_word = false;
 
-    for &c in &input {
-        let is_space = c == 32 || c == 10 || c == 13 || c == 9;
-        if is_space {
-            in_word = false;
-        } else if !in_word {
-            cnt += 1;
-            in_word = true;
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        for &c in &buf[..n] {
+            let is_space = c == 32 || c == 10 || c == 13 || c == 9;
+            if is_space {
+                in_word = false;
+            } else if !in_word {
+                cnt += 1;
+                in_word = true;
+            }
         }
     }
 
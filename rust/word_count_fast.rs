@@ -0,0 +1,47 @@
+use std::io::{Read, Write};
+
+// The word_count count computed branch-free: each 64-byte block is turned
+// into a bitmask of word bytes via a lookup table, and word starts are the
+// set bits whose predecessor bit is clear.
+const CHUNK: usize = 256 * 1024;
+const BLOCK: usize = 64;
+
+// 1 for bytes that belong to a word, 0 for the separators word_count uses.
+const fn make_table() -> [u8; 256] {
+    let mut table = [1u8; 256];
+    table[b' ' as usize] = 0;
+    table[b'\n' as usize] = 0;
+    table[b'\r' as usize] = 0;
+    table[b'\t' as usize] = 0;
+    table
+}
+
+const IS_WORD: [u8; 256] = make_table();
+
+fn main() {
+    let mut stdin = std::io::stdin().lock();
+    let mut buf = vec![0u8; CHUNK];
+
+    let mut cnt: u32 = 0;
+    // Whether the last byte seen was a word byte; carried across blocks and
+    // chunks so a word split by a boundary counts once.
+    let mut prev_word: u64 = 0;
+
+    loop {
+        let n = stdin.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        for block in buf[..n].chunks(BLOCK) {
+            let mut mask: u64 = 0;
+            for (i, &c) in block.iter().enumerate() {
+                mask |= (IS_WORD[c as usize] as u64) << i;
+            }
+            let starts = mask & !((mask << 1) | prev_word);
+            cnt = cnt.wrapping_add(starts.count_ones());
+            prev_word = (mask >> (block.len() - 1)) & 1;
+        }
+    }
+
+    std::io::stdout().write_all(&cnt.to_le_bytes()).unwrap();
+}
@@ -0,0 +1,30 @@
+use std::io::{Read, Write};
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Separators are chars with the Unicode White_Space property
+    // (char::is_whitespace), e.g. NBSP, VT, FF, U+2003, U+3000. Bytes that
+    // are not valid UTF-8 are never separators: each invalid sequence counts
+    // as word content, exactly like a non-whitespace char.
+    let mut cnt: u32 = 0;
+    let mut in_word = false;
+
+    for chunk in input.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c.is_whitespace() {
+                in_word = false;
+            } else if !in_word {
+                cnt += 1;
+                in_word = true;
+            }
+        }
+        if !chunk.invalid().is_empty() && !in_word {
+            cnt += 1;
+            in_word = true;
+        }
+    }
+
+    std::io::stdout().write_all(&cnt.to_le_bytes()).unwrap();
+}
@@ -0,0 +1,51 @@
+use std::io::{Read, Write};
+
+// Input: a key length as u32 LE, then the key, then the plaintext (the rest
+// of stdin). Output: the plaintext XORed with the key repeated cyclically.
+// An empty key, or a length past the end of the input, exits with status 2.
+//
+// The key is repeated out to at least REPEAT_MIN bytes (a whole number of
+// key lengths), so each chunk of text is XORed with the same slice and the
+// inner loop vectorizes even for a 1- or 3-byte key.
+const REPEAT_MIN: usize = 4096;
+
+fn split(input: &[u8]) -> Result<(&[u8], &[u8]), String> {
+    if input.len() < 4 {
+        return Err(format!("truncated header: need 4 bytes, got {}", input.len()));
+    }
+    let len = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
+    let rest = &input[4..];
+    if len > rest.len() {
+        return Err(format!("key ends at byte {}, past end of input ({})", 4 + len, input.len()));
+    }
+    if len == 0 {
+        return Err("empty key".to_string());
+    }
+    Ok(rest.split_at(len))
+}
+
+fn xor_cipher(key: &[u8], text: &[u8]) -> Vec<u8> {
+    let repeats = REPEAT_MIN.div_ceil(key.len());
+    let stream = key.repeat(repeats);
+
+    let mut output = text.to_vec();
+    for chunk in output.chunks_mut(stream.len()) {
+        for (b, k) in chunk.iter_mut().zip(&stream) {
+            *b ^= k;
+        }
+    }
+    output
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match split(&input) {
+        Ok((key, text)) => std::io::stdout().write_all(&xor_cipher(key, text)).unwrap(),
+        Err(e) => {
+            eprintln!("malformed input: {e}");
+            std::process::exit(2);
+        }
+    }
+}
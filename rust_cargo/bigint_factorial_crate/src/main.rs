@@ -0,0 +1,27 @@
+use std::io::{Read, Write};
+use num_bigint::BigUint;
+
+// bigint_factorial's input and output, with n! built by num-bigint: the same
+// one factor at a time, each a BigUint *= u32 scalar multiply.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    if input.len() < 4 {
+        eprintln!("malformed input: truncated header: need 4 bytes, got {}", input.len());
+        std::process::exit(2);
+    }
+    let n = u32::from_le_bytes([input[0], input[1], input[2], input[3]]);
+
+    let mut product = BigUint::from(1u32);
+    for k in 2..=n {
+        product *= k;
+    }
+
+    let limbs = product.to_u64_digits();
+    let mut output = Vec::with_capacity(4 + 8 * limbs.len());
+    output.extend_from_slice(&(limbs.len() as u32).to_le_bytes());
+    for limb in &limbs {
+        output.extend_from_slice(&limb.to_le_bytes());
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
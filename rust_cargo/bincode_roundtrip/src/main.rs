@@ -0,0 +1,136 @@
+use std::io::{Read, Write};
+
+use serde::Serialize;
+
+// Input: a mode byte, then records back to back until the end of the input,
+// each in this wire format (all integers little-endian):
+//
+//   id         u64
+//   timestamp  i64
+//   name       u32 byte length, then that many bytes of UTF-8
+//   tags       u32 count, then each tag as a u32 byte length and UTF-8
+//   value      f64, IEEE 754 bits
+//
+// Each record is read into an owned `Record` (a String per name and tag)
+// and written back out in bincode 1's default layout, which is the same
+// fields in the same order with u64 lengths and counts:
+//
+//   0  bincode: `bincode::serialize_into`, through serde's derive
+//   1  manual: the same bytes written field by field
+//
+// Output: the record count and the total serialized length, two u64 LE;
+// both modes give the same. An unknown or missing mode byte, a record cut
+// short, or a name or tag that is not UTF-8 exits with status 2. No records
+// gives two zeros.
+//
+// The record {id 1, timestamp -2, name "ab", tags ["x"], value 1.5} is 39
+// bytes on the wire:
+//
+//   01 00 00 00 00 00 00 00  fe ff ff ff ff ff ff ff  02 00 00 00 61 62
+//   01 00 00 00  01 00 00 00 78  00 00 00 00 00 00 f8 3f
+//
+// and 51 serialized (8 + 8 + 8 + 2 + 8 + 8 + 1 + 8 bytes), so a stream of
+// just it gives 1 and 51.
+const MODE_BINCODE: u8 = 0;
+const MODE_MANUAL: u8 = 1;
+
+#[derive(Serialize)]
+struct Record {
+    id: u64,
+    timestamp: i64,
+    name: String,
+    tags: Vec<String>,
+    value: f64,
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.input.len());
+        let end = end.ok_or_else(|| {
+            let have = self.input.len() - self.pos;
+            format!("record truncated: need {n} bytes at offset {}, have {have}", self.pos)
+        })?;
+        let bytes = &self.input[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let at = self.pos;
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| format!("string at offset {at} is not UTF-8"))
+    }
+
+    fn record(&mut self) -> Result<Record, String> {
+        let id = u64::from_le_bytes(self.array()?);
+        let timestamp = i64::from_le_bytes(self.array()?);
+        let name = self.string()?;
+        let count = u32::from_le_bytes(self.array()?);
+        // Not with_capacity(count): a corrupt count could ask for gigabytes.
+        let mut tags = Vec::new();
+        for _ in 0..count {
+            tags.push(self.string()?);
+        }
+        let value = f64::from_le_bytes(self.array()?);
+        Ok(Record { id, timestamp, name, tags, value })
+    }
+}
+
+fn write_bincode(out: &mut Vec<u8>, record: &Record) {
+    bincode::serialize_into(out, record).unwrap();
+}
+
+fn write_manual(out: &mut Vec<u8>, record: &Record) {
+    out.extend_from_slice(&record.id.to_le_bytes());
+    out.extend_from_slice(&record.timestamp.to_le_bytes());
+    out.extend_from_slice(&(record.name.len() as u64).to_le_bytes());
+    out.extend_from_slice(record.name.as_bytes());
+    out.extend_from_slice(&(record.tags.len() as u64).to_le_bytes());
+    for tag in &record.tags {
+        out.extend_from_slice(&(tag.len() as u64).to_le_bytes());
+        out.extend_from_slice(tag.as_bytes());
+    }
+    out.extend_from_slice(&record.value.to_le_bytes());
+}
+
+fn run(input: &[u8]) -> Result<[u64; 2], String> {
+    let (&mode, records) = input.split_first().ok_or("missing mode byte")?;
+    let write: fn(&mut Vec<u8>, &Record) = match mode {
+        MODE_BINCODE => write_bincode,
+        MODE_MANUAL => write_manual,
+        _ => return Err(format!("unknown mode {mode}")),
+    };
+
+    let mut reader = Reader { input: records, pos: 0 };
+    let mut out = Vec::new();
+    let mut count = 0u64;
+    while reader.pos < records.len() {
+        let record = reader.record()?;
+        write(&mut out, &record);
+        count += 1;
+    }
+    Ok([count, out.len() as u64])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    match run(&input) {
+        Ok(fields) => {
+            let output: Vec<u8> = fields.iter().flat_map(|f| f.to_le_bytes()).collect();
+            std::io::stdout().write_all(&output).unwrap();
+        }
+        Err(e) => proto::exit_malformed(&e),
+    }
+}
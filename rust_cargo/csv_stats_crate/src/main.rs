@@ -0,0 +1,28 @@
+use std::io::{Read, Write};
+
+// The csv_stats workload using the `csv` crate: record count, field count,
+// and total field bytes after unquoting, as three u64 LE. No header row,
+// records may differ in width, and fields are bytes (no UTF-8 check).
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(input.as_slice());
+
+    let (mut records, mut fields, mut bytes) = (0u64, 0u64, 0u64);
+    let mut record = csv::ByteRecord::new();
+    while reader.read_byte_record(&mut record).unwrap() {
+        records += 1;
+        fields += record.len() as u64;
+        bytes += record.as_slice().len() as u64;
+    }
+
+    let mut output = Vec::with_capacity(24);
+    output.extend_from_slice(&records.to_le_bytes());
+    output.extend_from_slice(&fields.to_le_bytes());
+    output.extend_from_slice(&bytes.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,33 @@
+use chrono::DateTime;
+use std::io::{Read, Write};
+
+// The datetime_parse workload for any RFC 3339 timestamp, parsed with
+// chrono's DateTime::parse_from_rfc3339: "T", "t" or a space between date
+// and time, "Z", "z" or a +HH:MM / -HH:MM offset (up to 23:59), and
+// optional fractional seconds, which are dropped (the Unix time is floored:
+// 1969-12-31T23:59:59.5Z is -1). The output is datetime_parse's three
+// fields; lines not valid UTF-8 are rejected.
+//
+// 2024-06-01T12:00:00+05:30 is 1717223400, 2024-06-01t12:00:00z 1717243200.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let (mut parsed, mut sum, mut rejected) = (0u64, 0i64, 0u64);
+    for line in input.split_inclusive(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        match std::str::from_utf8(line).ok().and_then(|s| DateTime::parse_from_rfc3339(s).ok()) {
+            Some(t) => {
+                parsed += 1;
+                sum = sum.wrapping_add(t.timestamp());
+            }
+            None => rejected += 1,
+        }
+    }
+
+    let mut output = Vec::with_capacity(24);
+    output.extend_from_slice(&parsed.to_le_bytes());
+    output.extend_from_slice(&sum.to_le_bytes());
+    output.extend_from_slice(&rejected.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
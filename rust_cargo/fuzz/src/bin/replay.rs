@@ -0,0 +1,38 @@
+//! Runs every file under `corpus/<target>/` and `artifacts/<target>/`
+//! through its target's check, so a crash fixed once stays fixed. Exits
+//! non-zero if any input panics.
+
+use std::panic;
+use std::path::Path;
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut ran = 0;
+    let mut failed = 0;
+
+    for (target, check) in fuzz::TARGETS {
+        for dir in ["corpus", "artifacts"] {
+            let Ok(entries) = std::fs::read_dir(root.join(dir).join(target)) else {
+                continue;
+            };
+            let mut paths: Vec<_> = entries.map(|e| e.unwrap().path()).collect();
+            paths.sort();
+            for path in paths {
+                if path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')) {
+                    continue;
+                }
+                let data = std::fs::read(&path).unwrap();
+                ran += 1;
+                if panic::catch_unwind(|| check(&data)).is_err() {
+                    eprintln!("FAIL {target}: {}", path.display());
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("replayed {ran} inputs, {failed} failed");
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
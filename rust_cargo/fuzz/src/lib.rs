@@ -0,0 +1,69 @@
+//! Properties checked by the fuzz targets, kept out of the `fuzz_target!`
+//! bodies so `replay` can run the same checks on the corpus and on crash
+//! artifacts without libFuzzer.
+
+use proto::regex_flags;
+
+/// Every field count the multi-field benchmarks use, plus the degenerate
+/// zero-field frame.
+const MAX_FIELDS: usize = 3;
+
+/// Any input either fails to parse or parses into `n + 1` slices that
+/// rebuild the exact input in the layout it was read as.
+pub fn check_frames(data: &[u8]) {
+    for n in 0..=MAX_FIELDS {
+        let Ok(frames) = proto::read_frames(data, n) else {
+            continue;
+        };
+        assert_eq!(frames.len(), n + 1);
+        for field in 0..=n {
+            let _ = proto::frame_str(&frames, field);
+        }
+
+        let (fields, payload) = frames.split_at(n);
+        let rebuilt = if proto::read_frames_v2(data, n).is_ok() {
+            proto::write_frames_v2(fields, payload[0])
+        } else {
+            proto::write_frames(fields, payload[0])
+        };
+        assert_eq!(rebuilt, data, "frame with {n} fields does not round-trip");
+    }
+
+    // v3 adds a flags byte; flagless layouts read as flags 0.
+    for n in 0..=MAX_FIELDS {
+        let Ok((flags, frames)) = proto::read_frames_flagged(data, n, regex_flags::ALL) else {
+            continue;
+        };
+        assert_eq!(flags & !regex_flags::ALL, 0);
+        let (fields, payload) = frames.split_at(n);
+        if proto::write_frames_v3(fields, flags, payload[0]) != data {
+            assert_eq!(flags, 0, "flagged frame with {n} fields does not round-trip");
+            assert_eq!(Ok(frames), proto::read_frames(data, n));
+        }
+    }
+}
+
+/// `regex_is_match` either rejects the input or answers with a u32 0 or 1.
+pub fn check_regex_is_match(data: &[u8]) {
+    if let Ok(out) = regex_is_match::run(data) {
+        assert!(out == [0, 0, 0, 0] || out == [1, 0, 0, 0], "unexpected output {out:?}");
+    }
+}
+
+/// `regex_replace` either rejects the input or returns UTF-8 text.
+pub fn check_regex_replace(data: &[u8]) {
+    if let Ok(out) = regex_replace::run(data) {
+        assert!(std::str::from_utf8(&out).is_ok(), "output is not valid UTF-8");
+    }
+}
+
+/// Property check run on one fuzz input; panics on failure.
+pub type Check = fn(&[u8]);
+
+/// Target name (the directory under `corpus/` and `artifacts/`) and the
+/// check it runs.
+pub const TARGETS: &[(&str, Check)] = &[
+    ("frames", check_frames),
+    ("regex_is_match", check_regex_is_match),
+    ("regex_replace", check_regex_replace),
+];
@@ -0,0 +1,31 @@
+use rustc_hash::FxHashMap;
+use std::io::Read;
+
+#[path = "../../../rust/records/mod.rs"]
+mod records;
+use records::Op;
+
+// The hashmap_bench workload with rustc-hash's FxHashMap in place of std's
+// SipHash-keyed HashMap; the record format and output are described there.
+fn run(input: &[u8]) -> Result<Vec<u64>, String> {
+    let mut map: FxHashMap<u64, u64> = FxHashMap::default();
+    let mut sum = 0u64;
+    for (i, record) in records::parse(input)?.enumerate() {
+        match record? {
+            (key, Op::Insert) => *map.entry(key).or_insert(0) += 1,
+            (key, Op::Lookup) => {
+                if let Some(&v) = map.get(&key) {
+                    sum = sum.wrapping_add(v);
+                }
+            }
+            (_, Op::RangeSum) => return Err(format!("range op in record {i} needs an ordered map")),
+        }
+    }
+    Ok(vec![map.len() as u64, sum])
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+    records::finish(run(&input));
+}
@@ -0,0 +1,60 @@
+use std::io::{Read, Write};
+use serde_json::Value;
+
+#[derive(Default)]
+struct Counts {
+    objects: u64,
+    arrays: u64,
+    string_bytes: u64,
+}
+
+// Counts every object and array in the tree, including the root, and the
+// UTF-8 length (after unescaping) of every string value. Object keys are
+// not strings for this purpose and are not counted.
+fn walk(v: &Value, c: &mut Counts) {
+    match v {
+        Value::Object(map) => {
+            c.objects += 1;
+            for child in map.values() {
+                walk(child, c);
+            }
+        }
+        Value::Array(items) => {
+            c.arrays += 1;
+            for child in items {
+                walk(child, c);
+            }
+        }
+        Value::String(s) => c.string_bytes += s.len() as u64,
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+fn write_counts(c: &Counts) {
+    let mut output = Vec::with_capacity(24);
+    output.extend_from_slice(&c.objects.to_le_bytes());
+    output.extend_from_slice(&c.arrays.to_le_bytes());
+    output.extend_from_slice(&c.string_bytes.to_le_bytes());
+    std::io::stdout().write_all(&output).unwrap();
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    // Malformed JSON still produces the fixed 24-byte output (all zeros),
+    // then exits with the shared malformed-input status.
+    match serde_json::from_slice::<Value>(&input) {
+        Ok(doc) => {
+            let mut counts = Counts::default();
+            walk(&doc, &mut counts);
+            write_counts(&counts);
+        }
+        Err(e) => {
+            write_counts(&Counts::default());
+            std::io::stdout().flush().unwrap();
+            eprintln!("malformed input: {e}");
+            std::process::exit(proto::EXIT_MALFORMED);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::io::{Read, Write};
+
+// Input: a DNA-like byte sequence. A, C, G, and T (either case) are bases;
+// any other byte, including a newline, is skipped and resets the window, so
+// no k-mer spans it. Output, for each k in K: the number of distinct k-mers,
+// then the count of the probe (the first k bases of PROBE), as u64 LE.
+//
+// Each k-mer is counted in a HashMap keyed on its 2 bits per base packing.
+// For "GGTATTTTAATT" the output is 3 2, 6 1, 8 1, 9 1, 7 1, 1 1.
+const K: [usize; 6] = [1, 2, 3, 4, 6, 12];
+const PROBE: &[u8] = b"GGTATTTTAATT";
+
+const NOT_A_BASE: u8 = 0xFF;
+
+const fn base_table() -> [u8; 256] {
+    let mut table = [NOT_A_BASE; 256];
+    let bases = *b"ACGT";
+    let mut i = 0;
+    while i < 4 {
+        table[bases[i] as usize] = i as u8;
+        table[bases[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+const BASE: [u8; 256] = base_table();
+
+/// Multiplicative hash for the packed keys, which SipHash would spend most
+/// of the run on. Only `write_u64` is used.
+#[derive(Default)]
+struct KmerHasher(u64);
+
+impl Hasher for KmerHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(b as u64);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0 ^ n).wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(26);
+    }
+}
+
+type Counts = HashMap<u64, u64, BuildHasherDefault<KmerHasher>>;
+
+fn count_kmers(input: &[u8], k: usize) -> Counts {
+    let mask = (1u64 << (2 * k)) - 1;
+    let mut counts = Counts::default();
+    let mut key = 0u64;
+    let mut filled = 0;
+    for &b in input {
+        let base = BASE[b as usize];
+        if base == NOT_A_BASE {
+            filled = 0;
+            continue;
+        }
+        key = (key << 2 | base as u64) & mask;
+        filled += 1;
+        if filled >= k {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn pack(kmer: &[u8]) -> u64 {
+    kmer.iter().fold(0, |key, &b| key << 2 | BASE[b as usize] as u64)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let mut output = Vec::with_capacity(16 * K.len());
+    for k in K {
+        let counts = count_kmers(&input, k);
+        let probe = counts.get(&pack(&PROBE[..k])).copied().unwrap_or(0);
+        output.extend_from_slice(&(counts.len() as u64).to_le_bytes());
+        output.extend_from_slice(&probe.to_le_bytes());
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
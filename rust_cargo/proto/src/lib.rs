@@ -0,0 +1,550 @@
+//! Length-prefixed stdin framing shared by the multi-field benchmarks.
+//!
+//! A frame with `n` fields is laid out as `n` little-endian lengths,
+//! followed by the field bytes in the same order, followed by a trailing
+//! payload that runs to the end of the input. Three layouts exist:
+//!
+//! - v1 (untagged): u32 lengths, starting at byte 0.
+//! - v2: the byte [`FRAME_V2_TAG`], then u64 lengths. Needed once a frame
+//!   no longer fits in 4 GiB.
+//! - v3: the byte [`FRAME_V3_TAG`], then u64 lengths, then one flags byte
+//!   (see [`regex_flags`]). Only [`read_frames_flagged`] accepts it.
+//!
+//! [`read_frames`] accepts v1 and v2. An input is read as a tagged layout
+//! only if it starts with the tag *and* parses as that layout; anything else
+//! is read as v1, so v1 inputs whose first length byte happens to equal a
+//! tag still work.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameError {
+    /// The input is shorter than the fixed-size length header.
+    TruncatedHeader { needed: usize, len: usize },
+    /// A field's declared length runs past the end of the input.
+    FieldOutOfBounds { field: usize, end: usize, len: usize },
+    /// A field that must be text is not valid UTF-8.
+    InvalidUtf8 { field: usize },
+    /// The flags byte sets bits the benchmark does not define.
+    UnknownFlags { flags: u8 },
+    /// A tagged layout was required but the input starts with another byte
+    /// (`found` is `None` for empty input).
+    BadTag { expected: u8, found: Option<u8> },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::TruncatedHeader { needed, len } => {
+                write!(f, "truncated header: need {needed} bytes, got {len}")
+            }
+            FrameError::FieldOutOfBounds { field, end, len } => {
+                write!(f, "field {field} ends at byte {end}, past end of input ({len})")
+            }
+            FrameError::InvalidUtf8 { field } => write!(f, "field {field} is not valid UTF-8"),
+            FrameError::UnknownFlags { flags } => write!(f, "unknown flag bits {flags:#04x}"),
+            FrameError::BadTag { expected, found: Some(found) } => {
+                write!(f, "expected version tag {expected:#04x}, got {found:#04x}")
+            }
+            FrameError::BadTag { expected, found: None } => {
+                write!(f, "expected version tag {expected:#04x}, got empty input")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+/// Why a benchmark rejected its input: either the framing is broken, or a
+/// field parsed fine but its contents are unusable (e.g. an invalid regex).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Frame(FrameError),
+    Invalid(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Frame(e) => e.fmt(f),
+            Error::Invalid(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<FrameError> for Error {
+    fn from(e: FrameError) -> Self {
+        Error::Frame(e)
+    }
+}
+
+/// Exit status for malformed input, shared by every framed benchmark so
+/// differential runs can tell "rejected" apart from a crash or a result.
+pub const EXIT_MALFORMED: i32 = 2;
+
+/// Prints a one-line diagnostic to stderr and exits with [`EXIT_MALFORMED`].
+/// Nothing is written to stdout.
+pub fn exit_malformed(err: &dyn fmt::Display) -> ! {
+    eprintln!("malformed input: {err}");
+    std::process::exit(EXIT_MALFORMED)
+}
+
+/// Version tag that starts a v2 (u64-length) frame.
+pub const FRAME_V2_TAG: u8 = 0xF2;
+
+/// Parses `n` length-prefixed fields in either layout, returning them
+/// followed by the trailing payload (so the result always has `n + 1`
+/// entries).
+pub fn read_frames(input: &[u8], n: usize) -> Result<Vec<&[u8]>, FrameError> {
+    if input.first() == Some(&FRAME_V2_TAG) {
+        if let Ok(frames) = read_frames_v2(input, n) {
+            return Ok(frames);
+        }
+    }
+    read_frames_v1(input, n)
+}
+
+/// Version tag that starts a v3 (u64-length, flagged) frame.
+pub const FRAME_V3_TAG: u8 = 0xF3;
+
+/// Flag bits carried by v3 frames for the regex benchmarks.
+pub mod regex_flags {
+    pub const CASE_INSENSITIVE: u8 = 1 << 0;
+    pub const MULTI_LINE: u8 = 1 << 1;
+    pub const DOT_MATCHES_NEW_LINE: u8 = 1 << 2;
+    pub const SWAP_GREED: u8 = 1 << 3;
+
+    pub const ALL: u8 = CASE_INSENSITIVE | MULTI_LINE | DOT_MATCHES_NEW_LINE | SWAP_GREED;
+
+    /// `regex_replace` only: insert the replacement literally instead of
+    /// expanding `$1`, `${name}`, and `$$` in it.
+    pub const LITERAL_REPLACEMENT: u8 = 1 << 4;
+
+    // The str and bytes builders have the same methods but no common trait.
+    #[cfg(feature = "regex")]
+    macro_rules! build_with {
+        ($builder:ty, $pattern:expr, $flags:expr) => {
+            <$builder>::new($pattern)
+                .case_insensitive($flags & CASE_INSENSITIVE != 0)
+                .multi_line($flags & MULTI_LINE != 0)
+                .dot_matches_new_line($flags & DOT_MATCHES_NEW_LINE != 0)
+                .swap_greed($flags & SWAP_GREED != 0)
+                .build()
+                .map_err(|_| crate::Error::Invalid("invalid pattern".into()))
+        };
+    }
+
+    /// Compiles `pattern` with these flag bits applied (bits outside
+    /// [`ALL`] are ignored). Needs the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub fn build(pattern: &str, flags: u8) -> Result<regex::Regex, crate::Error> {
+        build_with!(regex::RegexBuilder, pattern, flags)
+    }
+
+    /// [`build`] for `regex::bytes`, for benchmarks that match raw bytes.
+    #[cfg(feature = "regex")]
+    pub fn build_bytes(pattern: &str, flags: u8) -> Result<regex::bytes::Regex, crate::Error> {
+        build_with!(regex::bytes::RegexBuilder, pattern, flags)
+    }
+}
+
+/// Like [`read_frames`], but also accepts a v3 frame and returns its flags
+/// byte; v1 and v2 frames have flags 0. Flags outside `known` are an error.
+pub fn read_frames_flagged(
+    input: &[u8],
+    n: usize,
+    known: u8,
+) -> Result<(u8, Vec<&[u8]>), FrameError> {
+    if input.first() == Some(&FRAME_V3_TAG) {
+        if let Ok(frames) = parse(input, 1, 8, n, 1) {
+            let flags = input[1 + 8 * n];
+            if flags & !known != 0 {
+                return Err(FrameError::UnknownFlags { flags });
+            }
+            return Ok((flags, frames));
+        }
+    }
+    Ok((0, read_frames(input, n)?))
+}
+
+/// Parses a v1 frame (u32 lengths, no tag).
+pub fn read_frames_v1(input: &[u8], n: usize) -> Result<Vec<&[u8]>, FrameError> {
+    parse(input, 0, 4, n, 0)
+}
+
+/// Parses a v2 frame (tag byte, then u64 lengths).
+pub fn read_frames_v2(input: &[u8], n: usize) -> Result<Vec<&[u8]>, FrameError> {
+    if input.first() != Some(&FRAME_V2_TAG) {
+        return Err(FrameError::BadTag { expected: FRAME_V2_TAG, found: input.first().copied() });
+    }
+    parse(input, 1, 8, n, 0)
+}
+
+/// `n` lengths of `width` bytes at `start`, then `extra` header bytes (the
+/// v3 flags), then the fields.
+fn parse(
+    input: &[u8],
+    start: usize,
+    width: usize,
+    n: usize,
+    extra: usize,
+) -> Result<Vec<&[u8]>, FrameError> {
+    let header = start + width * n + extra;
+    if input.len() < header {
+        return Err(FrameError::TruncatedHeader { needed: header, len: input.len() });
+    }
+
+    let mut frames = Vec::with_capacity(n + 1);
+    let mut pos = header;
+    for field in 0..n {
+        let at = start + width * field;
+        let mut raw = [0u8; 8];
+        raw[..width].copy_from_slice(&input[at..at + width]);
+        let field_len = u64::from_le_bytes(raw);
+
+        let end = usize::try_from(field_len).ok().and_then(|l| pos.checked_add(l));
+        let end = match end {
+            Some(end) if end <= input.len() => end,
+            _ => {
+                return Err(FrameError::FieldOutOfBounds {
+                    field,
+                    end: usize::try_from(field_len).map_or(usize::MAX, |l| pos.saturating_add(l)),
+                    len: input.len(),
+                })
+            }
+        };
+        frames.push(&input[pos..end]);
+        pos = end;
+    }
+    frames.push(&input[pos..]);
+
+    Ok(frames)
+}
+
+/// Interprets frame `field` (as returned by [`read_frames`]) as UTF-8.
+pub fn frame_str<'a>(frames: &[&'a [u8]], field: usize) -> Result<&'a str, FrameError> {
+    std::str::from_utf8(frames[field]).map_err(|_| FrameError::InvalidUtf8 { field })
+}
+
+/// Builds a v1 frame from `fields` and a trailing `payload`; the inverse of
+/// [`read_frames_v1`].
+pub fn write_frames(fields: &[&[u8]], payload: &[u8]) -> Vec<u8> {
+    assert!(
+        fields.iter().all(|f| u32::try_from(f.len()).is_ok()),
+        "field longer than u32::MAX; use write_frames_v2"
+    );
+    let mut out = Vec::with_capacity(frame_len(fields, payload, 4));
+    write_body(&mut out, fields, payload, 4, &[]);
+    out
+}
+
+/// Builds a v2 frame from `fields` and a trailing `payload`; the inverse of
+/// [`read_frames_v2`].
+pub fn write_frames_v2(fields: &[&[u8]], payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + frame_len(fields, payload, 8));
+    out.push(FRAME_V2_TAG);
+    write_body(&mut out, fields, payload, 8, &[]);
+    out
+}
+
+/// Just the v2 header for fields of `field_lens` bytes, for writers that
+/// stream the fields and payload after it rather than hold them in memory.
+pub fn frame_header_v2(field_lens: &[u64]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + 8 * field_lens.len());
+    out.push(FRAME_V2_TAG);
+    for len in field_lens {
+        out.extend_from_slice(&len.to_le_bytes());
+    }
+    out
+}
+
+/// Builds a v3 frame from `fields`, `flags`, and a trailing `payload`; the
+/// inverse of [`read_frames_flagged`] for v3 input.
+pub fn write_frames_v3(fields: &[&[u8]], flags: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(2 + frame_len(fields, payload, 8));
+    out.push(FRAME_V3_TAG);
+    write_body(&mut out, fields, payload, 8, &[flags]);
+    out
+}
+
+fn frame_len(fields: &[&[u8]], payload: &[u8], width: usize) -> usize {
+    let body: usize = fields.iter().map(|f| f.len()).sum();
+    width * fields.len() + body + payload.len()
+}
+
+fn write_body(out: &mut Vec<u8>, fields: &[&[u8]], payload: &[u8], width: usize, extra: &[u8]) {
+    for f in fields {
+        out.extend_from_slice(&(f.len() as u64).to_le_bytes()[..width]);
+    }
+    out.extend_from_slice(extra);
+    for f in fields {
+        out.extend_from_slice(f);
+    }
+    out.extend_from_slice(payload);
+}
+
+/// Helpers for the benchmarks' integration tests, which run the built
+/// binary on framed stdin. Needs the `testing` feature.
+#[cfg(feature = "testing")]
+pub mod testing {
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    /// Runs `bin` with `input` on stdin.
+    pub fn output(bin: &str, input: &[u8]) -> Output {
+        let mut child = Command::new(bin)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(input).unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    /// `bin`'s stdout for `input`, which it must accept.
+    pub fn run(bin: &str, input: &[u8]) -> Vec<u8> {
+        let out = output(bin, input);
+        assert!(
+            out.status.success(),
+            "input {input:?}: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        out.stdout
+    }
+
+    /// Asserts that `bin` rejects `input`: status [`crate::EXIT_MALFORMED`],
+    /// nothing on stdout, and `malformed input: {diagnostic}` on stderr.
+    pub fn assert_rejected(bin: &str, input: &[u8], diagnostic: &str) {
+        let out = output(bin, input);
+        assert_eq!(
+            out.status.code(),
+            Some(crate::EXIT_MALFORMED),
+            "input {input:?}"
+        );
+        assert!(out.stdout.is_empty(), "input {input:?}");
+        assert_eq!(
+            String::from_utf8(out.stderr).unwrap(),
+            format!("malformed input: {diagnostic}\n")
+        );
+    }
+
+    /// The malformed-input tests for a benchmark whose input is one
+    /// pattern field and the text as payload (regex_count, regex_is_match).
+    /// `$bin` is the binary's path, usually `env!("CARGO_BIN_EXE_...")`.
+    #[macro_export]
+    macro_rules! pattern_text_malformed_tests {
+        ($bin:expr) => {
+            use $crate::testing::assert_rejected;
+
+            #[test]
+            fn truncated_header() {
+                assert_rejected($bin, b"", "truncated header: need 4 bytes, got 0");
+                assert_rejected($bin, &[1, 0, 0], "truncated header: need 4 bytes, got 3");
+            }
+
+            #[test]
+            fn pattern_length_past_end() {
+                let mut input = $crate::write_frames(&[b"ab"], b"text");
+                input[0] = 200;
+                assert_rejected($bin, &input, "field 0 ends at byte 204, past end of input (10)");
+                let end = 4 + u32::MAX as usize;
+                assert_rejected(
+                    $bin,
+                    &u32::MAX.to_le_bytes(),
+                    &format!("field 0 ends at byte {end}, past end of input (4)"),
+                );
+            }
+
+            #[test]
+            fn non_utf8_fields() {
+                assert_rejected(
+                    $bin,
+                    &$crate::write_frames(&[b"\xff"], b"text"),
+                    "field 0 is not valid UTF-8",
+                );
+                assert_rejected(
+                    $bin,
+                    &$crate::write_frames(&[b"a"], b"\xc3"),
+                    "field 1 is not valid UTF-8",
+                );
+            }
+
+            #[test]
+            fn invalid_pattern() {
+                assert_rejected(
+                    $bin,
+                    &$crate::write_frames(&[b"("], b"text"),
+                    "invalid pattern",
+                );
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_round_trip() {
+        let fields: [&[u8]; 3] = [b"pattern", b"", b"\xff\x00"];
+        let input = write_frames(&fields, b"the text");
+        assert_eq!(&input[..4], &7u32.to_le_bytes());
+        let frames = read_frames(&input, 3).unwrap();
+        assert_eq!(frames, [&b"pattern"[..], b"", b"\xff\x00", b"the text"]);
+        assert_eq!(frame_str(&frames, 0), Ok("pattern"));
+        assert_eq!(frame_str(&frames, 2), Err(FrameError::InvalidUtf8 { field: 2 }));
+    }
+
+    #[test]
+    fn truncated_length() {
+        assert_eq!(read_frames(b"", 1), Err(FrameError::TruncatedHeader { needed: 4, len: 0 }));
+        // The first length is whole, the second is cut one byte short.
+        assert_eq!(
+            read_frames(&[0, 0, 0, 0, 0, 0, 0], 2),
+            Err(FrameError::TruncatedHeader { needed: 8, len: 7 })
+        );
+    }
+
+    #[test]
+    fn length_past_end() {
+        let mut input = write_frames(&[b"abc", b"de"], b"");
+        input.pop();
+        assert_eq!(
+            read_frames(&input, 2),
+            Err(FrameError::FieldOutOfBounds { field: 1, end: 13, len: 12 })
+        );
+        let huge = u32::MAX.to_le_bytes();
+        assert_eq!(
+            read_frames(&huge, 1),
+            Err(FrameError::FieldOutOfBounds { field: 0, end: 4 + u32::MAX as usize, len: 4 })
+        );
+    }
+
+    #[test]
+    fn empty_payload() {
+        let input = write_frames(&[b"abc"], b"");
+        assert_eq!(input.len(), 7);
+        assert_eq!(read_frames(&input, 1).unwrap(), [&b"abc"[..], b""]);
+        // Zero fields: the whole input is the payload, even when empty.
+        assert_eq!(read_frames(b"", 0).unwrap(), [&b""[..]]);
+    }
+
+    #[test]
+    fn v2_round_trip() {
+        let fields: [&[u8]; 2] = [b"a+", b""];
+        let input = write_frames_v2(&fields, b"text");
+        assert_eq!(input[..17], frame_header_v2(&[2, 0])[..]);
+        let want = [&b"a+"[..], b"", b"text"];
+        assert_eq!(read_frames_v2(&input, 2).unwrap(), want);
+        assert_eq!(read_frames(&input, 2).unwrap(), want);
+        // Without the tag, read_frames_v2 refuses rather than guessing.
+        let v1 = write_frames(&fields, b"text");
+        assert_eq!(
+            read_frames_v2(&v1, 2),
+            Err(FrameError::BadTag { expected: FRAME_V2_TAG, found: Some(2) })
+        );
+        assert_eq!(
+            read_frames_v2(b"", 2),
+            Err(FrameError::BadTag { expected: FRAME_V2_TAG, found: None })
+        );
+    }
+
+    #[test]
+    fn v2_truncated() {
+        let input = write_frames_v2(&[b"abc", b"de"], b"");
+        assert_eq!(
+            read_frames_v2(&input[..16], 2),
+            Err(FrameError::TruncatedHeader { needed: 17, len: 16 })
+        );
+        assert_eq!(
+            read_frames_v2(&input[..21], 2),
+            Err(FrameError::FieldOutOfBounds { field: 1, end: 22, len: 21 })
+        );
+        // read_frames falls back to v1, which fails on its own terms.
+        assert_eq!(
+            read_frames(&input[..21], 2),
+            Err(FrameError::FieldOutOfBounds { field: 0, end: 8 + 0x03F2, len: 21 })
+        );
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn v2_multi_gigabyte_frame() {
+        // A field just past what a u32 length can say. vec![0; n] maps
+        // zeroed pages lazily, so only the pages written below are touched.
+        const BIG: u64 = u32::MAX as u64 + 2;
+        let header = frame_header_v2(&[3, BIG]);
+        let len = header.len() + 3 + BIG as usize + 3;
+        if Vec::<u8>::new().try_reserve_exact(len).is_err() {
+            eprintln!("skipping: cannot reserve {len} bytes of address space");
+            return;
+        }
+        let mut input = vec![0u8; len];
+        input[..header.len()].copy_from_slice(&header);
+        let start = header.len();
+        input[start..start + 3].copy_from_slice(b"a+b");
+        input[start + 3] = b'<';
+        input[len - 4] = b'>';
+        input[len - 3..].copy_from_slice(b"end");
+
+        let frames = read_frames(&input, 2).unwrap();
+        assert_eq!(frames[0], b"a+b");
+        assert_eq!(frames[1].len() as u64, BIG);
+        assert_eq!((frames[1][0], frames[1][frames[1].len() - 1]), (b'<', b'>'));
+        assert_eq!(frames[2], b"end");
+    }
+
+    #[test]
+    fn v3_round_trip() {
+        let flags = regex_flags::CASE_INSENSITIVE | regex_flags::SWAP_GREED;
+        let input = write_frames_v3(&[b"a+", b"x"], flags, b"text");
+        assert_eq!(input[0], FRAME_V3_TAG);
+        let (got, frames) = read_frames_flagged(&input, 2, regex_flags::ALL).unwrap();
+        assert_eq!(got, flags);
+        assert_eq!(frames, [&b"a+"[..], b"x", b"text"]);
+        // v1 input through the same entry point has no flags.
+        let v1 = write_frames(&[b"a+", b"x"], b"text");
+        assert_eq!(read_frames_flagged(&v1, 2, regex_flags::ALL).unwrap(), (0, frames));
+    }
+
+    #[test]
+    fn unknown_flags() {
+        let input = write_frames_v3(&[b"a"], regex_flags::LITERAL_REPLACEMENT, b"");
+        assert_eq!(
+            read_frames_flagged(&input, 1, regex_flags::ALL),
+            Err(FrameError::UnknownFlags { flags: regex_flags::LITERAL_REPLACEMENT })
+        );
+        let known = regex_flags::ALL | regex_flags::LITERAL_REPLACEMENT;
+        assert!(read_frames_flagged(&input, 1, known).is_ok());
+    }
+
+    #[test]
+    fn v1_starting_with_a_tag_byte() {
+        // A first field 0xF2 or 0xF3 bytes long puts the tag at byte 0; as a
+        // tagged layout its lengths run far past the end, so it reads as v1.
+        for (len, tag) in [(0xF2, FRAME_V2_TAG), (0xF3, FRAME_V3_TAG)] {
+            let field = vec![b'a'; len];
+            let input = write_frames(&[&field], b"rest");
+            assert_eq!(input[0], tag);
+            let want = [&field[..], b"rest"];
+            assert_eq!(read_frames(&input, 1).unwrap(), want);
+            assert_eq!(read_frames_flagged(&input, 1, 0).unwrap(), (0, want.to_vec()));
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn build_applies_flags() {
+        use regex_flags::*;
+        assert!(!build("A", 0).unwrap().is_match("a"));
+        assert!(build("A", CASE_INSENSITIVE).unwrap().is_match("a"));
+        assert!(build("^b$", MULTI_LINE).unwrap().is_match("a\nb"));
+        assert!(build_bytes("a.b", DOT_MATCHES_NEW_LINE).unwrap().is_match(b"a\nb\xff"));
+        assert_eq!(build("a+", SWAP_GREED).unwrap().find("aaa").unwrap().as_str(), "a");
+        assert_eq!(build("(", 0).unwrap_err(), Error::Invalid("invalid pattern".into()));
+    }
+}
@@ -0,0 +1,50 @@
+use std::io::{Read, Write};
+use regex::Regex;
+
+/// Offset pair written for an optional group that did not participate.
+const NO_GROUP: (u32, u32) = (u32::MAX, u32::MAX);
+
+// Output, per match: the group count as u32 LE (including group 0, the
+// whole match), then each group's (start, end) byte offsets into the text
+// as u32 LE pairs. A u32 LE total match count follows the last match.
+// Text of u32::MAX bytes or more is rejected: below that, every offset is
+// at most the text length and so never collides with NO_GROUP, and there
+// are at most length + 1 matches.
+fn captures(input: &[u8]) -> Result<Vec<u8>, proto::Error> {
+    let frames = proto::read_frames(input, 1)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let text = proto::frame_str(&frames, 1)?;
+    if text.len() >= u32::MAX as usize {
+        return Err(proto::Error::Invalid(format!(
+            "text is {} bytes, too long for u32 offsets",
+            text.len()
+        )));
+    }
+
+    let re = Regex::new(pattern).map_err(|_| proto::Error::Invalid("invalid pattern".into()))?;
+    let groups = re.captures_len() as u32;
+
+    let mut output = Vec::new();
+    let mut matches: u32 = 0;
+    for caps in re.captures_iter(text) {
+        output.extend_from_slice(&groups.to_le_bytes());
+        for group in caps.iter() {
+            let (start, end) = group.map_or(NO_GROUP, |m| (m.start() as u32, m.end() as u32));
+            output.extend_from_slice(&start.to_le_bytes());
+            output.extend_from_slice(&end.to_le_bytes());
+        }
+        matches += 1;
+    }
+    output.extend_from_slice(&matches.to_le_bytes());
+
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let output = captures(&input).unwrap_or_else(|e| proto::exit_malformed(&e));
+
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,76 @@
+//! Group offsets for nested and optional groups, decoded from the output
+//! framing described in src/main.rs.
+
+const NO_GROUP: (u32, u32) = (u32::MAX, u32::MAX);
+
+fn run(pattern: &str, text: &str) -> Vec<u8> {
+    let input = proto::write_frames(&[pattern.as_bytes()], text.as_bytes());
+    proto::testing::run(env!("CARGO_BIN_EXE_regex_captures"), &input)
+}
+
+/// Each match's (start, end) pairs, group 0 first.
+fn captures(pattern: &str, text: &str) -> Vec<Vec<(u32, u32)>> {
+    let out = run(pattern, text);
+    let words: Vec<u32> = out
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+        .collect();
+    assert_eq!(out.len(), words.len() * 4);
+
+    let (&count, mut rest) = words.split_last().unwrap();
+    let mut matches = Vec::new();
+    while let Some((&groups, tail)) = rest.split_first() {
+        let (pairs, tail) = tail.split_at(2 * groups as usize);
+        matches.push(pairs.chunks_exact(2).map(|p| (p[0], p[1])).collect());
+        rest = tail;
+    }
+    assert_eq!(matches.len(), count as usize);
+    matches
+}
+
+#[test]
+fn nested_groups() {
+    assert_eq!(
+        captures("((a)(b(c)))", "xabcabc"),
+        [
+            [(1, 4), (1, 4), (1, 2), (2, 4), (3, 4)],
+            [(4, 7), (4, 7), (4, 5), (5, 7), (6, 7)],
+        ]
+    );
+    // A repeated group reports its last iteration.
+    assert_eq!(captures("(?:(a)b)+", "abab"), [[(0, 4), (2, 3)]]);
+}
+
+#[test]
+fn optional_groups() {
+    assert_eq!(
+        captures("(a)|(b)", "ab"),
+        [[(0, 1), (0, 1), NO_GROUP], [(1, 2), NO_GROUP, (1, 2)]]
+    );
+    assert_eq!(
+        captures("(x)?y", "yxy"),
+        [[(0, 1), NO_GROUP], [(1, 3), (1, 2)]]
+    );
+    // An outer group can take part while the inner one does not.
+    assert_eq!(captures("(a(b)?)c", "ac"), [[(0, 2), (0, 1), NO_GROUP]]);
+}
+
+#[test]
+fn named_groups_and_byte_offsets() {
+    // Offsets are in bytes: "é" is two.
+    assert_eq!(
+        captures("(?P<word>é+)(?P<tail>z)?", "aéé"),
+        [[(1, 5), (1, 5), NO_GROUP]]
+    );
+}
+
+#[test]
+fn empty_matches() {
+    assert_eq!(captures("(a*)", "b"), [[(0, 0), (0, 0)], [(1, 1), (1, 1)]]);
+}
+
+#[test]
+fn no_matches() {
+    assert_eq!(run("(z)", "abc"), 0u32.to_le_bytes());
+    assert_eq!(run("(z)", ""), 0u32.to_le_bytes());
+}
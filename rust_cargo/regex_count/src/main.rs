@@ -1,46 +1,23 @@
 use std::io::{Read, Write};
-use regex::Regex;
+
+// Counts non-overlapping matches via find_iter. An empty match can occur at
+// any char boundary (including the end of the text) except directly at the
+// end of the previous match, so `a*` finds 2 matches in "baaa": "" at 0 and
+// "aaa" at 1..4.
+fn count(input: &[u8]) -> Result<u64, proto::Error> {
+    let (flags, frames) = proto::read_frames_flagged(input, 1, proto::regex_flags::ALL)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let text = proto::frame_str(&frames, 1)?;
+
+    let re = proto::regex_flags::build(pattern, flags)?;
+    Ok(re.find_iter(text).count() as u64)
+}
 
 fn main() {
     let mut input = Vec::new();
     std::io::stdin().read_to_end(&mut input).unwrap();
 
-    if input.len() < 4 {
-        let result: u32 = 0;
-        std::io::stdout().write_all(&result.to_le_bytes()).unwrap();
-        return;
-    }
-
-    let pat_len = u32::from_le_bytes([input[0], input[1], input[2], input[3]]) as usize;
-
-    if 4 + pat_len > input.len() {
-        let result: u32 = 0;
-        std::io::stdout().write_all(&result.to_le_bytes()).unwrap();
-        return;
-    }
-
-    let pattern = match std::str::from_utf8(&input[4..4 + pat_len]) {
-        Ok(s) => s,
-        Err(_) => {
-            let result: u32 = 0;
-            std::io::stdout().write_all(&result.to_le_bytes()).unwrap();
-            return;
-        }
-    };
-
-    let text = match std::str::from_utf8(&input[4 + pat_len..]) {
-        Ok(s) => s,
-        Err(_) => {
-            let result: u32 = 0;
-            std::io::stdout().write_all(&result.to_le_bytes()).unwrap();
-            return;
-        }
-    };
-
-    let count: u32 = match Regex::new(pattern) {
-        Ok(re) => re.find_iter(text).count() as u32,
-        Err(_) => 0,
-    };
+    let count = count(&input).unwrap_or_else(|e| proto::exit_malformed(&e));
 
     std::io::stdout().write_all(&count.to_le_bytes()).unwrap();
 }
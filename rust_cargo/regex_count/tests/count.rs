@@ -0,0 +1,28 @@
+//! Match counts, including the empty-match rules described in src/main.rs.
+
+fn count(pattern: &str, text: &str) -> u64 {
+    let input = proto::write_frames(&[pattern.as_bytes()], text.as_bytes());
+    let out = proto::testing::run(env!("CARGO_BIN_EXE_regex_count"), &input);
+    u64::from_le_bytes(out.try_into().unwrap())
+}
+
+#[test]
+fn counts() {
+    assert_eq!(count("ab", "abxabab"), 3);
+    assert_eq!(count("aa", "aaaaa"), 2); // non-overlapping
+    assert_eq!(count("z", "abc"), 0);
+    assert_eq!(count("a", ""), 0);
+}
+
+#[test]
+fn empty_matches() {
+    // "" at 0 and "aaa" at 1..4; an empty match at 4 would touch the end of
+    // the previous match.
+    assert_eq!(count("a*", "baaa"), 2);
+    assert_eq!(count("a*", "aaab"), 2);
+    // One at each char boundary, including the end of the text: "é" is two
+    // bytes but has only two boundaries.
+    assert_eq!(count("", "abc"), 4);
+    assert_eq!(count("", "é"), 2);
+    assert_eq!(count("", ""), 1);
+}
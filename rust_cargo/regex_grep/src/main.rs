@@ -0,0 +1,38 @@
+use std::io::{Read, Write};
+
+// Prints the lines of the text that contain a match, like grep. A line is
+// every byte between newlines ("a\r" keeps its '\r', so `a$` misses it), and
+// a final newline ends the last line rather than starting an empty one:
+// "a\n\nb" is the lines "a", "" and "b", "a\n" just "a", and "" none.
+//
+// Each line is matched on its own, so `^` and `$` anchor to the line's start
+// and end without the multi-line flag, and no match spans a newline. Each
+// matching line is printed followed by '\n', whether or not the text ended
+// with one, so every match shows in the output: on "a\nb\nab", `a` gives
+// "a\nab\n", `^a$` "a\n", `x` nothing, and `^$` on "\nb" gives "\n" (the
+// empty first line).
+fn grep(input: &[u8]) -> Result<Vec<u8>, proto::Error> {
+    let (flags, frames) = proto::read_frames_flagged(input, 1, proto::regex_flags::ALL)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let text = proto::frame_str(&frames, 1)?;
+
+    let re = proto::regex_flags::build(pattern, flags)?;
+    let mut output = Vec::new();
+    for line in text.split_inclusive('\n') {
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        if re.is_match(line) {
+            output.extend_from_slice(line.as_bytes());
+            output.push(b'\n');
+        }
+    }
+    Ok(output)
+}
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let output = grep(&input).unwrap_or_else(|e| proto::exit_malformed(&e));
+
+    std::io::stdout().write_all(&output).unwrap();
+}
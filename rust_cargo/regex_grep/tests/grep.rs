@@ -0,0 +1,42 @@
+//! Matching lines, each printed with a '\n' after it, as described in
+//! src/main.rs.
+
+fn grep(pattern: &str, text: &str) -> String {
+    let input = proto::write_frames(&[pattern.as_bytes()], text.as_bytes());
+    String::from_utf8(proto::testing::run(
+        env!("CARGO_BIN_EXE_regex_grep"),
+        &input,
+    ))
+    .unwrap()
+}
+
+#[test]
+fn matching_lines() {
+    assert_eq!(grep("a", "a\nb\nab\n"), "a\nab\n");
+    assert_eq!(grep("^a$", "a\nb\nab\n"), "a\n");
+    assert_eq!(grep("x", "a\nb\nab\n"), "");
+    // Without a final newline the last line is still ended.
+    assert_eq!(grep("b", "a\nab"), "ab\n");
+    assert_eq!(grep("", "a\nb"), "a\nb\n");
+}
+
+#[test]
+fn empty_lines_are_distinguishable() {
+    // One empty matching line is "\n", never the "" of no match.
+    assert_eq!(grep("^$", "\nb"), "\n");
+    assert_eq!(grep("^$", "a\n\nb\n"), "\n");
+    assert_eq!(grep("^$", "a\nb"), "");
+    // A final newline ends the last line rather than starting an empty one.
+    assert_eq!(grep("^$", "a\n"), "");
+    assert_eq!(grep("^$", ""), "");
+    assert_eq!(grep("^$", "\n\n"), "\n\n");
+}
+
+#[test]
+fn lines_are_matched_alone() {
+    // `$` anchors at each line's end, '\r' is part of the line, and no
+    // match spans a newline.
+    assert_eq!(grep("a$", "ba\nab\n"), "ba\n");
+    assert_eq!(grep("a$", "a\r\n"), "");
+    assert_eq!(grep("a\\sb", "a\nb\na b\n"), "a b\n");
+}
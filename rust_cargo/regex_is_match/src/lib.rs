@@ -0,0 +1,14 @@
+//! `regex_is_match` input handling, callable in-process (e.g. by the fuzz
+//! targets) as well as from the binary.
+
+/// Runs the benchmark on a framed input (pattern, then text, optionally
+/// with regex flags), returning the bytes the binary writes to stdout: 1 or
+/// 0 as a u32 LE.
+pub fn run(input: &[u8]) -> Result<Vec<u8>, proto::Error> {
+    let (flags, frames) = proto::read_frames_flagged(input, 1, proto::regex_flags::ALL)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let text = proto::frame_str(&frames, 1)?;
+
+    let re = proto::regex_flags::build(pattern, flags)?;
+    Ok((re.is_match(text) as u32).to_le_bytes().to_vec())
+}
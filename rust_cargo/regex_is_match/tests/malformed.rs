@@ -0,0 +1,4 @@
+//! Malformed input exits with status 2, a one-line diagnostic on stderr,
+//! and nothing on stdout.
+
+proto::pattern_text_malformed_tests!(env!("CARGO_BIN_EXE_regex_is_match"));
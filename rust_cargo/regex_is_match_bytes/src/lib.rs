@@ -0,0 +1,15 @@
+//! `regex_is_match` over raw bytes: the same input and output as
+//! `regex_is_match`, but the text is matched with `regex::bytes` and never
+//! validated as UTF-8. The pattern must still be UTF-8.
+
+/// Runs the benchmark on a framed input (pattern, then text, optionally
+/// with regex flags), returning the bytes the binary writes to stdout: 1 or
+/// 0 as a u32 LE.
+pub fn run(input: &[u8]) -> Result<Vec<u8>, proto::Error> {
+    let (flags, frames) = proto::read_frames_flagged(input, 1, proto::regex_flags::ALL)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let text = frames[1];
+
+    let re = proto::regex_flags::build_bytes(pattern, flags)?;
+    Ok((re.is_match(text) as u32).to_le_bytes().to_vec())
+}
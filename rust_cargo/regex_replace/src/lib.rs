@@ -0,0 +1,26 @@
+//! `regex_replace` input handling, callable in-process (e.g. by the fuzz
+//! targets) as well as from the binary.
+
+use regex::NoExpand;
+
+/// Runs the benchmark on a framed input (pattern, replacement, then text,
+/// optionally with regex flags), returning the bytes the binary writes to
+/// stdout: the text with every match replaced.
+///
+/// The replacement is expanded (`$1`, `${name}`, `$$`) unless the frame sets
+/// [`proto::regex_flags::LITERAL_REPLACEMENT`]; flagless inputs expand.
+pub fn run(input: &[u8]) -> Result<Vec<u8>, proto::Error> {
+    use proto::regex_flags::{ALL, LITERAL_REPLACEMENT};
+    let (flags, frames) = proto::read_frames_flagged(input, 2, ALL | LITERAL_REPLACEMENT)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let replacement = proto::frame_str(&frames, 1)?;
+    let text = proto::frame_str(&frames, 2)?;
+
+    let re = proto::regex_flags::build(pattern, flags)?;
+    let replaced = if flags & LITERAL_REPLACEMENT != 0 {
+        re.replace_all(text, NoExpand(replacement))
+    } else {
+        re.replace_all(text, replacement)
+    };
+    Ok(replaced.into_owned().into_bytes())
+}
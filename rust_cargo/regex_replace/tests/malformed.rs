@@ -0,0 +1,57 @@
+//! Malformed input exits with status 2, a one-line diagnostic on stderr,
+//! and nothing on stdout.
+
+fn assert_rejected(input: &[u8], diagnostic: &str) {
+    proto::testing::assert_rejected(env!("CARGO_BIN_EXE_regex_replace"), input, diagnostic);
+}
+
+#[test]
+fn truncated_header() {
+    assert_rejected(b"", "truncated header: need 8 bytes, got 0");
+    assert_rejected(
+        &[1, 0, 0, 0, 1, 0, 0],
+        "truncated header: need 8 bytes, got 7",
+    );
+}
+
+#[test]
+fn pattern_length_past_end() {
+    let mut input = proto::write_frames(&[b"ab", b"x"], b"text");
+    input[0] = 200;
+    assert_rejected(&input, "field 0 ends at byte 208, past end of input (15)");
+    let mut input = u32::MAX.to_le_bytes().to_vec();
+    input.extend_from_slice(&[0; 4]);
+    let end = 8 + u32::MAX as usize;
+    assert_rejected(
+        &input,
+        &format!("field 0 ends at byte {end}, past end of input (8)"),
+    );
+    // The replacement's length is the one that overflows.
+    let mut input = proto::write_frames(&[b"ab", b"x"], b"");
+    input[4] = 9;
+    assert_rejected(&input, "field 1 ends at byte 19, past end of input (11)");
+}
+
+#[test]
+fn non_utf8_fields() {
+    assert_rejected(
+        &proto::write_frames(&[b"\xff", b"x"], b"text"),
+        "field 0 is not valid UTF-8",
+    );
+    assert_rejected(
+        &proto::write_frames(&[b"a", b"\xff"], b"text"),
+        "field 1 is not valid UTF-8",
+    );
+    assert_rejected(
+        &proto::write_frames(&[b"a", b"x"], b"\xc3"),
+        "field 2 is not valid UTF-8",
+    );
+}
+
+#[test]
+fn invalid_pattern() {
+    assert_rejected(
+        &proto::write_frames(&[b"(", b"x"], b"text"),
+        "invalid pattern",
+    );
+}
@@ -0,0 +1,56 @@
+//! Replacement semantics in both modes: expanded by default, or taken
+//! verbatim when the frame sets `LITERAL_REPLACEMENT`.
+
+use proto::regex_flags::LITERAL_REPLACEMENT;
+
+fn replace(pattern: &str, replacement: &str, text: &str, flags: Option<u8>) -> String {
+    let fields: [&[u8]; 2] = [pattern.as_bytes(), replacement.as_bytes()];
+    let input = match flags {
+        Some(flags) => proto::write_frames_v3(&fields, flags, text.as_bytes()),
+        None => proto::write_frames(&fields, text.as_bytes()),
+    };
+    String::from_utf8(proto::testing::run(
+        env!("CARGO_BIN_EXE_regex_replace"),
+        &input,
+    ))
+    .unwrap()
+}
+
+const PATTERN: &str = "(?P<word>[a-z]+)@([0-9]+)";
+const TEXT: &str = "ab@12 cd@3!";
+const REFERENCES: &str = "<$0|$1|${word}|${2}x|$$>";
+
+#[test]
+fn expanded() {
+    let expanded = "<ab@12|ab|ab|12x|$> <cd@3|cd|cd|3x|$>!";
+    // Flagless inputs, and v3 ones without the flag, expand.
+    assert_eq!(replace(PATTERN, REFERENCES, TEXT, None), expanded);
+    assert_eq!(replace(PATTERN, REFERENCES, TEXT, Some(0)), expanded);
+    // `$1a` names a group "1a", which doesn't exist, so expands to nothing;
+    // braces end the name.
+    assert_eq!(replace("([a-z]+)", "$1a", "xy", None), "");
+    assert_eq!(replace("([a-z]+)", "${1}a", "xy", None), "xya");
+    // An unmatched group expands to nothing as well.
+    assert_eq!(replace("a(b)?", "[$1]", "ab a", None), "[b] []");
+}
+
+#[test]
+fn literal() {
+    let literal = format!("{REFERENCES} {REFERENCES}!");
+    assert_eq!(
+        replace(PATTERN, REFERENCES, TEXT, Some(LITERAL_REPLACEMENT)),
+        literal
+    );
+    assert_eq!(
+        replace("([a-z]+)", "$1a", "xy", Some(LITERAL_REPLACEMENT)),
+        "$1a"
+    );
+}
+
+#[test]
+fn no_match_leaves_the_text() {
+    for flags in [None, Some(LITERAL_REPLACEMENT)] {
+        assert_eq!(replace(PATTERN, "$0$0", "no digits", flags), "no digits");
+        assert_eq!(replace(PATTERN, "$0$0", "", flags), "");
+    }
+}
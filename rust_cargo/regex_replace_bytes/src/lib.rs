@@ -0,0 +1,28 @@
+//! `regex_replace` over raw bytes: the same input as `regex_replace`, but
+//! the text and replacement are handled with `regex::bytes` and never
+//! validated as UTF-8. The pattern must still be UTF-8. Bytes outside any
+//! match, valid UTF-8 or not, are copied to the output unchanged.
+
+use regex::bytes::NoExpand;
+
+/// Runs the benchmark on a framed input (pattern, replacement, then text,
+/// optionally with regex flags), returning the bytes the binary writes to
+/// stdout: the text with every match replaced.
+///
+/// The replacement is expanded (`$1`, `${name}`, `$$`) unless the frame sets
+/// [`proto::regex_flags::LITERAL_REPLACEMENT`]; flagless inputs expand.
+pub fn run(input: &[u8]) -> Result<Vec<u8>, proto::Error> {
+    use proto::regex_flags::{ALL, LITERAL_REPLACEMENT};
+    let (flags, frames) = proto::read_frames_flagged(input, 2, ALL | LITERAL_REPLACEMENT)?;
+    let pattern = proto::frame_str(&frames, 0)?;
+    let replacement = frames[1];
+    let text = frames[2];
+
+    let re = proto::regex_flags::build_bytes(pattern, flags)?;
+    let replaced = if flags & LITERAL_REPLACEMENT != 0 {
+        re.replace_all(text, NoExpand(replacement))
+    } else {
+        re.replace_all(text, replacement)
+    };
+    Ok(replaced.into_owned())
+}
@@ -0,0 +1,10 @@
+use std::io::{Read, Write};
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let output = regex_replace_bytes::run(&input).unwrap_or_else(|e| proto::exit_malformed(&e));
+
+    std::io::stdout().write_all(&output).unwrap();
+}
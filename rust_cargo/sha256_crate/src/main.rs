@@ -0,0 +1,11 @@
+use std::io::{Read, Write};
+use sha2::{Digest, Sha256};
+
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let digest = Sha256::digest(&input);
+
+    std::io::stdout().write_all(&digest).unwrap();
+}
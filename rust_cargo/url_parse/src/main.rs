@@ -0,0 +1,39 @@
+use std::io::{Read, Write};
+use url::Url;
+
+// Input: newline-separated URLs (a final newline is optional). Each line is
+// parsed with the url crate's Url::parse, the WHATWG URL Standard: absolute
+// URLs only, hosts of special schemes IDNA-mapped to ASCII, paths
+// normalized ("..", an empty http path becomes "/") and percent-encoded.
+// Output: four u64 LE counts over the lines that parse: how many, how many
+// with scheme https, the total length of Url::path, and the total number of
+// query parameters from Url::query_pairs (&-separated, empty pieces
+// skipped, so "?=" is one parameter and "a&&b&" two).
+//
+// goldens/url_parse_fixture.txt covers userinfo, IPv6 hosts, empty paths,
+// IDNA, non-special schemes and five rejected lines (an IPv6 zone id, a
+// space in the host, port 99999, no scheme, an empty host); it gives 15
+// valid, 9 https, 91 path bytes and 9 parameters, pinned in
+// golden_outputs.json.
+fn main() {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input).unwrap();
+
+    let (mut valid, mut https, mut path_bytes, mut params) = (0u64, 0u64, 0u64, 0u64);
+    for line in input.split_inclusive(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let Some(url) = std::str::from_utf8(line).ok().and_then(|s| Url::parse(s).ok()) else {
+            continue;
+        };
+        valid += 1;
+        https += (url.scheme() == "https") as u64;
+        path_bytes += url.path().len() as u64;
+        params += url.query_pairs().count() as u64;
+    }
+
+    let mut output = Vec::with_capacity(32);
+    for count in [valid, https, path_bytes, params] {
+        output.extend_from_slice(&count.to_le_bytes());
+    }
+    std::io::stdout().write_all(&output).unwrap();
+}
@@ -0,0 +1,291 @@
+"""
+Inline SVG charts for the HTML report.
+
+Each function returns one standalone <svg> element as a string, with no
+scripts, stylesheets or external references, so a report can embed any
+number of them and still be a single file that opens offline.
+"""
+from __future__ import annotations
+
+import math
+from html import escape
+from typing import Callable
+
+# One colour per implementation, so a language looks the same in every chart.
+LANGUAGE_COLORS = {
+    "X07": "#7b3fe4",
+    "C": "#555555",
+    "Rust": "#d9622b",
+    "Go": "#1f9bcf",
+}
+FALLBACK_COLOR = "#999999"
+
+FONT = 'font-family="sans-serif" font-size="12"'
+
+
+def color_for(label: str) -> str:
+    return LANGUAGE_COLORS.get(label, FALLBACK_COLOR)
+
+
+def _open(title: str, width: float, height: float) -> list[str]:
+    """The <svg> start tag plus an accessible <title> and the visible title."""
+    return [
+        f'<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" '
+        f'viewBox="0 0 {width} {height}" role="img">',
+        f"<title>{escape(title)}</title>",
+        f'<text x="0" y="14" {FONT} font-weight="bold">{escape(title)}</text>',
+    ]
+
+
+def _text(x: float, y: float, text: str, anchor: str = "start", attrs: str = "") -> str:
+    attrs = f" {attrs}" if attrs else ""
+    return (f'<text x="{x:.2f}" y="{y:.2f}" text-anchor="{anchor}" {FONT}{attrs}>'
+            f"{escape(text)}</text>")
+
+
+def _legend(labels: list[str], x: float, y: float) -> list[str]:
+    """A colour swatch and name per label, one per 16px row from (x, y)."""
+    parts = []
+    for i, label in enumerate(labels):
+        row = y + i * 16
+        parts.append(f'<rect class="legend" x="{x:.2f}" y="{row - 9:.2f}" width="10" '
+                     f'height="10" fill="{color_for(label)}"/>')
+        parts.append(_text(x + 14, row, label))
+    return parts
+
+
+def log_ticks(lo: float, hi: float, max_ticks: int = 8) -> list[float]:
+    """Tick values for a log axis over [lo, hi] (both positive): 1, 2 and 5
+    times each power of ten in range, thinned to powers of ten alone when
+    that gives more than max_ticks. A range holding fewer than two of those
+    gets its own ends instead, so every axis has at least one labelled tick.
+
+    log_ticks(1, 100) is [1, 2, 5, 10, 20, 50, 100]; log_ticks(1, 10**6) is
+    [1, 10, ..., 10**6]; log_ticks(3, 4) is [3, 4].
+    """
+    first, last = math.floor(math.log10(lo)), math.ceil(math.log10(hi))
+    # A relative slack so that ticks landing exactly on lo or hi survive rounding.
+    inside = lambda t: lo * (1 - 1e-9) <= t <= hi * (1 + 1e-9)  # noqa: E731
+    ticks = [m * 10.0 ** e for e in range(first, last + 1) for m in (1, 2, 5)]
+    ticks = [t for t in ticks if inside(t)]
+    if len(ticks) > max_ticks:
+        ticks = [t for t in ticks if math.log10(t) == round(math.log10(t))]
+    if len(ticks) < 2:
+        ticks = sorted({lo, hi})
+    return ticks
+
+
+def _fmt(value: float) -> str:
+    """A tick or bar value with three significant digits, e.g. 1.23, 45.6, 789."""
+    if value == 0:
+        return "0"
+    digits = max(0, 2 - int(math.floor(math.log10(abs(value)))))
+    return f"{value:.{digits}f}"
+
+
+def bar_chart(
+    title: str,
+    bars: list[tuple[str, float]],
+    unit: str,
+    notes: dict[str, str] | None = None,
+    width: int = 520,
+) -> str:
+    """Horizontal bars, one per (label, value), each as long as its value
+    relative to the largest, labelled on the left and annotated on the right
+    with the value and notes[label] (e.g. a change against a baseline).
+
+    The x axis starts at zero and is titled with `unit`.
+    """
+    label_w, value_w, bar_h, gap, top, axis_h = 60, 130, 18, 6, 24, 30
+    plot_w = width - label_w - value_w
+    height = top + len(bars) * (bar_h + gap) + axis_h
+    largest = max((v for _, v in bars), default=0.0)
+
+    parts = _open(title, width, height)
+    for i, (label, value) in enumerate(bars):
+        y = top + i * (bar_h + gap)
+        length = plot_w * value / largest if largest > 0 else 0.0
+        note = (notes or {}).get(label, "")
+        parts.append(
+            f'<text x="{label_w - 6}" y="{y + bar_h - 5}" text-anchor="end" {FONT}>'
+            f"{escape(label)}</text>"
+        )
+        parts.append(
+            f'<rect class="bar" x="{label_w}" y="{y}" width="{length:.2f}" height="{bar_h}" '
+            f'fill="{color_for(label)}" data-label="{escape(label)}" data-value="{value}"/>'
+        )
+        parts.append(
+            f'<text x="{label_w + length + 4:.2f}" y="{y + bar_h - 5}" {FONT}>'
+            f"{escape(_fmt(value))}{escape(' ' + note if note else '')}</text>"
+        )
+
+    axis_y = top + len(bars) * (bar_h + gap)
+    parts.append(
+        f'<line x1="{label_w}" y1="{axis_y}" x2="{label_w + plot_w}" y2="{axis_y}" '
+        'stroke="#333"/>'
+    )
+    for frac in (0.0, 0.5, 1.0):
+        x = label_w + plot_w * frac
+        parts.append(
+            f'<text x="{x:.2f}" y="{axis_y + 14}" text-anchor="middle" {FONT}>'
+            f"{escape(_fmt(largest * frac))}</text>"
+        )
+    parts.append(
+        f'<text class="axis-label" x="{label_w + plot_w / 2:.2f}" y="{axis_y + 28}" '
+        f'text-anchor="middle" {FONT}>{escape(unit)}</text>'
+    )
+    parts.append("</svg>")
+    return "\n".join(parts)
+
+
+def line_chart(
+    title: str,
+    series: dict[str, list[tuple[float, float]]],
+    x_label: str,
+    y_label: str,
+    width: int = 520,
+    height: int = 300,
+) -> str:
+    """One polyline per series of (x, y) points, with a log2 x axis (input
+    sizes from a sweep double or more per step) and a linear y axis from
+    zero. Each point is a <circle>, and a legend names the series.
+
+    x values must be positive; points are drawn in x order.
+    """
+    left, right, top, bottom = 60, 90, 24, 44
+    plot_w, plot_h = width - left - right, height - top - bottom
+    points = [p for pts in series.values() for p in pts]
+    xs = [math.log2(x) for x, _ in points] or [0.0]
+    x_min, x_max = min(xs), max(xs)
+    y_max = max((y for _, y in points), default=0.0)
+
+    def px(x: float) -> float:
+        if x_max == x_min:
+            return left + plot_w / 2
+        return left + plot_w * (math.log2(x) - x_min) / (x_max - x_min)
+
+    def py(y: float) -> float:
+        return top + plot_h - (plot_h * y / y_max if y_max > 0 else 0.0)
+
+    parts = _open(title, width, height) + [
+        f'<line x1="{left}" y1="{top + plot_h}" x2="{left + plot_w}" y2="{top + plot_h}" '
+        'stroke="#333"/>',
+        f'<line x1="{left}" y1="{top}" x2="{left}" y2="{top + plot_h}" stroke="#333"/>',
+    ]
+    for x in sorted({x for x, _ in points}):
+        parts.append(
+            f'<text x="{px(x):.2f}" y="{top + plot_h + 14}" text-anchor="middle" {FONT}>'
+            f"{escape(_fmt(x))}</text>"
+        )
+    for frac in (0.0, 0.5, 1.0):
+        parts.append(
+            f'<text x="{left - 6}" y="{py(y_max * frac) + 4:.2f}" text-anchor="end" {FONT}>'
+            f"{escape(_fmt(y_max * frac))}</text>"
+        )
+    parts.append(
+        f'<text class="axis-label" x="{left + plot_w / 2:.2f}" y="{height - 6}" '
+        f'text-anchor="middle" {FONT}>{escape(x_label)}</text>'
+    )
+    parts.append(
+        f'<text class="axis-label" x="12" y="{top + plot_h / 2:.2f}" text-anchor="middle" '
+        f'transform="rotate(-90 12 {top + plot_h / 2:.2f})" {FONT}>{escape(y_label)}</text>'
+    )
+
+    for label, pts in series.items():
+        color = color_for(label)
+        pts = sorted(pts)
+        coords = " ".join(f"{px(x):.2f},{py(y):.2f}" for x, y in pts)
+        parts.append(
+            f'<polyline class="series" points="{coords}" fill="none" stroke="{color}" '
+            f'stroke-width="2" data-label="{escape(label)}"/>'
+        )
+        for x, y in pts:
+            parts.append(f'<circle cx="{px(x):.2f}" cy="{py(y):.2f}" r="3" fill="{color}"/>')
+    parts += _legend(list(series), left + plot_w + 10, top + 10)
+    parts.append("</svg>")
+    return "\n".join(parts)
+
+
+def log_log_chart(
+    title: str,
+    series: dict[str, list[tuple[float, float, float, float]]],
+    x_label: str,
+    y_label: str,
+    x_format: Callable[[float], str] = _fmt,
+    width: int = 640,
+    height: int = 400,
+) -> str:
+    """One polyline per series of (x, y, y_low, y_high) points on log x and
+    log y axes, each point a <circle> with a vertical error bar from y_low
+    to y_high. Every value must be positive.
+
+    x ticks are the measured x values (formatted with x_format) when there
+    are at most ten, else log_ticks; y ticks are log_ticks over the error
+    bars' range, with grid lines. A series with a single point is still
+    drawn and gets its y value written beside it, since there is no line to
+    read it from; a chart whose points all share one x (or one y) is
+    centred on it with a decade either side.
+    """
+    left, right, top, bottom = 70, 110, 28, 46
+    plot_w = width - left - right
+    height = max(height, top + 16 * len(series) + bottom)
+    plot_h = height - top - bottom
+    points = [p for pts in series.values() for p in pts]
+
+    def span(values: list[float]) -> tuple[float, float]:
+        lo, hi = min(values, default=1.0), max(values, default=1.0)
+        if lo == hi:
+            return lo / 10, hi * 10
+        return lo, hi
+
+    x_lo, x_hi = span([x for x, _, _, _ in points])
+    y_lo, y_hi = span([v for _, y, lo, hi in points for v in (y, lo, hi)])
+    # A little headroom so points and bars at the ends aren't cut by the frame.
+    x_lo, x_hi = x_lo / 1.15, x_hi * 1.15
+    y_lo, y_hi = y_lo / 1.25, y_hi * 1.25
+
+    def px(x: float) -> float:
+        return left + plot_w * math.log(x / x_lo) / math.log(x_hi / x_lo)
+
+    def py(y: float) -> float:
+        return top + plot_h - plot_h * math.log(y / y_lo) / math.log(y_hi / y_lo)
+
+    parts = _open(title, width, height)
+    measured_x = sorted({x for x, _, _, _ in points})
+    x_ticks = measured_x if len(measured_x) <= 10 else log_ticks(x_lo, x_hi)
+    for x in x_ticks:
+        parts.append(f'<line class="grid" x1="{px(x):.2f}" y1="{top}" x2="{px(x):.2f}" '
+                     f'y2="{top + plot_h}" stroke="#e4e4e4"/>')
+        parts.append(_text(px(x), top + plot_h + 14, x_format(x), "middle", 'class="tick"'))
+    for y in log_ticks(y_lo, y_hi):
+        parts.append(f'<line class="grid" x1="{left}" y1="{py(y):.2f}" x2="{left + plot_w}" '
+                     f'y2="{py(y):.2f}" stroke="#e4e4e4"/>')
+        parts.append(_text(left - 6, py(y) + 4, _fmt(y), "end", 'class="tick"'))
+    parts.append(f'<rect x="{left}" y="{top}" width="{plot_w}" height="{plot_h}" '
+                 'fill="none" stroke="#333"/>')
+    parts.append(_text(left + plot_w / 2, height - 8, x_label, "middle", 'class="axis-label"'))
+    mid = top + plot_h / 2
+    parts.append(_text(14, mid, y_label, "middle",
+                       f'class="axis-label" transform="rotate(-90 14 {mid:.2f})"'))
+
+    for label, pts in series.items():
+        color = color_for(label)
+        pts = sorted(pts)
+        if len(pts) > 1:
+            coords = " ".join(f"{px(x):.2f},{py(y):.2f}" for x, y, _, _ in pts)
+            parts.append(f'<polyline class="series" points="{coords}" fill="none" '
+                         f'stroke="{color}" stroke-width="2" data-label="{escape(label)}"/>')
+        for x, y, lo, hi in pts:
+            cx = px(x)
+            parts.append(f'<line class="error-bar" x1="{cx:.2f}" y1="{py(lo):.2f}" '
+                         f'x2="{cx:.2f}" y2="{py(hi):.2f}" stroke="{color}"/>')
+            for cap in (lo, hi):
+                parts.append(f'<line x1="{cx - 4:.2f}" y1="{py(cap):.2f}" x2="{cx + 4:.2f}" '
+                             f'y2="{py(cap):.2f}" stroke="{color}"/>')
+            parts.append(f'<circle class="point" cx="{cx:.2f}" cy="{py(y):.2f}" r="3" '
+                         f'fill="{color}" data-label="{escape(label)}"/>')
+            if len(pts) == 1:
+                parts.append(_text(cx + 8, py(y) - 6, f"{label} {_fmt(y)}", attrs='class="note"'))
+    parts += _legend(list(series), left + plot_w + 12, top + 10)
+    parts.append("</svg>")
+    return "\n".join(parts)
@@ -0,0 +1,113 @@
+import unittest
+
+import bench_stats
+from bench_stats import (
+    CONVERGED, MAD_SCALE, MAD_Z_THRESHOLD, MAX_SAMPLES, TIME_BUDGET, AdaptivePolicy,
+    bootstrap_median_ci, mad_filter, median_ci_fraction, summarize,
+)
+
+
+class SummarizeTest(unittest.TestCase):
+    def test_median(self):
+        s = summarize([3.0, 1.0, 2.0])
+        self.assertEqual(s.median, 2.0)
+        self.assertEqual((s.samples, s.outliers, s.min, s.max), (3, 0, 1.0, 3.0))
+        self.assertEqual(s.mean, 2.0)
+
+    def test_even_length_median(self):
+        # The mean of the two middle samples, not either one.
+        self.assertEqual(summarize([4.0, 1.0, 3.0, 2.0]).median, 2.5)
+
+    def test_single_sample(self):
+        s = summarize([7.0])
+        self.assertEqual((s.samples, s.outliers), (1, 0))
+        self.assertEqual((s.mean, s.median, s.min, s.max), (7.0, 7.0, 7.0, 7.0))
+        self.assertEqual(s.stddev, 0.0)
+        self.assertFalse(s.unstable)
+
+    def test_no_samples(self):
+        self.assertEqual(summarize([]), bench_stats.EMPTY)
+
+    def test_all_equal_samples(self):
+        # MAD == 0: nothing is rejected, even a sample far from the rest.
+        self.assertEqual(mad_filter([5.0] * 4), ([5.0] * 4, 0))
+        self.assertEqual(mad_filter([5.0, 5.0, 5.0, 500.0]), ([5.0, 5.0, 5.0, 500.0], 0))
+        s = summarize([5.0] * 4)
+        self.assertEqual((s.samples, s.outliers, s.median, s.stddev), (4, 0, 5.0, 0.0))
+
+    def test_mad_rejection_at_threshold(self):
+        # With x above 12, [8, 9, 10, 11, 12, x] has median 10.5 and MAD 1.5
+        # whatever x is, so x's modified z-score is MAD_SCALE * (x - 10.5) / 1.5.
+        base = [8.0, 9.0, 10.0, 11.0, 12.0]
+        cutoff = 10.5 + MAD_Z_THRESHOLD * 1.5 / MAD_SCALE
+        kept, outliers = mad_filter(base + [cutoff - 1e-9])
+        self.assertEqual((kept, outliers), (base + [cutoff - 1e-9], 0))
+        kept, outliers = mad_filter(base + [cutoff + 1e-9])
+        self.assertEqual((kept, outliers), (base, 1))
+
+        s = summarize(base + [cutoff + 1e-9])
+        self.assertEqual((s.samples, s.outliers, s.median, s.max), (5, 1, 10.0, 12.0))
+
+    def test_too_few_samples_to_reject(self):
+        self.assertEqual(mad_filter([1.0, 1000.0]), ([1.0, 1000.0], 0))
+
+    def test_unstable(self):
+        self.assertTrue(summarize([10.0, 12.0, 14.0]).unstable)
+        self.assertFalse(summarize([10.0, 10.1, 10.2]).unstable)
+
+
+class AdaptiveTest(unittest.TestCase):
+    # Alternating modes: the bootstrap median swings between them, so the
+    # interval stays wide however many samples there are.
+    BIMODAL = [10.0, 20.0] * 100
+
+    def test_converges_on_tight_samples(self):
+        policy = AdaptivePolicy(target=0.02, min_samples=5)
+        self.assertIsNone(policy.stop([10.0] * 4, 0.0))
+        self.assertEqual(policy.stop([10.0] * 5, 0.0), CONVERGED)
+        self.assertEqual(policy.stop([10.0, 10.1, 9.9, 10.0, 10.05, 9.95], 0.0), CONVERGED)
+
+    def test_spread_samples_keep_sampling(self):
+        policy = AdaptivePolicy(target=0.02, min_samples=5, max_samples=200)
+        self.assertIsNone(policy.stop(self.BIMODAL[:50], 0.0))
+
+    def test_max_samples_cap(self):
+        policy = AdaptivePolicy(target=0.02, min_samples=5, max_samples=40)
+        self.assertIsNone(policy.stop(self.BIMODAL[:39], 0.0))
+        self.assertEqual(policy.stop(self.BIMODAL[:40], 0.0), MAX_SAMPLES)
+        self.assertEqual(policy.stop(self.BIMODAL[:41], 0.0), MAX_SAMPLES)
+
+    def test_converging_on_the_last_sample_counts(self):
+        policy = AdaptivePolicy(min_samples=5, max_samples=5)
+        self.assertEqual(policy.stop([10.0] * 5, 0.0), CONVERGED)
+
+    def test_time_budget(self):
+        policy = AdaptivePolicy(min_samples=5, max_seconds=1.0)
+        self.assertIsNone(policy.stop(self.BIMODAL[:10], 0.99))
+        self.assertEqual(policy.stop(self.BIMODAL[:10], 1.0), TIME_BUDGET)
+        # Convergence takes precedence over the budget.
+        self.assertEqual(policy.stop([10.0] * 5, 5.0), CONVERGED)
+
+    def test_bootstrap_is_deterministic(self):
+        samples = [10.0 + (i * 37 % 101) / 100 for i in range(30)]
+        ci = bootstrap_median_ci(samples)
+        self.assertEqual(bootstrap_median_ci(list(samples)), ci)
+        self.assertEqual(bootstrap_median_ci(samples, seed=bench_stats.BOOTSTRAP_SEED), ci)
+        low, high = ci
+        self.assertLessEqual(low, summarize(samples).median)
+        self.assertLessEqual(summarize(samples).median, high)
+        other = [bootstrap_median_ci(samples, seed=seed) for seed in range(5)]
+        self.assertNotEqual(set(other), {ci})
+
+    def test_ci_fraction(self):
+        self.assertEqual(median_ci_fraction([5.0] * 10), 0.0)
+        self.assertEqual(median_ci_fraction([0.0] * 10), 0.0)
+        self.assertEqual(median_ci_fraction([0.0, 0.0, 0.0, 1.0, 1.0]), float("inf"))
+        self.assertGreater(median_ci_fraction(self.BIMODAL[:20]), 0.02)
+
+    def test_describe(self):
+        self.assertEqual(AdaptivePolicy().describe(), "adaptive 0.02 5-200 30s")
+
+
+if __name__ == "__main__":
+    unittest.main()
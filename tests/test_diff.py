@@ -0,0 +1,147 @@
+import struct
+import subprocess
+import unittest
+from pathlib import Path
+from types import SimpleNamespace
+from unittest import mock
+
+import run_benchmarks
+from run_benchmarks import InputData, diff_benchmark, hex_context, output_mismatch
+from test_results import cli
+
+
+def f64s(*values: float) -> bytes:
+    return struct.pack(f"<{len(values)}d", *values)
+
+
+class OutputMismatchTest(unittest.TestCase):
+    def test_same(self):
+        self.assertIsNone(output_mismatch(b"abc", b"abc"))
+        self.assertIsNone(output_mismatch(b"", b""))
+
+    def test_first_differing_byte(self):
+        self.assertEqual(output_mismatch(b"abcd", b"abXd"),
+                         (2, "stdout differs at byte 2 (rust 4 bytes, x07 4 bytes)"))
+
+    def test_prefix(self):
+        # One output a prefix of the other differs where the shorter ends.
+        self.assertEqual(output_mismatch(b"abc", b"abcdef"),
+                         (3, "stdout differs at byte 3 (rust 3 bytes, x07 6 bytes)"))
+        self.assertEqual(output_mismatch(b"ab", b"")[0], 0)
+
+    def test_epsilon(self):
+        self.assertIsNone(output_mismatch(f64s(1.0, 1e6), f64s(1.0 + 1e-13, 1e6 + 1e-7), 1e-12))
+        self.assertEqual(output_mismatch(f64s(1.0, 2.0), f64s(1.0, 2.001), 1e-12),
+                         (8, "stdout differs at f64 1 by more than 1e-12 (relative)"))
+        # Below 1 the tolerance is absolute.
+        self.assertIsNone(output_mismatch(f64s(0.0), f64s(1e-13), 1e-12))
+        # Without the epsilon the same outputs differ byte for byte.
+        self.assertIsNotNone(output_mismatch(f64s(1.0), f64s(1.0 + 1e-13)))
+        # A length that is not whole f64s is compared byte for byte.
+        self.assertEqual(output_mismatch(b"abc", b"abd", 1.0)[0], 2)
+
+    def test_byte_tolerance(self):
+        self.assertIsNone(output_mismatch(bytes([10, 20, 30]), bytes([11, 19, 30]),
+                                          byte_tolerance=1))
+        self.assertEqual(output_mismatch(bytes([10, 20, 30]), bytes([11, 22, 30]),
+                                         byte_tolerance=1),
+                         (1, "stdout differs at byte 1 by more than 1"))
+        # Lengths must match for a tolerance to apply.
+        self.assertEqual(output_mismatch(bytes([10]), bytes([10, 0]), byte_tolerance=255)[0], 1)
+
+
+class HexContextTest(unittest.TestCase):
+    def test_marks_the_offset(self):
+        data = bytes(range(0x20))
+        self.assertEqual(hex_context(data, 0x10, radius=2),
+                         "0000000e: 0e 0f [10] 11")
+        self.assertEqual(hex_context(b"abc", 0, radius=2), "00000000: [61] 62")
+
+    def test_end_of_output(self):
+        self.assertEqual(hex_context(b"abc", 3, radius=2), "00000001: 62 63 [EOF]")
+        self.assertEqual(hex_context(b"", 0), "00000000: [EOF]")
+
+
+class DiffBenchmarkTest(unittest.TestCase):
+    """diff_benchmark over canned outputs and exit statuses, without building anything."""
+
+    SPEC = SimpleNamespace(name="canned", implementations={"X07": Path("x"), "Rust": Path("r")},
+                           diff_epsilon=None, diff_byte_tolerance=None)
+
+    def diff(self, rust: tuple[int, bytes], x07: tuple[int, bytes]) -> list[str]:
+        rust_exit, rust_out = rust
+        with mock.patch.object(run_benchmarks, "_build_x07"), \
+                mock.patch.object(run_benchmarks, "_build_rust"), \
+                mock.patch.object(run_benchmarks, "_run_x07_binary", return_value=x07), \
+                mock.patch.object(run_benchmarks, "_run_child", return_value=subprocess
+                                  .CompletedProcess([], rust_exit, rust_out, b"")):
+            return diff_benchmark(self.SPEC, InputData(name="canned", data=b"in", size_kb=0),
+                                  Path("host"), Path("."), Path("tmp"))
+
+    def test_same(self):
+        self.assertEqual(self.diff((0, b"42\n"), (0, b"42\n")), [])
+
+    def test_stdout_differs(self):
+        self.assertEqual(self.diff((0, b"hello"), (0, b"help!")), [
+            "stdout differs at byte 3 (rust 5 bytes, x07 5 bytes)",
+            "  rust 00000000: 68 65 6c [6c] 6f",
+            "  x07  00000000: 68 65 6c [70] 21",
+        ])
+
+    def test_exit_status_differs(self):
+        self.assertEqual(self.diff((0, b""), (1, b"")), ["exit status differs: rust 0, x07 1"])
+
+    def test_both_differ(self):
+        problems = self.diff((2, b"ab"), (0, b"abc"))
+        self.assertEqual(problems[0], "exit status differs: rust 2, x07 0")
+        self.assertEqual(problems[1], "stdout differs at byte 2 (rust 2 bytes, x07 3 bytes)")
+        self.assertEqual(problems[2], "  rust 00000000: 61 62 [EOF]")
+
+
+class DiffCommandTest(unittest.TestCase):
+    """`diff` fails on unexpected differences and reports allowed ones."""
+
+    def run_diff(self, problems: dict[str, list[str]], allowed: dict[str, str]) -> tuple[int, str]:
+        real = run_benchmarks.load_manifest(run_benchmarks._perf_repo_root())
+        manifest = {name: dict(entry) for name, entry in real.items()}
+        for name, reason in allowed.items():
+            manifest[name]["diff_allowed"] = reason
+
+        def canned(spec, *args, **kwargs):
+            return problems[spec.name]
+
+        with mock.patch.object(run_benchmarks, "_resolve_x07_host_runner",
+                               return_value=Path("host")), \
+                mock.patch.object(run_benchmarks, "load_manifest", return_value=manifest), \
+                mock.patch.object(run_benchmarks, "diff_benchmark", side_effect=canned):
+            status, out, _ = cli("diff", "--size", "1", "--benchmarks", *problems)
+        return status, out
+
+    def test_all_same(self):
+        status, out = self.run_diff({"sum_bytes": [], "word_count": []}, {})
+        self.assertEqual(status, 0)
+        self.assertEqual(out, "sum_bytes: same\nword_count: same\n\nNo unexpected differences\n")
+
+    def test_unexpected_difference_fails(self):
+        status, out = self.run_diff({"sum_bytes": ["exit status differs: rust 0, x07 1"],
+                                     "word_count": []}, {})
+        self.assertEqual(status, 1)
+        self.assertIn("sum_bytes: DIFFERS\n  exit status differs: rust 0, x07 1\n", out)
+        self.assertTrue(out.endswith("\n1 benchmark(s) differ\n"))
+
+    def test_allowed_difference_passes(self):
+        status, out = self.run_diff({"sum_bytes": ["stdout differs at byte 0"]},
+                                    {"sum_bytes": "formats not aligned yet"})
+        self.assertEqual(status, 0)
+        self.assertIn("sum_bytes: differs (allowed: formats not aligned yet)\n", out)
+
+    def test_unpaired_benchmark_is_rejected(self):
+        with mock.patch.object(run_benchmarks, "_resolve_x07_host_runner",
+                               return_value=Path("host")):
+            status, _, err = cli("diff", "--benchmarks", "noop", "nope")
+        self.assertEqual(status, 2)
+        self.assertIn("no Rust and X07 pair for: nope", err)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,156 @@
+import hashlib
+import shutil
+import sys
+import tempfile
+import unittest
+from pathlib import Path
+
+import run_benchmarks
+from run_benchmarks import (
+    BenchmarkTimeout, InputData, _run_to_file, run_benchmark, set_child_timeout, store_output,
+)
+
+needs_cc = unittest.skipUnless(shutil.which("cc"), "cc not on PATH")
+needs_rustc = unittest.skipUnless(shutil.which("rustc"), "rustc not on PATH")
+
+
+def python(script: str) -> list[str]:
+    return [sys.executable, "-c", script]
+
+
+def sha256(data: bytes) -> str:
+    return hashlib.sha256(data).hexdigest()
+
+
+class RunToFileTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.out = Path(tmp.name) / "out"
+
+    def test_streams_stdout_through_the_hash(self):
+        # Several read chunks' worth, echoed back from stdin.
+        data = bytes(range(256)) * (3 * run_benchmarks._OUTPUT_CHUNK // 256 + 7)
+        script = "import sys; sys.stdout.buffer.write(sys.stdin.buffer.read())"
+        digest = _run_to_file(python(script), data, self.out)
+        self.assertEqual((digest.sha256, digest.size), (sha256(data), len(data)))
+        self.assertEqual(self.out.read_bytes(), data)
+
+    def test_length_prefixed(self):
+        script = ("import struct, sys; sys.stdout.buffer.write("
+                  "struct.pack('<I', 5) + b'hello' + b'ignored')")
+        digest = _run_to_file(python(script), b"", self.out, length_prefixed=True)
+        self.assertEqual((digest.sha256, self.out.read_bytes()), (sha256(b"hello"), b"hello"))
+
+    def test_length_prefixed_errors(self):
+        cases = [
+            ("sys.stdout.buffer.write(b'ab')", "X07 output too short: 2 bytes"),
+            ("sys.stdout.buffer.write(struct.pack('<I', 9) + b'abc')",
+             "X07 output truncated: expected 9, got 3"),
+        ]
+        for write, message in cases:
+            with self.subTest(message):
+                with self.assertRaisesRegex(RuntimeError, f"^{message}$"):
+                    _run_to_file(python(f"import struct, sys; {write}"), b"", self.out,
+                                 length_prefixed=True)
+
+    def test_failure_reports_stderr(self):
+        script = "import sys; sys.stderr.write('bad input'); sys.exit(2)"
+        with self.assertRaisesRegex(RuntimeError, r"^execution failed \(exit 2\): bad input$"):
+            _run_to_file(python(script), b"", self.out)
+
+    def test_child_that_ignores_stdin(self):
+        # A large input the child never reads neither blocks nor fails the run.
+        digest = _run_to_file(python("print('done')"), bytes(8 << 20), self.out)
+        self.assertEqual(self.out.read_bytes(), b"done\n")
+        self.assertEqual(digest.size, 5)
+
+    def test_timeout(self):
+        set_child_timeout(0.5)
+        self.addCleanup(set_child_timeout, run_benchmarks.DEFAULT_TIMEOUT_S)
+        with self.assertRaises(BenchmarkTimeout):
+            _run_to_file(python("import time; time.sleep(60)"), b"", self.out)
+
+
+C_PROGRAM = '#include <stdio.h>\nint main(void) { fputs("%s", stdout); return 0; }\n'
+RUST_PROGRAM = 'fn main() { print!("%s"); }\n'
+
+
+@needs_cc
+class GoldenMismatchTest(unittest.TestCase):
+    """Mismatches always report the first differing byte when any output
+    with the expected hash is at hand, from this run or the output store."""
+
+    EXPECTED = "hello world"
+
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        self.store = self.dir / "outputs"
+        self.programs = 0
+
+    def program(self, language: str, output: str) -> Path:
+        self.programs += 1
+        template, suffix = (C_PROGRAM, "c") if language == "C" else (RUST_PROGRAM, "rs")
+        path = self.dir / f"p{self.programs}.{suffix}"
+        path.write_text(template % output)
+        return path
+
+    def run_with(self, **outputs: str) -> dict[str, str]:
+        """Each implementation's error for these outputs, by language."""
+        work = self.dir / f"work{self.programs}"
+        work.mkdir()
+        results = run_benchmark(
+            "hello", InputData(name="hello", data=b"", size_kb=0), self.dir / "no-x07", self.dir,
+            work, iterations=1, warmup=0,
+            implementations={lang: self.program(lang, out) for lang, out in outputs.items()},
+            output_sha256=sha256(self.EXPECTED.encode()), output_store=self.store)
+        return {r.language: r.error for r in results}
+
+    def mismatch(self, output: str) -> str:
+        return (f"golden mismatch: output sha256 {sha256(output.encode())}, "
+                f"expected {sha256(self.EXPECTED.encode())}")
+
+    @needs_rustc
+    def test_later_match_resolves_an_earlier_mismatch(self):
+        errors = self.run_with(C="hello wXrld", Rust=self.EXPECTED)
+        self.assertEqual(errors, {
+            "C": self.mismatch("hello wXrld") + "; first difference at byte 7", "Rust": "",
+        })
+
+    @needs_rustc
+    def test_earlier_match(self):
+        errors = self.run_with(C=self.EXPECTED, Rust="hello")
+        self.assertEqual(errors, {
+            "C": "", "Rust": self.mismatch("hello") + "; first difference at byte 5",
+        })
+
+    def test_no_reference(self):
+        self.assertEqual(self.run_with(C="jello"), {
+            "C": self.mismatch("jello") + "; no output with the expected hash to diff against",
+        })
+
+    def test_reference_from_an_earlier_run(self):
+        self.assertEqual(self.run_with(C=self.EXPECTED), {"C": ""})
+        self.assertEqual(len(list(self.store.iterdir())), 1)
+        self.assertEqual(self.run_with(C="hellO world"),
+                         {"C": self.mismatch("hellO world") + "; first difference at byte 4"})
+
+    def test_store_skips_large_outputs(self):
+        big = self.dir / "big.out"
+        big.write_bytes(b"x" * 10)
+        digest = run_benchmarks.OutputDigest(sha256="ab" * 32, size=10, path=big)
+        limit = run_benchmarks.OUTPUT_STORE_MAX_FILE_BYTES
+        self.addCleanup(setattr, run_benchmarks, "OUTPUT_STORE_MAX_FILE_BYTES", limit)
+        run_benchmarks.OUTPUT_STORE_MAX_FILE_BYTES = 9
+        store_output(self.store, digest)
+        self.assertIsNone(run_benchmarks.stored_output(self.store, digest.sha256))
+        run_benchmarks.OUTPUT_STORE_MAX_FILE_BYTES = 10
+        store_output(self.store, digest)
+        self.assertEqual(run_benchmarks.stored_output(self.store, digest.sha256).read_bytes(),
+                         b"x" * 10)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,142 @@
+import contextlib
+import sqlite3
+import tempfile
+import unittest
+from pathlib import Path
+from unittest import mock
+
+import history_db
+from history_db import HistoryError, RunInfo, connect, history, insert_run, largest_regression
+from test_results import record
+
+
+def run_info(commit: str | None, started_at: str, hostname: str = "bench1") -> RunInfo:
+    return RunInfo(started_at=started_at, git_commit=commit, hostname=hostname,
+                   rustc_version="rustc 1.80.0", config="{}")
+
+
+def schema_version(path: Path) -> int:
+    with contextlib.closing(sqlite3.connect(path)) as conn:
+        return conn.execute("SELECT version FROM schema_version").fetchone()[0]
+
+
+class HistoryDbTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.path = Path(tmp.name) / "history.sqlite"
+
+    def open(self) -> sqlite3.Connection:
+        conn = connect(self.path)
+        self.addCleanup(conn.close)
+        return conn
+
+    def test_new_database_is_at_the_current_version(self):
+        self.open()
+        self.assertEqual(schema_version(self.path), history_db.SCHEMA_VERSION)
+        self.assertEqual(history_db.migrate(self.open()), history_db.SCHEMA_VERSION)
+
+    def test_migrate_from_schema_1(self):
+        conn = self.open()
+        insert_run(conn, run_info("aaa", "2026-01-01T00:00:00Z"),
+                   [record("sum_bytes", "Rust", [1.0, 1.1, 0.9])])
+        conn.close()
+        self.assertEqual(schema_version(self.path), 1)
+
+        v2 = ("ALTER TABLE runs ADD COLUMN note TEXT",
+              "UPDATE runs SET note = 'migrated'")
+        with mock.patch.object(history_db, "MIGRATIONS", history_db.MIGRATIONS + [v2]), \
+                mock.patch.object(history_db, "SCHEMA_VERSION", 2):
+            conn = self.open()
+            self.assertEqual(schema_version(self.path), 2)
+            self.assertEqual(conn.execute("SELECT git_commit, note FROM runs").fetchall(),
+                             [("aaa", "migrated")])
+            [point] = history(conn, "sum_bytes")
+            self.assertEqual((point.git_commit, point.median_time_ms, point.samples),
+                             ("aaa", 1.0, 3))
+            # Already at 2: reopening migrates nothing.
+            self.assertEqual(history_db.migrate(conn), 2)
+
+    def test_failed_migration_rolls_back(self):
+        self.open().close()
+        broken = ("ALTER TABLE runs ADD COLUMN note TEXT", "NOT SQL")
+        with mock.patch.object(history_db, "MIGRATIONS", history_db.MIGRATIONS + [broken]), \
+                mock.patch.object(history_db, "SCHEMA_VERSION", 2):
+            with self.assertRaises(HistoryError):
+                connect(self.path)
+        self.assertEqual(schema_version(self.path), 1)
+        with contextlib.closing(sqlite3.connect(self.path)) as conn:
+            columns = [row[1] for row in conn.execute("PRAGMA table_info(runs)")]
+        self.assertNotIn("note", columns)
+
+    def test_newer_database_is_rejected(self):
+        self.open().close()
+        with contextlib.closing(sqlite3.connect(self.path, isolation_level=None)) as conn:
+            conn.execute("UPDATE schema_version SET version = 99")
+        with self.assertRaisesRegex(HistoryError, "schema version 99, newer than"):
+            connect(self.path)
+
+    def test_not_a_database(self):
+        self.path.write_text("not sqlite")
+        with self.assertRaisesRegex(HistoryError, str(self.path)):
+            connect(self.path)
+
+    def test_largest_regression_across_two_runs(self):
+        conn = self.open()
+        insert_run(conn, run_info("aaa", "2026-01-01T00:00:00Z"), [
+            record("sum_bytes", "Rust", [10.0, 10.0, 10.0]),
+            record("sum_bytes", "X07", [20.0, 20.0, 20.0]),
+            record("word_count", "Rust", [5.0]),
+        ])
+        insert_run(conn, run_info("bbb", "2026-01-02T00:00:00Z"), [
+            record("sum_bytes", "Rust", [11.0, 11.0, 11.0]),  # +10%
+            record("sum_bytes", "X07", [26.0, 25.0, 26.0]),  # +30%
+            record("sum_bytes", "C", [], success=False),
+            record("word_count", "Rust", [50.0]),
+        ])
+
+        points = history(conn, "sum_bytes")
+        self.assertEqual([(p.git_commit, p.language, p.median_time_ms) for p in points], [
+            ("aaa", "Rust", 10.0), ("aaa", "X07", 20.0),
+            ("bbb", "Rust", 11.0), ("bbb", "X07", 26.0),
+        ])
+        worst = largest_regression(points)
+        self.assertEqual((worst.language, worst.before.git_commit, worst.after.git_commit),
+                         ("X07", "aaa", "bbb"))
+        self.assertAlmostEqual(worst.pct, 30.0)
+
+    def test_largest_regression_rules(self):
+        conn = self.open()
+        runs = [
+            ("aaa", "bench1", 10.0),
+            ("aaa", "bench1", 12.0),  # a rerun of aaa replaces the first
+            (None, "bench1", 99.0),  # outside git: no commit to blame
+            ("bbb", "bench1", 13.2),
+            ("bbb", "bench2", 1.0),  # another host starts its own line
+            ("ccc", "bench2", 1.5),
+        ]
+        for day, (commit, host, ms) in enumerate(runs, start=1):
+            insert_run(conn, run_info(commit, f"2026-01-0{day}T00:00:00Z", host),
+                       [record("sum_bytes", "Rust", [ms])])
+
+        worst = largest_regression(history(conn, "sum_bytes"))
+        self.assertEqual((worst.before.median_time_ms, worst.after.median_time_ms), (1.0, 1.5))
+        self.assertAlmostEqual(worst.pct, 50.0)
+
+        on_bench1 = largest_regression(history(conn, "sum_bytes", hostname="bench1"))
+        self.assertEqual((on_bench1.before.median_time_ms, on_bench1.after.median_time_ms),
+                         (12.0, 13.2))
+        self.assertAlmostEqual(on_bench1.pct, 10.0)
+
+    def test_no_regression(self):
+        conn = self.open()
+        insert_run(conn, run_info("aaa", "2026-01-01T00:00:00Z"),
+                   [record("sum_bytes", "Rust", [10.0])])
+        insert_run(conn, run_info("bbb", "2026-01-02T00:00:00Z"),
+                   [record("sum_bytes", "Rust", [9.0])])
+        self.assertIsNone(largest_regression(history(conn, "sum_bytes")))
+        self.assertIsNone(largest_regression([]))
+
+
+if __name__ == "__main__":
+    unittest.main()
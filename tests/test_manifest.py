@@ -0,0 +1,223 @@
+import json
+import tempfile
+import unittest
+from pathlib import Path
+
+import run_benchmarks
+from run_benchmarks import (
+    GOLDEN_SCHEMA_VERSION, BenchmarkSpec, ManifestError, discover_benchmarks, load_manifest,
+    validate_manifest,
+)
+
+
+class ManifestTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.root = Path(tmp.name)
+
+    def repo(self, manifest: str, *programs: str) -> Path:
+        """A repo root with this benchmarks.toml and these (empty) programs."""
+        (self.root / "benchmarks.toml").write_text(manifest)
+        for program in programs:
+            (self.root / program).parent.mkdir(parents=True, exist_ok=True)
+            (self.root / program).write_text("")
+        return self.root
+
+    def assert_rejected(self, manifest: str, message: str):
+        with self.assertRaises(ManifestError) as cm:
+            load_manifest(self.repo(manifest))
+        self.assertEqual(str(cm.exception), message)
+
+
+class LoadManifestTest(ManifestTest):
+    def test_entries(self):
+        root = self.repo('[benchmarks.a]\nrust = "rust/a.rs"\nsize = "1MiB"\n\n'
+                         '[benchmarks.a_fast]\nsource = "a"\ngen_params = { mode = "fast" }\n')
+        self.assertEqual(load_manifest(root), {
+            "a": {"rust": "rust/a.rs", "size": "1MiB"},
+            "a_fast": {"source": "a", "gen_params": {"mode": "fast"}},
+        })
+
+    def test_no_manifest(self):
+        self.assertEqual(load_manifest(self.root), {})
+
+    def test_toml_syntax_error(self):
+        with self.assertRaises(ManifestError) as cm:
+            load_manifest(self.repo('[benchmarks.a]\nrust = "rust/a.rs"\nsize = = 1\n'))
+        self.assertRegex(str(cm.exception), r"^benchmarks\.toml: .*line 3")
+        self.assertTrue(str(cm.exception).endswith("\n  size = = 1"))
+
+    def test_unknown_key(self):
+        with self.assertRaises(ManifestError) as cm:
+            load_manifest(self.repo('[benchmarks.a]\nrust = "rust/a.rs"\ncolour = "red"\n'))
+        first, quoted = str(cm.exception).split("\n")
+        self.assertRegex(first, r"^benchmarks\.toml:3: benchmarks\.a\.colour: unknown key "
+                                r"\(known: notes, kind, .*\)$")
+        self.assertEqual(quoted, '  colour = "red"')
+
+    def test_wrong_types(self):
+        cases = [
+            ("samples = 0", "samples", "expected a positive integer, got 0"),
+            ("kind = \"slow\"", "kind",
+             "expected \"latency\" or \"throughput\", got 'slow'"),
+            ("size = \"lots\"", "size",
+             "expected a size such as 100 (KiB) or \"1MiB\", got 'lots'"),
+            ("tags = \"regex\"", "tags", "expected a list of strings, got 'regex'"),
+            ("output_sha256 = \"ABC\"", "output_sha256",
+             "expected a lowercase hex SHA-256, got 'ABC'"),
+            ("generator = \"\"", "generator", "expected an input generator name, got ''"),
+        ]
+        for line, key, message in cases:
+            with self.subTest(key):
+                self.assert_rejected(
+                    f'# leading comment\n[benchmarks.a]\nrust = "rust/a.rs"\n{line}\n',
+                    f"benchmarks.toml:4: benchmarks.a.{key}: {message}\n  {line}")
+
+    def test_program_paths_stay_in_the_repo(self):
+        for path in ("/usr/bin/true", "../other/a.rs", ""):
+            with self.subTest(path):
+                self.assert_rejected(
+                    f'[benchmarks.a]\nrust = "{path}"\n',
+                    f"benchmarks.toml:2: benchmarks.a.rust: expected a path under the repo root, "
+                    f"got {path!r}\n  rust = \"{path}\"")
+
+    def test_entry_must_be_a_table(self):
+        self.assert_rejected('[benchmarks]\na = 1\n',
+                             "benchmarks.toml: benchmarks.a: expected a [benchmarks.NAME] table")
+
+    def test_entry_needs_programs(self):
+        self.assert_rejected('[benchmarks.a]\nkind = "latency"\n',
+                             "benchmarks.toml:1: benchmarks.a: no programs: set one of x07, c, "
+                             "rust, go, or a source\n  [benchmarks.a]")
+
+    def test_source_and_programs_conflict(self):
+        self.assert_rejected(
+            '[benchmarks.a]\nrust = "rust/a.rs"\n\n[benchmarks.b]\nsource = "a"\nc = "c/b.c"\n',
+            "benchmarks.toml:6: benchmarks.b.c: an entry with a source runs the source's "
+            "programs\n  c = \"c/b.c\"")
+
+    def test_inline_entry_points_at_its_header(self):
+        # Keys not on a line of their own are located at the entry header.
+        self.assert_rejected(
+            '[benchmarks.a]\nrust = "rust/a.rs"\ngen_params = { samples = 1 }\n'
+            '[benchmarks.b]\nrust = "rust/b.rs"\nsamples = -1\n',
+            "benchmarks.toml:6: benchmarks.b.samples: expected a positive integer, got -1\n"
+            "  samples = -1")
+
+
+class SpecTest(ManifestTest):
+    MANIFEST = (
+        '[benchmarks.zeta]\nrust = "rust_cargo/zeta"\n\n'
+        '[benchmarks.alpha]\nx07 = "projects/p/src/alpha.x07.json"\nc = "c/alpha.c"\n'
+        'generator = "bytes"\ngroup = "g"\n\n'
+        '[benchmarks.alpha_fast]\nsource = "alpha"\n\n'
+        '[benchmarks.beta]\nrust = "rust/beta.rs"\ngroup = "g"\n'
+    )
+
+    def test_discovery_is_the_manifest(self):
+        root = self.repo(self.MANIFEST, "rust/unlisted.rs")
+        # Sorted, with group g together at alpha's place.
+        self.assertEqual(discover_benchmarks(root), ["alpha", "beta", "alpha_fast", "zeta"])
+
+    def test_spec_from_source(self):
+        root = self.repo(self.MANIFEST)
+        manifest = load_manifest(root)
+        spec = BenchmarkSpec.load(root, manifest, "alpha_fast")
+        self.assertEqual(spec.source, "alpha")
+        self.assertEqual(spec.generator, "bytes")
+        self.assertEqual(spec.implementations, {
+            "X07": root / "projects/p/src/alpha.x07.json", "C": root / "c/alpha.c",
+        })
+        zeta = BenchmarkSpec.load(root, manifest, "zeta")
+        self.assertEqual((zeta.generator, zeta.implementations), ("zeta", {
+            "Rust": root / "rust_cargo/zeta",
+        }))
+
+    def test_x07_project_entry(self):
+        root = self.repo("", "projects/p/x07.json", "projects/p/src/alpha.x07.json",
+                         "x07/beta.x07.json")
+        project_for = run_benchmarks._x07_project_for
+        self.assertEqual(project_for(root, root / "projects/p/src/alpha.x07.json"),
+                         (root / "projects/p/x07.json", "src/alpha.x07.json"))
+        self.assertIsNone(project_for(root, root / "x07/beta.x07.json"))
+
+
+class ValidateManifestTest(ManifestTest):
+    def golden(self, benchmark: str, input_sha256: str, output_sha256: str) -> None:
+        doc = {"schema_version": GOLDEN_SCHEMA_VERSION, "outputs": {
+            benchmark: {input_sha256: {"sha256": output_sha256, "bytes": 8}}}}
+        (self.root / "golden_outputs.json").write_text(json.dumps(doc))
+
+    def test_valid(self):
+        root = self.repo('[benchmarks.sum_bytes]\nrust = "rust/sum_bytes.rs"\nsize = 1\n\n'
+                         '[benchmarks.sum_bytes_again]\nsource = "sum_bytes"\n'
+                         'compare_to = "sum_bytes"\n',
+                         "rust/sum_bytes.rs")
+        self.assertEqual(validate_manifest(root), [])
+
+    def test_missing_program(self):
+        root = self.repo('[benchmarks.sum_bytes]\nrust = "rust/sum_bytes.rs"\n'
+                         'c = "c/sum_bytes.c"\nsize = 1\n', "rust/sum_bytes.rs")
+        self.assertEqual(validate_manifest(root), [
+            "benchmarks.toml:3: benchmarks.sum_bytes.c: c/sum_bytes.c does not exist\n"
+            "  c = \"c/sum_bytes.c\"",
+        ])
+
+    def test_unknown_source_and_compare_to(self):
+        root = self.repo('[benchmarks.a]\nsource = "nosuch"\n\n'
+                         '[benchmarks.sum_bytes]\nrust = "rust/sum_bytes.rs"\nsize = 1\n'
+                         'compare_to = "other"\n', "rust/sum_bytes.rs")
+        self.assertEqual(validate_manifest(root), [
+            "benchmarks.toml:2: benchmarks.a.source: unknown benchmark 'nosuch'\n"
+            "  source = \"nosuch\"",
+            "benchmarks.toml:7: benchmarks.sum_bytes.compare_to: unknown benchmark 'other'\n"
+            "  compare_to = \"other\"",
+        ])
+
+    def test_generator_must_make_the_default_input(self):
+        root = self.repo('[benchmarks.a]\nrust = "rust/a.rs"\n\n'
+                         '[benchmarks.b]\nrust = "rust/b.rs"\ngenerator = "nosuch"\n\n'
+                         '[benchmarks.c]\nrust = "rust/c.rs"\ngenerator = "word_count"\n'
+                         'gen_params = { break_rate = "often" }\n',
+                         "rust/a.rs", "rust/b.rs", "rust/c.rs")
+        problems = validate_manifest(root)
+        self.assertEqual(len(problems), 3)
+        self.assertEqual(problems[0], "benchmarks.toml:1: benchmarks.a: input generator 'a' "
+                                      "fails: unknown input generator 'a'\n  [benchmarks.a]")
+        self.assertTrue(problems[1].startswith(
+            "benchmarks.toml:6: benchmarks.b.generator: input generator 'nosuch' fails"))
+        self.assertEqual(problems[2], "benchmarks.toml:11: benchmarks.c.gen_params: input "
+                                      "generator 'word_count' rejects them: break_rate: expected "
+                                      "float, got 'often'\n"
+                                      "  gen_params = { break_rate = \"often\" }")
+
+    def test_output_sha256_must_agree_with_goldens(self):
+        manifest = ('[benchmarks.fibonacci]\nrust = "rust/fibonacci.rs"\n'
+                    f'output_sha256 = "{"a" * 64}"\n')
+        root = self.repo(manifest, "rust/fibonacci.rs")
+        input_sha256 = run_benchmarks.generate_input_data("fibonacci", 100).sha256
+        self.golden("fibonacci", input_sha256, "a" * 64)
+        self.assertEqual(validate_manifest(root), [])
+
+        self.golden("fibonacci", input_sha256, "b" * 64)
+        self.assertEqual(validate_manifest(root), [
+            f"benchmarks.toml:3: benchmarks.fibonacci.output_sha256: golden_outputs.json has "
+            f"{'b' * 64} for this input\n  output_sha256 = \"{'a' * 64}\"",
+        ])
+
+    def test_unlisted_programs(self):
+        root = self.repo('[benchmarks.sum_bytes]\nrust = "rust_cargo/sum_bytes"\nsize = 1\n',
+                         "rust_cargo/sum_bytes/src/main.rs", "rust_cargo/lib_only/src/lib.rs",
+                         "rust_cargo/extra/src/main.rs", "go/extra.go")
+        self.assertEqual(validate_manifest(root), [
+            "benchmarks.toml: no entry names rust_cargo/extra",
+            "benchmarks.toml: no entry names go/extra.go",
+        ])
+
+    def test_repo_manifest(self):
+        self.assertEqual(validate_manifest(Path(run_benchmarks.__file__).resolve().parent), [])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,64 @@
+import os
+import sys
+import tempfile
+import unittest
+from pathlib import Path
+
+import run_benchmarks
+from run_benchmarks import _run_child, _run_to_file, child_placement, set_child_placement
+
+# The child's view of where it was placed.
+REPORT = """
+import os
+status = open("/proc/self/status").read()
+[cpus] = [l.split(":")[1].strip() for l in status.splitlines() if l.startswith("Cpus_allowed_list")]
+print(cpus, sorted(os.sched_getaffinity(0)), os.nice(0))
+"""
+
+
+@unittest.skipUnless(hasattr(os, "sched_getaffinity") and Path("/proc/self/status").exists(),
+                     "needs Linux CPU affinity")
+class PlacementTest(unittest.TestCase):
+    def setUp(self):
+        self.addCleanup(set_child_placement, None, None)
+        self.runner_cpus = os.sched_getaffinity(0)
+        # The last CPU this process may use: on a multi-core machine, not
+        # the only one a child would be allowed anyway.
+        self.cpu = max(self.runner_cpus)
+
+    def report(self) -> str:
+        return _run_child([sys.executable, "-c", REPORT], capture_output=True,
+                          text=True).stdout.strip()
+
+    def test_pinned_child(self):
+        self.assertEqual(set_child_placement(self.cpu, None), [])
+        self.assertEqual(child_placement(), f"cpu={self.cpu}")
+        self.assertEqual(self.report(), f"{self.cpu} [{self.cpu}] {os.nice(0)}")
+        # Only the children are pinned, not the runner.
+        self.assertEqual(os.sched_getaffinity(0), self.runner_cpus)
+
+    def test_streamed_and_rss_runs_are_pinned(self):
+        set_child_placement(self.cpu, None)
+        with tempfile.TemporaryDirectory() as tmp:
+            out = Path(tmp) / "out"
+            _run_to_file([sys.executable, "-c", REPORT], b"", out)
+            self.assertTrue(out.read_text().startswith(f"{self.cpu} [{self.cpu}]"))
+        # Through tools/rss_exec, which the benchmark inherits its placement from.
+        res, _ = run_benchmarks._run_with_optional_rss([sys.executable, "-c", REPORT], b"",
+                                                       measure_rss=True)
+        self.assertTrue(res.stdout.decode().startswith(f"{self.cpu} [{self.cpu}]"))
+
+    def test_nice(self):
+        set_child_placement(None, 3)
+        self.assertEqual(child_placement(), "nice=3")
+        allowed = run_benchmarks._format_cpu_list(self.runner_cpus)
+        self.assertEqual(self.report(), f"{allowed} {sorted(self.runner_cpus)} {os.nice(0) + 3}")
+
+    def test_unavailable_cpu(self):
+        with self.assertRaisesRegex(ValueError, r"^--pin 100000: CPU 100000 is not one this "):
+            set_child_placement(100000, None)
+        self.assertEqual(child_placement(), "")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,454 @@
+import contextlib
+import csv
+import dataclasses
+import io
+import json
+import tempfile
+import unittest
+from pathlib import Path
+
+import run_benchmarks
+from run_benchmarks import (
+    CSV_COMPARISON_COLUMNS, CSV_SAMPLE_COLUMNS, CSV_SUMMARY_COLUMNS, RESULTS_SCHEMA_VERSION,
+    BenchmarkResult, ResultRecord, SuiteSummary, compare_results,
+    load_results, render_markdown_report, results_document,
+)
+
+
+def record(benchmark: str, language: str, times: list[float], input_bytes: int = 1 << 20,
+           **fields) -> ResultRecord:
+    """A record as a run makes it, from the raw result."""
+    r = BenchmarkResult(language=language, benchmark=benchmark, times_ms=times,
+                        input_bytes=input_bytes, input_sha256="ab" * 32,
+                        size_kb=input_bytes // 1024, **fields)
+    return ResultRecord.from_result(r, "default")
+
+
+def write_results(path: Path, records: list[ResultRecord], config: dict | None = None) -> None:
+    """Write records the way `run --format json` prints them."""
+    doc = results_document(records, config or {}, SuiteSummary.from_records(records, {}))
+    path.write_text(json.dumps(doc, indent=2) + "\n")
+
+
+def cli(*argv: str) -> tuple[int, str, str]:
+    """run_benchmarks.main's (exit status, stdout, stderr); argparse errors
+    exit rather than return, so SystemExit is caught too."""
+    out, err = io.StringIO(), io.StringIO()
+    with contextlib.redirect_stdout(out), contextlib.redirect_stderr(err):
+        try:
+            status = run_benchmarks.main(list(argv))
+        except SystemExit as e:
+            status = e.code
+    return status, out.getvalue(), err.getvalue()
+
+
+class ResultsFileTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        self.records = [
+            record("sum_bytes", "Rust", [2.0, 2.1, 1.9, 2.0, 9.0], peak_rss_kb=1800),
+            record("sum_bytes", "X07", [2.4, 2.5, 2.6], peak_rss_kb=2100, converged=True),
+            record("sum_bytes", "C", [], success=False, error="exit 139"),
+            record("fibonacci", "Rust", [0.5, 0.6, 0.55], input_bytes=8),
+        ]
+        self.config = {"size_kb": 1024, "samples": 5, "subtract_baseline": False}
+
+    def path(self, name: str) -> Path:
+        return self.dir / name
+
+    def test_round_trip(self):
+        write_results(self.path("run.json"), self.records, self.config)
+        config, records = load_results(self.path("run.json"))
+        self.assertEqual(config, self.config)
+        self.assertEqual(records, self.records)
+        self.assertIsNotNone(records[1].median_ci_ms)
+        self.assertEqual(records[2].error, "exit 139")
+
+    def test_compare_reads_run_output(self):
+        write_results(self.path("a.json"), self.records, self.config)
+        write_results(self.path("b.json"), self.records, self.config)
+        status, out, _ = cli("compare", str(self.path("a.json")), str(self.path("b.json")))
+        self.assertEqual(status, 0)
+        self.assertIn("No regressions", out)
+        self.assertIn("sum_bytes/C", out)  # failed, so listed rather than compared
+
+    def test_report_reads_run_output(self):
+        write_results(self.path("run.json"), self.records, self.config)
+        status, out, _ = cli("report", str(self.path("run.json")))
+        self.assertEqual(status, 0)
+        self.assertIn("sum_bytes", out)
+        status, out, _ = cli("report", "--format", "html", str(self.path("run.json")))
+        self.assertEqual(status, 0)
+        self.assertIn("<svg", out)
+
+    def test_unknown_schema_version_is_rejected(self):
+        write_results(self.path("run.json"), self.records, self.config)
+        doc = json.loads(self.path("run.json").read_text())
+        doc["schema_version"] = "x07-perf-compare.results@9.0.0"
+        self.path("future.json").write_text(json.dumps(doc))
+
+        with self.assertRaisesRegex(ValueError, "unsupported results schema"):
+            load_results(self.path("future.json"))
+        for argv in (["compare", str(self.path("run.json")), str(self.path("future.json"))],
+                     ["report", str(self.path("future.json"))]):
+            status, out, err = cli(*argv)
+            self.assertEqual(status, 2)
+            self.assertEqual(out, "")
+            self.assertIn("unsupported results schema 'x07-perf-compare.results@9.0.0'", err)
+
+    def test_snapshot_layout(self):
+        # snapshots/ predates the schema: {benchmark: [records]} and no config.
+        legacy = {"sum_bytes": [{"language": "Rust", "mean_time_ms": 2.0}]}
+        self.path("old.json").write_text(json.dumps(legacy))
+        config, records = load_results(self.path("old.json"))
+        self.assertEqual(config, {})
+        self.assertEqual((records[0].benchmark, records[0].median_time_ms), ("sum_bytes", 2.0))
+
+    def test_schema_version_is_written(self):
+        write_results(self.path("run.json"), self.records)
+        doc = json.loads(self.path("run.json").read_text())
+        self.assertEqual(doc["schema_version"], RESULTS_SCHEMA_VERSION)
+        self.assertEqual(len(doc["results"]), len(self.records))
+
+
+class CompareTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+
+    def change(self, baseline_ms: float, current_ms: float, benchmark: str = "sum_bytes",
+               **kwargs):
+        """The single entry comparing one Rust median against another."""
+        input_bytes = 8 if benchmark == "fibonacci" else 1 << 20
+        cmp = compare_results([record(benchmark, "Rust", [baseline_ms], input_bytes)],
+                              [record(benchmark, "Rust", [current_ms], input_bytes)], 5.0,
+                              **kwargs)
+        [entry] = cmp.entries
+        return entry
+
+    def test_threshold(self):
+        self.assertFalse(self.change(100.0, 104.9).regressed)
+        self.assertFalse(self.change(100.0, 105.0).regressed)  # exactly 5% is not over
+        self.assertTrue(self.change(100.0, 105.1).regressed)
+        self.assertFalse(self.change(100.0, 50.0).regressed)
+        self.assertAlmostEqual(self.change(100.0, 105.1).change_pct, 5.1)
+
+    def test_latency_threshold(self):
+        latency = frozenset({"fibonacci"})
+        entry = self.change(0.5, 0.525, "fibonacci", latency_benchmarks=latency)
+        self.assertFalse(entry.by_throughput)
+        self.assertFalse(entry.regressed)
+        self.assertTrue(self.change(0.5, 0.526, "fibonacci", latency_benchmarks=latency).regressed)
+
+    def test_rss_threshold(self):
+        def rss(baseline_kb, current_kb):
+            return compare_results(
+                [record("sum_bytes", "Rust", [1.0], peak_rss_kb=baseline_kb)],
+                [record("sum_bytes", "Rust", [1.0], peak_rss_kb=current_kb)], 5.0, 10.0,
+            ).entries[0]
+        self.assertFalse(rss(1000, 1100).rss_regressed)
+        self.assertTrue(rss(1000, 1101).rss_regressed)
+        self.assertFalse(rss(1000, None).rss_regressed)
+
+    def test_missing_on_either_side(self):
+        baseline = [record("sum_bytes", "Rust", [1.0]), record("word_count", "Rust", [1.0])]
+        current = [record("sum_bytes", "Rust", [1.0]), record("sum_bytes", "X07", [9.0])]
+        cmp = compare_results(baseline, current, 5.0)
+        self.assertEqual(cmp.only_in_baseline, [("word_count", "Rust")])
+        self.assertEqual(cmp.only_in_current, [("sum_bytes", "X07")])
+        self.assertEqual([(e.benchmark, e.language) for e in cmp.entries], [("sum_bytes", "Rust")])
+        self.assertEqual(cmp.regressions, [])
+
+    def compare_files(self, current_ms: float, *flags: str) -> tuple[int, str]:
+        baseline = [record("sum_bytes", "Rust", [100.0]), record("word_count", "Rust", [1.0])]
+        current = [record("sum_bytes", "Rust", [current_ms]), record("sum_bytes", "X07", [1.0])]
+        write_results(self.dir / "baseline.json", baseline)
+        write_results(self.dir / "current.json", current)
+        status, out, _ = cli("compare", str(self.dir / "baseline.json"),
+                             str(self.dir / "current.json"), *flags)
+        return status, out
+
+    def test_exit_status(self):
+        status, out = self.compare_files(105.0)
+        self.assertEqual(status, 0)
+        self.assertIn("No regressions", out)
+        self.assertIn("Only in baseline:\n  word_count/Rust", out)
+        self.assertIn("Only in current:\n  sum_bytes/X07", out)
+
+        status, out = self.compare_files(105.1)
+        self.assertEqual(status, 1)
+        self.assertIn("REGRESSED", out)
+        self.assertIn("1 regression(s)", out)
+
+        self.assertEqual(self.compare_files(105.1, "--fail-over", "6%")[0], 0)
+        self.assertEqual(self.compare_files(105.1, "--format", "csv")[0], 1)
+        self.assertEqual(self.compare_files(104.0, "--format", "csv")[0], 0)
+
+
+# Tags and kinds for the report fixtures: word_count groups under its first tag.
+REPORT_MANIFEST = {
+    "sum_bytes": {"tags": ["bytes"]},
+    "word_count": {"tags": ["bytes", "text"]},
+    "fibonacci": {"kind": "latency"},
+}
+
+
+def report_records(sum_bytes_x07_ms: float, word_count_rust_ms: float) -> list[ResultRecord]:
+    """sum_bytes at two sizes, word_count, and a fibonacci whose X07 run failed."""
+    fibonacci = [record("fibonacci", "Rust", [0.25], 8),
+                 record("fibonacci", "X07", [], 8, success=False, error="exit 1")]
+    return [
+        record("sum_bytes", "Rust", [2.0]), record("sum_bytes", "X07", [sum_bytes_x07_ms]),
+        record("sum_bytes", "Rust", [1.0], 64 << 10), record("sum_bytes", "X07", [0.5], 64 << 10),
+        record("word_count", "Rust", [word_count_rust_ms]), record("word_count", "X07", [4.0]),
+    ] + [dataclasses.replace(r, size_kb=None) for r in fibonacci]
+
+
+REPORT_NOTE = ("Medians are startup-subtracted where the run recorded them; X07/Rust is X07's "
+               "median over Rust's (above 1.00x: X07 is slower).\n")
+
+ONE_FILE_REPORT = """\
+### bytes
+
+| Benchmark | Size | Rust median (ms) | X07 median (ms) | X07/Rust |
+|---|---:|---:|---:|---:|
+| `sum_bytes` | 1MiB | 2.000 | 3.000 | 1.50x |
+| `sum_bytes` | 64KiB | 1.000 | 0.500 | 0.50x |
+| `word_count` | 1MiB | 4.000 | 4.000 | 1.00x |
+| **geomean (3)** |  | - | - | **0.91x** |
+
+### latency
+
+| Benchmark | Size | Rust median (ms) | X07 median (ms) | X07/Rust |
+|---|---:|---:|---:|---:|
+| `fibonacci` | 8 B | 0.250 | - | - |
+| **geomean (1)** |  | - | - | - |
+
+""" + REPORT_NOTE
+
+TWO_FILE_HEADER = """\
+| Benchmark | Size | Rust median (ms) | Rust change | X07 median (ms) | X07 change | X07/Rust |
+|---|---:|---:|---:|---:|---:|---:|
+"""
+
+TWO_FILE_REPORT = """\
+### bytes
+
+""" + TWO_FILE_HEADER + """\
+| `sum_bytes` | 1MiB | 2.000 | 0.0% | 3.300 | ▲ +10.0% | 1.65x |
+| `sum_bytes` | 64KiB | 1.000 | 0.0% | 0.500 | 0.0% | 0.50x |
+| `word_count` | 1MiB | 3.800 | ▼ -5.0% | 4.000 | 0.0% | 1.05x |
+| **geomean (3)** |  | - | **▼ -1.7%** | - | **▲ +3.2%** | **0.95x** |
+
+### latency
+
+""" + TWO_FILE_HEADER + """\
+| `fibonacci` | 8 B | 0.250 | 0.0% | - | - | - |
+| **geomean (1)** |  | - | **0.0%** | - | - | - |
+
+▲ slower than the baseline, ▼ faster. """ + REPORT_NOTE
+
+TOP_ONE_REPORT = """\
+### bytes
+
+""" + TWO_FILE_HEADER + """\
+| `sum_bytes` | 1MiB | 2.000 | 0.0% | 3.300 | ▲ +10.0% | 1.65x |
+| **geomean (3, 1 shown)** |  | - | **▼ -1.7%** | - | **▲ +3.2%** | **0.95x** |
+
+▲ slower than the baseline, ▼ faster. """ + REPORT_NOTE
+
+
+class MarkdownReportTest(unittest.TestCase):
+    """Snapshots of `report --format markdown` over results files."""
+
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        write_results(self.dir / "baseline.json", report_records(3.0, 4.0))
+        write_results(self.dir / "current.json", report_records(3.3, 3.8))
+
+    def render(self, *names: str, top: int | None = None) -> str:
+        loaded = [load_results(self.dir / name)[1] for name in names]
+        baseline = loaded[0] if len(loaded) == 2 else None
+        return render_markdown_report(loaded[-1], REPORT_MANIFEST, baseline, top)
+
+    def test_one_file(self):
+        self.assertEqual(self.render("baseline.json"), ONE_FILE_REPORT)
+
+    def test_two_files(self):
+        self.assertEqual(self.render("baseline.json", "current.json"), TWO_FILE_REPORT)
+
+    def test_top(self):
+        # The geomean rows still cover every benchmark in the group, and a
+        # group with nothing shown is left out.
+        self.assertEqual(self.render("baseline.json", "current.json", top=1), TOP_ONE_REPORT)
+
+    def test_cli_matches(self):
+        manifest = run_benchmarks.load_manifest(run_benchmarks._perf_repo_root())
+        for names in (["baseline.json"], ["baseline.json", "current.json"]):
+            with self.subTest(names):
+                loaded = [load_results(self.dir / name)[1] for name in names]
+                expected = render_markdown_report(
+                    loaded[-1], manifest, loaded[0] if len(loaded) == 2 else None)
+                status, out, _ = cli("report", *(str(self.dir / name) for name in names))
+                self.assertEqual((status, out), (0, expected))
+
+
+class SuiteSummaryTest(unittest.TestCase):
+    """Geomeans of X07/Rust median ratios, checked against hand-computed values."""
+
+    MANIFEST = {"sum_bytes": {"tags": ["bytes"]}, "word_count": {"tags": ["bytes", "text"]},
+                "fibonacci": {"kind": "latency"}}
+
+    def pair(self, benchmark: str, rust_ms: float, x07_ms: float) -> list[ResultRecord]:
+        return [record(benchmark, "Rust", [rust_ms]), record(benchmark, "X07", [x07_ms])]
+
+    def test_hand_computed(self):
+        # Ratios 2.0, 8.0 and 0.5: overall (2 * 8 * 0.5) ** (1/3) = 2.
+        records = (self.pair("sum_bytes", 1.0, 2.0) + self.pair("word_count", 1.0, 8.0)
+                   + self.pair("fibonacci", 4.0, 2.0))
+        summary = SuiteSummary.from_records(records, self.MANIFEST)
+        self.assertEqual(summary.count, 3)
+        self.assertAlmostEqual(summary.geomean, 2.0)
+        self.assertEqual(sorted(summary.by_tag), ["bytes", "latency", "text", "throughput"])
+        self.assertAlmostEqual(summary.by_tag["bytes"][0], 4.0)  # sqrt(2 * 8)
+        self.assertEqual(summary.by_tag["bytes"][1], 2)
+        self.assertAlmostEqual(summary.by_tag["throughput"][0], 4.0)
+        self.assertAlmostEqual(summary.by_tag["text"][0], 8.0)
+        self.assertAlmostEqual(summary.by_tag["latency"][0], 0.5)
+        self.assertEqual(summary.excluded, [])
+
+    def test_one_benchmark(self):
+        summary = SuiteSummary.from_records(self.pair("sum_bytes", 3.0, 4.5), self.MANIFEST)
+        self.assertEqual(summary.count, 1)
+        self.assertAlmostEqual(summary.geomean, 1.5)
+        self.assertEqual({tag: n for tag, (_, n) in summary.by_tag.items()},
+                         {"bytes": 1, "throughput": 1})
+
+    def test_missing_failed_and_zero_are_excluded(self):
+        records = self.pair("sum_bytes", 1.0, 2.0) + [
+            record("word_count", "Rust", [1.0]),
+            record("fibonacci", "Rust", [1.0], 8),
+            record("fibonacci", "X07", [], 8, success=False, error="exit 1"),
+            record("byte_freq", "Rust", [0.0]), record("byte_freq", "X07", [1.0]),
+        ]
+        summary = SuiteSummary.from_records(records, self.MANIFEST)
+        self.assertEqual(summary.count, 1)
+        self.assertAlmostEqual(summary.geomean, 2.0)
+        self.assertEqual(summary.excluded, [
+            ("word_count", "no X07 result"),
+            ("fibonacci", "X07 failed"),
+            ("byte_freq", "Rust median is 0"),
+        ])
+        self.assertNotIn("latency", summary.by_tag)
+
+    def test_nothing_to_average_is_none_not_nan(self):
+        records = [record("sum_bytes", "Rust", [0.0]),
+                   record("sum_bytes", "X07", [], success=False, error="exit 1")]
+        summary = SuiteSummary.from_records(records, self.MANIFEST)
+        self.assertIsNone(summary.geomean)
+        self.assertEqual((summary.count, summary.by_tag), (0, {}))
+        self.assertEqual(summary.excluded, [("sum_bytes", "X07 failed, Rust median is 0")])
+        self.assertEqual(summary.to_json()["x07_over_rust_geomean"], None)
+        self.assertEqual(run_benchmarks._describe_ratio(summary.geomean), "n/a")
+
+    def test_sizes_are_separate_benchmarks(self):
+        records = self.pair("sum_bytes", 1.0, 2.0) + [
+            record("sum_bytes", "Rust", [1.0], 64 << 10),
+            record("sum_bytes", "X07", [8.0], 64 << 10),
+        ]
+        summary = SuiteSummary.from_records(records, self.MANIFEST)
+        self.assertEqual(summary.count, 2)
+        self.assertAlmostEqual(summary.geomean, 4.0)
+
+    def test_json(self):
+        records = self.pair("sum_bytes", 1.0, 2.0) + [record("word_count", "X07", [1.0])]
+        doc = SuiteSummary.from_records(records, self.MANIFEST).to_json()
+        self.assertEqual(doc, {
+            "x07_over_rust_geomean": 2.0,
+            "benchmarks": 1,
+            "by_tag": {"bytes": {"x07_over_rust_geomean": 2.0, "benchmarks": 1},
+                       "throughput": {"x07_over_rust_geomean": 2.0, "benchmarks": 1}},
+            "excluded": [{"benchmark": "word_count", "reason": "no Rust result"}],
+        })
+
+    def test_compare_diffs_the_geomeans(self):
+        out = io.StringIO()
+        with contextlib.redirect_stdout(out):
+            run_benchmarks.print_suite_comparison(
+                SuiteSummary.from_records(self.pair("sum_bytes", 1.0, 2.0), self.MANIFEST),
+                SuiteSummary.from_records(self.pair("sum_bytes", 1.0, 2.5)
+                                          + self.pair("fibonacci", 1.0, 1.0), self.MANIFEST))
+        lines = out.getvalue().splitlines()
+        self.assertEqual(lines[1].split(), ["overall", "2.00x", "->", "1.58x", "-20.9%",
+                                            "(1", "->", "2", "benchmark(s))"])
+        self.assertEqual(lines[3].split(), ["latency", "n/a", "->", "1.00x", "n/a",
+                                            "(0", "->", "1", "benchmark(s))"])
+
+
+class CsvTest(unittest.TestCase):
+    """The CSV writers' output parses back to the records it was written from."""
+
+    def setUp(self):
+        self.records = [
+            record("sum_bytes", "Rust", [2.0, 2.5, 1.5], peak_rss_kb=1800),
+            # A name with a comma and a quote, and an error spanning lines.
+            record('odd, "quoted" name', "X07", [], success=False, error="exit 1\nsee log"),
+            record("fibonacci", "C", [0.25, 0.125], input_bytes=8, converged=True),
+        ]
+
+    def parse(self, write, *args) -> tuple[list[str], list[dict[str, str]]]:
+        out = io.StringIO(newline="")
+        write(out, *args)
+        text = out.getvalue()
+        self.assertTrue(text.endswith("\r\n"))
+        rows = list(csv.reader(io.StringIO(text, newline="")))
+        return rows[0], [dict(zip(rows[0], row, strict=True)) for row in rows[1:]]
+
+    def test_summary_round_trip(self):
+        header, rows = self.parse(run_benchmarks.write_summary_csv, self.records)
+        self.assertEqual(header, list(CSV_SUMMARY_COLUMNS))
+        self.assertEqual(len(rows), len(self.records))
+        for row, rec in zip(rows, self.records):
+            with self.subTest(rec.benchmark):
+                low, high = rec.median_ci_ms or ("", "")
+                expected = {**dataclasses.asdict(rec), "samples": len(rec.samples_ms),
+                            "median_ci_low_ms": low, "median_ci_high_ms": high}
+                self.assertEqual(row, {c: "" if expected[c] is None else str(expected[c])
+                                       for c in CSV_SUMMARY_COLUMNS})
+        self.assertEqual(rows[1]["benchmark"], 'odd, "quoted" name')
+        self.assertEqual(rows[1]["error"], "exit 1\nsee log")
+        self.assertEqual(rows[0]["peak_rss_kb"], "1800")
+        self.assertEqual(rows[2]["peak_rss_kb"], "")
+        self.assertEqual(float(rows[0]["median_time_ms"]), self.records[0].median_time_ms)
+
+    def test_samples_round_trip(self):
+        header, rows = self.parse(run_benchmarks.write_samples_csv, self.records)
+        self.assertEqual(header, list(CSV_SAMPLE_COLUMNS))
+        # The failed run has no samples, so no rows.
+        self.assertEqual([(r["benchmark"], r["language"], r["sample"], float(r["time_ms"]))
+                          for r in rows], [
+            ("sum_bytes", "Rust", "0", 2.0), ("sum_bytes", "Rust", "1", 2.5),
+            ("sum_bytes", "Rust", "2", 1.5), ("fibonacci", "C", "0", 0.25),
+            ("fibonacci", "C", "1", 0.125),
+        ])
+        self.assertEqual({(r["size_kb"], r["input_bytes"]) for r in rows[3:]}, {("0", "8")})
+
+    def test_comparison_round_trip(self):
+        cmp = compare_results([record("a,b", "Rust", [100.0])], [record("a,b", "Rust", [110.0])],
+                              5.0)
+        header, rows = self.parse(run_benchmarks.write_comparison_csv, cmp)
+        self.assertEqual(header, list(CSV_COMPARISON_COLUMNS))
+        [row] = rows
+        self.assertEqual((row["benchmark"], row["language"], row["regressed"]),
+                         ("a,b", "Rust", "True"))
+        self.assertAlmostEqual(float(row["change_pct"]), 10.0)
+        self.assertEqual((row["baseline_rss_kb"], row["rss_change_pct"]), ("", ""))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,137 @@
+import os
+import shutil
+import tempfile
+import time
+import unittest
+from pathlib import Path
+from unittest import mock
+
+import run_benchmarks
+from run_benchmarks import InputData, ResultsCache, run_benchmark
+from test_results import record
+
+needs_cc = unittest.skipUnless(shutil.which("cc"), "cc not on PATH")
+
+C_PROGRAM = """#include <stdio.h>
+int main(void) {
+    unsigned long sum = 0;
+    int c;
+    while ((c = getchar()) != EOF) sum += c;
+    printf("PREFIX%lu", sum);
+    return 0;
+}
+"""
+
+
+class ResultsCacheTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        self.cache = ResultsCache(self.dir / "cache")
+        self.binary = self.dir / "prog"
+        self.binary.write_bytes(b"binary v1")
+
+    def key(self, input_sha256: str = "ab" * 32) -> str:
+        return self.cache.key(self.binary, input_sha256, 5, 1, "")
+
+    def test_hit(self):
+        stored = record("sum_bytes", "C", [1.0, 2.0, 3.0])
+        self.cache.store(self.key(), stored)
+        self.assertEqual(self.cache.load(self.key()), stored)
+
+    def test_key_covers_binary_input_and_runner_version(self):
+        key = self.key()
+        self.assertEqual(self.key(), key)
+        self.assertNotEqual(self.key("cd" * 32), key)
+        self.assertNotEqual(self.cache.key(self.binary, "ab" * 32, 6, 1, ""), key)
+        self.assertNotEqual(self.cache.key(self.binary, "ab" * 32, 5, 1, "direct"), key)
+        bumped = run_benchmarks.RUNNER_VERSION + 1
+        with mock.patch.object(run_benchmarks, "RUNNER_VERSION", bumped):
+            self.assertNotEqual(self.key(), key)
+        self.binary.write_bytes(b"binary v2")
+        self.assertNotEqual(self.key(), key)
+
+    def test_corrupted_entry_is_a_miss_and_removed(self):
+        for contents in ("{not json", '{"benchmark": "sum_bytes"}', "[]", ""):
+            with self.subTest(contents):
+                self.cache.store(self.key(), record("sum_bytes", "C", [1.0]))
+                path = self.cache.root / f"{self.key()}.json"
+                path.write_text(contents)
+                self.assertIsNone(self.cache.load(self.key()))
+                self.assertFalse(path.exists())
+
+    def test_eviction(self):
+        for name in ("old", "new", "stale"):
+            self.cache.store(name, record(name, "C", [1.0]))
+        now = time.time()
+        for name, age in (("stale", 2 * 86400), ("old", 60), ("new", 0)):
+            os.utime(self.cache.root / f"{name}.json", (now - age, now - age))
+        # Past max_age_s goes first, then the oldest until the rest fit.
+        self.cache.max_age_s = 86400
+        self.cache.max_bytes = (self.cache.root / "new.json").stat().st_size
+        self.cache.evict()
+        self.assertEqual([p.stem for p in self.cache.root.glob("*.json")], ["new"])
+        self.assertEqual(self.cache.clear(), 1)
+        self.assertIsNone(self.cache.load("new"))
+
+
+@needs_cc
+class CachedRunTest(unittest.TestCase):
+    """run_benchmark takes cached samples until a key input changes."""
+
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        self.cache = ResultsCache(self.dir / "cache")
+        self.source = self.dir / "prog.c"
+        self.source.write_text(C_PROGRAM.replace("PREFIX", "sum="))
+        self.runs = 0
+
+    def run_once(self, data: bytes = b"abc") -> run_benchmarks.BenchmarkResult:
+        self.runs += 1
+        work = self.dir / f"work{self.runs}"
+        work.mkdir()
+        [result] = run_benchmark(
+            "prog", InputData(name="prog", data=data, size_kb=0), self.dir / "no-x07", self.dir,
+            work, iterations=3, warmup=0, implementations={"C": self.source},
+            results_cache=self.cache)
+        self.assertTrue(result.success, result.error)
+        self.assertEqual(len(result.times_ms), 3)
+        return result
+
+    def test_hit_and_misses(self):
+        first = self.run_once()
+        self.assertFalse(first.cached)
+        hit = self.run_once()
+        self.assertTrue(hit.cached)
+        self.assertEqual(hit.times_ms, first.times_ms)
+
+        with self.subTest("input changed"):
+            self.assertFalse(self.run_once(b"abcd").cached)
+            self.assertTrue(self.run_once(b"abcd").cached)
+
+        with self.subTest("binary changed"):
+            self.source.write_text(C_PROGRAM.replace("PREFIX", "total="))
+            self.assertFalse(self.run_once().cached)
+            self.assertTrue(self.run_once().cached)
+
+        with self.subTest("runner version bumped"):
+            with mock.patch.object(run_benchmarks, "RUNNER_VERSION",
+                                   run_benchmarks.RUNNER_VERSION + 1):
+                self.assertFalse(self.run_once().cached)
+                self.assertTrue(self.run_once().cached)
+
+    def test_corrupted_entry_is_retimed(self):
+        self.run_once()
+        [entry] = self.cache.root.glob("*.json")
+        entry.write_text("{truncated")
+        retimed = self.run_once()
+        self.assertFalse(retimed.cached)
+        # The fresh samples replace the bad entry.
+        self.assertTrue(self.run_once().cached)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,62 @@
+import shutil
+import tempfile
+import unittest
+from pathlib import Path
+
+from run_benchmarks import CRunner, _rss_exec
+
+# Allocates the MiB given on stdin and writes to every page, so all of it is
+# resident; prints one byte of it so the stores can't be optimized away.
+TOUCH = r"""#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+int main(void) {
+    size_t mib = 0;
+    if (scanf("%zu", &mib) != 1) return 2;
+    unsigned char *p = malloc(mib << 20);
+    if (mib && !p) return 3;
+    memset(p, 1, mib << 20);
+    printf("%d\n", mib ? p[(mib << 20) - 1] : 0);
+    free(p);
+    return 0;
+}
+"""
+
+MIB = 1024
+
+
+@unittest.skipUnless(shutil.which("cc"), "cc not on PATH")
+class PeakRssTest(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.tmp = tempfile.TemporaryDirectory()
+        source = Path(cls.tmp.name) / "touch.c"
+        source.write_text(TOUCH)
+        cls.binary = Path(cls.tmp.name) / "touch"
+        CRunner().compile(source, cls.binary)
+        if _rss_exec() is None:
+            raise unittest.SkipTest("tools/rss_exec could not be built")
+
+    @classmethod
+    def tearDownClass(cls):
+        cls.tmp.cleanup()
+
+    def rss_kb(self, mib: int) -> int:
+        output, rss_kb = CRunner().run_with_rss(self.binary, str(mib).encode())
+        self.assertEqual(output, b"1\n" if mib else b"0\n")
+        self.assertIsNotNone(rss_kb)
+        return rss_kb
+
+    def test_64_mib(self):
+        idle = self.rss_kb(0)
+        touched = self.rss_kb(64)
+        # The helper measures the benchmark alone, not the Python runner that
+        # launched it (tens of MiB on its own).
+        self.assertLess(idle, 8 * MIB)
+        self.assertGreaterEqual(touched, 64 * MIB)
+        self.assertLess(touched, 64 * MIB + idle + 4 * MIB)
+        self.assertAlmostEqual(touched - idle, 64 * MIB, delta=4 * MIB)
+
+
+if __name__ == "__main__":
+    unittest.main()
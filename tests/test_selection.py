@@ -0,0 +1,68 @@
+import argparse
+import unittest
+
+from run_benchmarks import add_selection_args, select_benchmarks
+from test_results import cli
+
+BENCHMARKS = ["regex_count", "regex_grep", "sort_u32", "sum_bytes", "word_count"]
+MANIFEST = {
+    "regex_count": {"tags": ["regex", "text"]},
+    "regex_grep": {"tags": ["regex"]},
+    "sort_u32": {"tags": ["sort"], "kind": "latency"},
+    "word_count": {"tags": ["text"]},
+}
+
+
+def select(*argv: str) -> list[str]:
+    ap = argparse.ArgumentParser()
+    add_selection_args(ap)
+    return select_benchmarks(BENCHMARKS, MANIFEST, ap.parse_args(argv))
+
+
+class SelectionTest(unittest.TestCase):
+    def test_no_flags_selects_everything(self):
+        self.assertEqual(select(), BENCHMARKS)
+
+    def test_glob_only(self):
+        self.assertEqual(select("--filter", "regex_*"), ["regex_count", "regex_grep"])
+        self.assertEqual(select("--filter", "*count,sort*"),
+                         ["regex_count", "sort_u32", "word_count"])
+        self.assertEqual(select("--filter", "sum_bytes"), ["sum_bytes"])
+
+    def test_tag_only(self):
+        self.assertEqual(select("--tag", "text"), ["regex_count", "word_count"])
+        self.assertEqual(select("--tag", "regex", "--tag", "sort"),
+                         ["regex_count", "regex_grep", "sort_u32"])
+        # The kind counts as a tag, so untagged benchmarks can be selected.
+        self.assertEqual(select("--tag", "latency"), ["sort_u32"])
+        self.assertEqual(select("--tag", "throughput"),
+                         ["regex_count", "regex_grep", "sum_bytes", "word_count"])
+
+    def test_glob_and_tag_intersect(self):
+        self.assertEqual(select("--filter", "regex_*", "--tag", "text"), ["regex_count"])
+        self.assertEqual(select("--filter", "*count", "--tag", "regex"), ["regex_count"])
+
+    def test_excludes(self):
+        self.assertEqual(select("--tag", "regex", "--exclude", "*grep"), ["regex_count"])
+        self.assertEqual(select("--filter", "*count", "--exclude-tag", "regex"), ["word_count"])
+
+    def test_unknown_tag(self):
+        with self.assertRaisesRegex(ValueError, "^no benchmarks match --tag nosuch$"):
+            select("--tag", "nosuch")
+        with self.assertRaisesRegex(
+                ValueError, "^no benchmarks match --filter regex_\\* --tag sort$"):
+            select("--filter", "regex_*", "--tag", "sort")
+
+    def test_list_rejects_unknown_tag(self):
+        status, out, err = cli("list", "--tag", "nosuch")
+        self.assertEqual(status, 2)
+        self.assertEqual(out, "")
+        self.assertIn("no benchmarks match --tag nosuch", err)
+        status, out, _ = cli("list", "--filter", "regex_*", "--tag", "regex")
+        self.assertEqual(status, 0)
+        self.assertIn("regex_count", out)
+        self.assertNotIn("sort_u32", out)
+
+
+if __name__ == "__main__":
+    unittest.main()
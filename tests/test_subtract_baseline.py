@@ -0,0 +1,97 @@
+import contextlib
+import io
+import tempfile
+import unittest
+from pathlib import Path
+
+import run_benchmarks
+from run_benchmarks import BenchmarkResult, compare_results, load_results
+from test_results import cli, record, write_results
+
+# Each implementation's noop median, as --subtract-baseline measures it.
+BASELINES = {"Rust": 0.5, "X07": 2.0}
+
+
+def subtracted(benchmark: str, language: str, times: list[float], **fields):
+    return record(benchmark, language, times, startup_baseline_ms=BASELINES.get(language),
+                  **fields)
+
+
+class AdjustedMedianTest(unittest.TestCase):
+    def test_per_implementation(self):
+        rust = subtracted("sum_bytes", "Rust", [3.0])
+        x07 = subtracted("sum_bytes", "X07", [3.0])
+        self.assertEqual((rust.median_time_ms, rust.adjusted_median_time_ms), (3.0, 2.5))
+        self.assertEqual((x07.median_time_ms, x07.adjusted_median_time_ms), (3.0, 1.0))
+        self.assertEqual((rust.compared_time_ms, x07.compared_time_ms), (2.5, 1.0))
+
+    def test_clamped_at_zero(self):
+        # Faster than the implementation's own noop: noise, not negative time.
+        x07 = subtracted("fibonacci", "X07", [1.5], input_bytes=8)
+        self.assertEqual(x07.adjusted_median_time_ms, 0.0)
+        self.assertEqual(x07.compared_time_ms, 0.0)
+
+    def test_no_baseline(self):
+        # No noop for C: its median is reported as is.
+        c = subtracted("sum_bytes", "C", [3.0])
+        self.assertIsNone(c.startup_baseline_ms)
+        self.assertIsNone(c.adjusted_median_time_ms)
+        self.assertEqual(c.compared_time_ms, 3.0)
+
+    def test_compare_uses_adjusted_medians(self):
+        # Raw 3.0 -> 3.1 ms is +3.3%; less 2.0 ms of startup, 1.0 -> 1.1 is +10%.
+        cmp = compare_results([subtracted("sum_bytes", "X07", [3.0])],
+                              [subtracted("sum_bytes", "X07", [3.1])], 5.0,
+                              latency_benchmarks=frozenset({"sum_bytes"}))
+        [entry] = cmp.entries
+        self.assertAlmostEqual(entry.change_pct, 10.0)
+        self.assertTrue(entry.regressed)
+
+    def test_table_shows_raw_and_adjusted(self):
+        results = {"sum_bytes": [
+            BenchmarkResult(language=language, benchmark="sum_bytes", times_ms=[3.0],
+                            startup_baseline_ms=BASELINES.get(language))
+            for language in ("Rust", "X07", "C")]}
+        out = io.StringIO()
+        with contextlib.redirect_stdout(out):
+            run_benchmarks.print_results(results, 100, startup_baselines=BASELINES)
+        lines = out.getvalue().splitlines()
+        self.assertIn("Startup baseline (noop median): Rust 0.50 ms, X07 2.00 ms", lines)
+        rows = {line.split()[0]: line.split()[1:4] for line in lines
+                if line.startswith(("Rust ", "X07 ", "C "))}
+        # Mean, median, then adjusted.
+        self.assertEqual(rows, {"Rust": ["3.00", "3.00", "2.50"], "X07": ["3.00", "3.00", "1.00"],
+                                "C": ["3.00", "3.00", "-"]})
+
+
+class SubtractedResultsFileTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+
+    def write(self, name: str, subtract: bool) -> Path:
+        records = [subtracted("sum_bytes", lang, [3.0]) if subtract
+                   else record("sum_bytes", lang, [3.0]) for lang in ("Rust", "X07")]
+        path = self.dir / name
+        write_results(path, records, {"subtract_baseline": subtract})
+        return path
+
+    def test_round_trip(self):
+        config, records = load_results(self.write("run.json", True))
+        self.assertTrue(config["subtract_baseline"])
+        self.assertEqual([(r.startup_baseline_ms, r.adjusted_median_time_ms) for r in records],
+                         [(0.5, 2.5), (2.0, 1.0)])
+
+    def test_modes_are_not_mixed(self):
+        raw, adjusted = str(self.write("raw.json", False)), str(self.write("adj.json", True))
+        for command in ("compare", "report"):
+            with self.subTest(command):
+                status, out, err = cli(command, raw, adjusted)
+                self.assertEqual((status, out), (2, ""))
+                self.assertIn("recorded with --subtract-baseline and the other without", err)
+                self.assertEqual(cli(command, adjusted, adjusted)[0], 0)
+
+
+if __name__ == "__main__":
+    unittest.main()
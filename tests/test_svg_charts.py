@@ -0,0 +1,192 @@
+import tempfile
+import unittest
+import xml.etree.ElementTree as ET
+from pathlib import Path
+
+import svg_charts
+from test_results import cli, record, write_results
+
+SVG = "{http://www.w3.org/2000/svg}"
+
+
+def parse(svg: str) -> ET.Element:
+    """svg as XML; fails the test if it isn't well formed."""
+    root = ET.fromstring(svg)
+    assert root.tag == f"{SVG}svg", root.tag
+    return root
+
+
+def find(root: ET.Element, tag: str, cls: str | None = None) -> list[ET.Element]:
+    return [e for e in root.iter(f"{SVG}{tag}") if cls is None or e.get("class") == cls]
+
+
+def texts(root: ET.Element) -> list[str]:
+    return [e.text for e in find(root, "text")]
+
+
+class BarChartTest(unittest.TestCase):
+    BARS = [("X07", 12.5), ("Rust", 10.0), ("C", 5.0)]
+
+    def test_structure(self):
+        root = parse(svg_charts.bar_chart("sum_bytes", self.BARS, "median time (ms)"))
+        bars = find(root, "rect", "bar")
+        self.assertEqual([b.get("data-label") for b in bars], ["X07", "Rust", "C"])
+        self.assertEqual([float(b.get("data-value")) for b in bars], [12.5, 10.0, 5.0])
+        # Lengths are relative to the largest bar.
+        widths = [float(b.get("width")) for b in bars]
+        self.assertAlmostEqual(widths[1] / widths[0], 0.8)
+        self.assertAlmostEqual(widths[2] / widths[0], 0.4)
+        self.assertEqual(bars[0].get("fill"), svg_charts.LANGUAGE_COLORS["X07"])
+
+        self.assertEqual(root.find(f"{SVG}title").text, "sum_bytes")
+        self.assertEqual([e.text for e in find(root, "text", "axis-label")],
+                         ["median time (ms)"])
+        # The axis runs from zero to the largest value.
+        for tick in ("0", "6.25", "12.5"):
+            self.assertIn(tick, texts(root))
+
+    def test_notes(self):
+        root = parse(svg_charts.bar_chart("t", self.BARS, "ms", notes={"Rust": "(+4.0%)"}))
+        self.assertIn("10.0 (+4.0%)", texts(root))
+        self.assertIn("12.5", texts(root))
+
+    def test_escaping(self):
+        name = 'a<b & "c"'
+        root = parse(svg_charts.bar_chart(f"{name} chart", [(name, 1.0)], "ms <&>"))
+        self.assertEqual(root.find(f"{SVG}title").text, f"{name} chart")
+        self.assertEqual(find(root, "rect", "bar")[0].get("data-label"), name)
+        self.assertIn(name, texts(root))
+        self.assertEqual(find(root, "text", "axis-label")[0].text, "ms <&>")
+
+    def test_no_bars(self):
+        root = parse(svg_charts.bar_chart("empty", [], "ms"))
+        self.assertEqual(find(root, "rect", "bar"), [])
+
+    def test_all_zero(self):
+        root = parse(svg_charts.bar_chart("zero", [("X07", 0.0), ("Rust", 0.0)], "ms"))
+        self.assertEqual([b.get("width") for b in find(root, "rect", "bar")], ["0.00", "0.00"])
+
+
+class LineChartTest(unittest.TestCase):
+    SERIES = {
+        "X07": [(1024.0, 4.0), (256.0, 1.0), (4096.0, 16.0)],
+        "Rust": [(256.0, 0.5), (1024.0, 2.0), (4096.0, 8.0)],
+    }
+
+    def test_structure(self):
+        root = parse(svg_charts.line_chart("sweep", self.SERIES, "size (KiB)", "ms"))
+        lines = find(root, "polyline", "series")
+        self.assertEqual([p.get("data-label") for p in lines], ["X07", "Rust"])
+        self.assertEqual(len(find(root, "circle")), 6)
+        self.assertEqual([e.text for e in find(root, "text", "axis-label")], ["size (KiB)", "ms"])
+        self.assertEqual([e.get("fill") for e in find(root, "rect", "legend")],
+                         [svg_charts.LANGUAGE_COLORS["X07"], svg_charts.LANGUAGE_COLORS["Rust"]])
+
+        # Points are drawn in x order, and each 4x step in x is an equal step
+        # along the log2 axis.
+        xs = [float(pair.split(",")[0]) for pair in lines[0].get("points").split()]
+        self.assertEqual(xs, sorted(xs))
+        self.assertAlmostEqual(xs[1] - xs[0], xs[2] - xs[1])
+
+    def test_escaping(self):
+        root = parse(svg_charts.line_chart("a&b", {"<X>": [(1.0, 1.0)]}, "x<", "y&"))
+        self.assertEqual(find(root, "polyline", "series")[0].get("data-label"), "<X>")
+        self.assertIn("<X>", texts(root))
+        self.assertEqual([e.text for e in find(root, "text", "axis-label")], ["x<", "y&"])
+
+
+class LogTicksTest(unittest.TestCase):
+    def test_docstring_examples(self):
+        self.assertEqual(svg_charts.log_ticks(1, 100), [1, 2, 5, 10, 20, 50, 100])
+        self.assertEqual(svg_charts.log_ticks(1, 10**6), [10.0**e for e in range(7)])
+        self.assertEqual(svg_charts.log_ticks(3, 4), [3, 4])
+
+
+class LogLogChartTest(unittest.TestCase):
+    # Each point's bar runs from half to twice its y.
+    SERIES = {"Rust": [(x, x, x / 2, x * 2) for x in (100.0, 1.0, 10.0)]}
+
+    def chart(self, series=None) -> ET.Element:
+        return parse(svg_charts.log_log_chart("scaling", series or self.SERIES, "size", "ms"))
+
+    def centres(self, root: ET.Element) -> list[tuple[float, float]]:
+        return [(float(c.get("cx")), float(c.get("cy"))) for c in find(root, "circle", "point")]
+
+    def test_log_scaling(self):
+        (x1, y1), (x10, y10), (x100, y100) = self.centres(self.chart())
+        # Equal ratios are equal distances, and larger y is higher up.
+        self.assertAlmostEqual(x10 - x1, x100 - x10)
+        self.assertAlmostEqual(y1 - y10, y10 - y100)
+        self.assertGreater(x10, x1)
+        self.assertGreater(y1, y10)
+
+    def test_error_bars(self):
+        root = self.chart()
+        bars = find(root, "line", "error-bar")
+        self.assertEqual(len(bars), 3)
+        for (cx, cy), bar in zip(self.centres(root), bars):
+            self.assertEqual(float(bar.get("x1")), cx)
+            self.assertEqual(float(bar.get("x2")), cx)
+            low, high = float(bar.get("y1")), float(bar.get("y2"))
+            # From y_low below the point to y_high above it; half and twice
+            # are the same distance away on a log axis.
+            self.assertGreater(low, cy)
+            self.assertLess(high, cy)
+            self.assertAlmostEqual(low - cy, cy - high, places=1)
+
+    def test_axes(self):
+        root = self.chart()
+        self.assertEqual([e.text for e in find(root, "text", "axis-label")], ["size", "ms"])
+        ticks = [e.text for e in find(root, "text", "tick")]
+        self.assertEqual(ticks[:3], ["1.00", "10.0", "100"])  # the measured x values
+        self.assertIn("1.00", ticks[3:])
+        self.assertIn("100", ticks[3:])
+
+    def test_single_point(self):
+        root = self.chart({"a<b": [(4.0, 2.0, 1.0, 3.0)]})
+        self.assertEqual(find(root, "polyline", "series"), [])
+        self.assertEqual(len(find(root, "circle", "point")), 1)
+        self.assertEqual([e.text for e in find(root, "text", "note")], ["a<b 2.00"])
+
+
+class PlotCommandTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        # Samples m, 2m and 3m, with m growing with the size.
+        records = [record("sum_bytes", language, [kb * scale * k for k in (1, 2, 3)],
+                          input_bytes=kb * 1024)
+                   for language, scale in (("Rust", 1.0), ("X07", 2.0))
+                   for kb in (1, 4, 16)]
+        records.append(record("sum_bytes", "C", [], input_bytes=1024, success=False))
+        write_results(self.dir / "sweep.json", records)
+
+    def test_plot(self):
+        status, _, err = cli("plot", str(self.dir / "sweep.json"), "--bench", "sum_bytes",
+                             "--out", str(self.dir / "plot.svg"))
+        self.assertEqual(status, 0)
+        self.assertIn("wrote", err)
+        root = parse((self.dir / "plot.svg").read_text())
+        # The failed C run is left out; each size is one point per language.
+        self.assertEqual([p.get("data-label") for p in find(root, "polyline", "series")],
+                         ["Rust", "X07"])
+        self.assertEqual(len(find(root, "circle", "point")), 6)
+        self.assertEqual([e.text for e in find(root, "text", "tick")][:3],
+                         ["1KiB", "4KiB", "16KiB"])
+
+        # Bars span the fastest to the slowest sample: m to 3m around the
+        # median 2m, so the point sits log(2)/log(3) of the way up the bar.
+        for circle, bar in zip(find(root, "circle", "point"), find(root, "line", "error-bar")):
+            low, high, cy = float(bar.get("y1")), float(bar.get("y2")), float(circle.get("cy"))
+            self.assertAlmostEqual((low - cy) / (low - high), 0.6309, places=2)
+
+    def test_unknown_benchmark(self):
+        status, _, err = cli("plot", str(self.dir / "sweep.json"), "--bench", "nosuch",
+                             "--out", "-")
+        self.assertEqual(status, 2)
+        self.assertIn("no results for 'nosuch' (the file has: sum_bytes)", err)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,119 @@
+import argparse
+import contextlib
+import io
+import tempfile
+import unittest
+from pathlib import Path
+from unittest import mock
+
+import run_benchmarks
+from run_benchmarks import (
+    BenchmarkResult, InputData, cached_input_data, compare_results, load_results, parse_sweep,
+)
+from test_results import record, write_results
+
+SWEEP = [1, 64, 1024]
+
+
+def result(language: str, size_kb: int, ms: float) -> BenchmarkResult:
+    return BenchmarkResult(language=language, benchmark="sum_bytes", times_ms=[ms],
+                           input_bytes=size_kb * 1024, size_kb=size_kb)
+
+
+class ParseSweepTest(unittest.TestCase):
+    def test_sizes(self):
+        self.assertEqual(parse_sweep("1KiB,64KiB,1MiB,16MiB,256MiB"),
+                         [1, 64, 1024, 16 << 10, 256 << 10])
+        self.assertEqual(parse_sweep(" 100 , 2m,"), [100, 2048])
+
+    def test_invalid(self):
+        for text in ("", ",", "1KiB,lots", "1XB"):
+            with self.subTest(text):
+                with self.assertRaises(argparse.ArgumentTypeError):
+                    parse_sweep(text)
+
+
+class SweepResultsTest(unittest.TestCase):
+    """A sweep's results file keys every record by its size."""
+
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+
+    def sweep(self, rust_ms: list[float]) -> list[run_benchmarks.ResultRecord]:
+        return [record("sum_bytes", language, [ms], size_kb * 1024)
+                for size_kb, rust in zip(SWEEP, rust_ms)
+                for language, ms in (("Rust", rust), ("X07", 2 * rust))]
+
+    def test_sizes_round_trip(self):
+        write_results(self.dir / "sweep.json", self.sweep([0.1, 1.0, 10.0]), {"sweep": SWEEP})
+        config, records = load_results(self.dir / "sweep.json")
+        self.assertEqual(config["sweep"], SWEEP)
+        self.assertEqual([(r.language, r.size_kb, r.input_bytes) for r in records[:2]],
+                         [("Rust", 1, 1024), ("X07", 1, 1024)])
+        self.assertEqual([r.size_kb for r in records], [1, 1, 64, 64, 1024, 1024])
+
+    def test_compare_matches_by_size(self):
+        cmp = compare_results(self.sweep([0.1, 1.0, 10.0]), self.sweep([0.1, 1.2, 10.0]), 5.0)
+        self.assertEqual([e.benchmark for e in cmp.entries if e.language == "Rust"],
+                         ["sum_bytes@1KiB", "sum_bytes@1MiB", "sum_bytes@64KiB"])
+        self.assertEqual([(e.benchmark, e.language) for e in cmp.regressions],
+                         [("sum_bytes@64KiB", "Rust"), ("sum_bytes@64KiB", "X07")])
+
+    def test_table_has_a_row_per_size(self):
+        # X07 at half of Rust's time at 1KiB, a quarter at 64KiB.
+        results = {"sum_bytes": [result("Rust", 1, 1.0), result("X07", 1, 0.5),
+                                 result("Rust", 64, 4.0), result("X07", 64, 1.0)]}
+        out = io.StringIO()
+        with contextlib.redirect_stdout(out):
+            run_benchmarks.print_results(results, 100, sweep=[1, 64])
+            run_benchmarks.print_summary_table(results, sweep=[1, 64])
+        lines = out.getvalue().splitlines()
+        self.assertIn("Performance Benchmark Results (input sizes: 1KiB, 64KiB, ", lines[2])
+        rows = [line.split()[:2] for line in lines if line.startswith(("1KiB", "64KiB"))]
+        self.assertEqual(rows, [["1KiB", "Rust"], ["1KiB", "X07"],
+                                ["64KiB", "Rust"], ["64KiB", "X07"]])
+        summary = [line.split() for line in lines if line.startswith("sum_bytes ")]
+        self.assertEqual(summary, [["sum_bytes", "1KiB", "1.00x", "N/A", "0.50x", "N/A"],
+                                   ["sum_bytes", "64KiB", "1.00x", "N/A", "0.25x", "N/A"]])
+
+
+class InputCacheTest(unittest.TestCase):
+    """Generated inputs are memoized by benchmark, size, seed and params."""
+
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.cache = Path(tmp.name) / "inputs"
+        self.generated = []
+
+        def generate(benchmark, size_kb, seed, params):
+            self.generated.append((benchmark, size_kb, seed))
+            return InputData(name=benchmark, data=f"{benchmark}/{size_kb}/{seed}".encode(),
+                             size_kb=size_kb)
+
+        patch = mock.patch.object(run_benchmarks, "generate_input_data", side_effect=generate)
+        patch.start()
+        self.addCleanup(patch.stop)
+
+    def test_hit(self):
+        first = cached_input_data(self.cache, "sum_bytes", 64, 42, {})
+        again = cached_input_data(self.cache, "sum_bytes", 64, 42, {})
+        self.assertEqual(self.generated, [("sum_bytes", 64, 42)])
+        self.assertEqual((again.data, again.size_kb), (first.data, 64))
+        self.assertEqual(len(list(self.cache.iterdir())), 1)
+
+    def test_key(self):
+        for args in (("sum_bytes", 64, 42, {}), ("sum_bytes", 1, 42, {}),
+                     ("sum_bytes", 64, 7, {}), ("word_count", 64, 42, {}),
+                     ("sum_bytes", 64, 42, {"density": 0.5})):
+            cached_input_data(self.cache, *args)
+        self.assertEqual(len(self.generated), 5)
+        self.assertEqual(len(list(self.cache.iterdir())), 5)
+        cached_input_data(self.cache, "sum_bytes", 64, 42, {"density": 0.5})
+        self.assertEqual(len(self.generated), 5)
+
+
+if __name__ == "__main__":
+    unittest.main()
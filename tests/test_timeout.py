@@ -0,0 +1,80 @@
+import shutil
+import sys
+import tempfile
+import time
+import unittest
+from pathlib import Path
+
+import run_benchmarks
+from run_benchmarks import BenchmarkTimeout, InputData, _run_child, run_benchmark, set_child_timeout
+
+# Starts a grandchild in its process group, records its pid, then both
+# sleep forever.
+SLEEPER = """
+import subprocess, sys, time
+child = subprocess.Popen([sys.executable, "-c", "import time; time.sleep(3600)"])
+with open(sys.argv[1], "w") as f:
+    f.write(str(child.pid))
+time.sleep(3600)
+"""
+
+C_SLEEPER = """#include <unistd.h>
+int main(void) { for (;;) pause(); }
+"""
+
+
+def gone(pid: int) -> bool:
+    """Whether pid has exited (a zombie awaiting its reaper counts as gone)."""
+    try:
+        status = Path(f"/proc/{pid}/status").read_text()
+    except FileNotFoundError:
+        return True
+    return "\nState:\tZ" in status
+
+
+class TimeoutTest(unittest.TestCase):
+    def setUp(self):
+        tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(tmp.cleanup)
+        self.dir = Path(tmp.name)
+        set_child_timeout(1.0)
+        self.addCleanup(set_child_timeout, run_benchmarks.DEFAULT_TIMEOUT_S)
+
+    @unittest.skipUnless(Path("/proc/self/status").exists(), "needs /proc")
+    def test_process_group_is_killed(self):
+        pid_file = self.dir / "grandchild.pid"
+        started = time.monotonic()
+        with self.assertRaises(BenchmarkTimeout) as cm:
+            _run_child([sys.executable, "-c", SLEEPER, str(pid_file)], input=b"")
+        self.assertLess(time.monotonic() - started, 30)
+        self.assertEqual(cm.exception.seconds, 1.0)
+        self.assertRegex(str(cm.exception), r"^timed out after 1s: python")
+
+        grandchild = int(pid_file.read_text())
+        # SIGKILL is delivered asynchronously; give the kernel a moment.
+        deadline = time.monotonic() + 5
+        while not gone(grandchild) and time.monotonic() < deadline:
+            time.sleep(0.05)
+        self.assertTrue(gone(grandchild), f"grandchild {grandchild} survived the timeout")
+
+    @unittest.skipUnless(shutil.which("cc"), "cc not on PATH")
+    def test_result_is_recorded_as_a_timeout(self):
+        source = self.dir / "sleeper.c"
+        source.write_text(C_SLEEPER)
+        work = self.dir / "work"
+        work.mkdir()
+        [result] = run_benchmark(
+            "sleeper", InputData(name="sleeper", data=b"", size_kb=0), self.dir / "no-x07",
+            self.dir, work, iterations=3, warmup=0, implementations={"C": source})
+        self.assertFalse(result.success)
+        self.assertTrue(result.timed_out)
+        self.assertEqual(result.times_ms, [])
+        self.assertEqual(result.error, "timed out after 1s: sleeper_c")
+
+        record = run_benchmarks.ResultRecord.from_result(result, "default")
+        self.assertTrue(record.timed_out)
+        self.assertFalse(record.success)
+
+
+if __name__ == "__main__":
+    unittest.main()
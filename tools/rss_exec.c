@@ -0,0 +1,58 @@
+/*
+ * rss_exec OUT CMD [ARGS...]
+ *
+ * Runs CMD, waits for it with wait4, and writes its peak RSS in KiB to the
+ * file OUT. Exits with CMD's exit status (128 + signal if it was killed).
+ *
+ * The runner can't take ru_maxrss from its own children directly: Linux
+ * folds the RSS of the process that called exec into the result, so every
+ * program launched from Python would report at least the interpreter's
+ * size. Forking from this small process keeps the measurement honest.
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <sys/resource.h>
+#include <sys/types.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+int main(int argc, char **argv) {
+    if (argc < 3) {
+        fprintf(stderr, "usage: rss_exec OUT CMD [ARGS...]\n");
+        return 125;
+    }
+
+    pid_t pid = fork();
+    if (pid < 0) {
+        perror("rss_exec: fork");
+        return 125;
+    }
+    if (pid == 0) {
+        execvp(argv[2], argv + 2);
+        perror("rss_exec: exec");
+        _exit(127);
+    }
+
+    int status;
+    struct rusage ru;
+    if (wait4(pid, &status, 0, &ru) < 0) {
+        perror("rss_exec: wait4");
+        return 125;
+    }
+
+#ifdef __APPLE__
+    long rss_kb = (long)(ru.ru_maxrss / 1024); /* bytes on macOS */
+#else
+    long rss_kb = (long)ru.ru_maxrss; /* KiB on Linux and the BSDs */
+#endif
+    FILE *out = fopen(argv[1], "w");
+    if (out != NULL) {
+        fprintf(out, "%ld\n", rss_kb);
+        fclose(out);
+    }
+
+    if (WIFSIGNALED(status)) {
+        return 128 + WTERMSIG(status);
+    }
+    return WEXITSTATUS(status);
+}